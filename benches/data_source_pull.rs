@@ -0,0 +1,84 @@
+// Compares loading task events one query per task (how
+// `DataSource::pull` used to work) with the single grouped query. Runs
+// inside a transaction that is rolled back, so any database will do.
+extern crate diesel;
+extern crate task_manager;
+
+use diesel::connection::Connection;
+use diesel::pg::PgConnection;
+use diesel::result::Error;
+use std::env;
+use std::time::{Duration, Instant};
+use task_manager::components::{DataSource, ScoringStrategy};
+use task_manager::config::ScoringConfig;
+use task_manager::models::Task;
+use task_manager::queries::{task as task_queries, task_event as te_queries};
+use task_manager::storage::PgStorage;
+
+const NUM_TASKS: usize = 300;
+const EVENTS_PER_TASK: usize = 50;
+const NUM_RUNS: u32 = 10;
+
+fn seed(connection: &PgConnection) {
+  for idx in 0..NUM_TASKS {
+    let title = format!("task {}", idx);
+    let task = task_queries::create(&title, connection).unwrap();
+    for _ in 0..EVENTS_PER_TASK {
+      te_queries::record_task_effort(task.id, connection).unwrap();
+    }
+  }
+}
+
+fn time<F: Fn()>(f: F) -> Duration {
+  let start = Instant::now();
+  for _ in 0..NUM_RUNS {
+    f();
+  }
+  start.elapsed() / NUM_RUNS
+}
+
+fn report(name: &str, duration: Duration) {
+  let ms = duration.as_secs() as f64 * 1000.0
+    + f64::from(duration.subsec_nanos()) / 1_000_000.0;
+  println!("{:24} {:>10.2} ms", name, ms);
+}
+
+fn main() {
+  let database_url =
+    env::var("DATABASE_URL").expect("Set DATABASE_URL to run benchmarks");
+  let storage = PgStorage::establish(&database_url).unwrap();
+  let connection = storage.connection();
+
+  connection.test_transaction::<_, Error, _>(|| {
+    seed(connection);
+    let tasks: Vec<Task> =
+      task_queries::all_available_to_perform(connection).unwrap();
+    println!(
+      "{} tasks, {} events each, mean of {} runs",
+      tasks.len(),
+      EVENTS_PER_TASK,
+      NUM_RUNS
+    );
+
+    report(
+      "query per task",
+      time(|| {
+        for task in &tasks {
+          te_queries::task_events(task, connection).unwrap();
+        }
+      }),
+    );
+    report(
+      "grouped query",
+      time(|| {
+        te_queries::task_events_by_task(&tasks, connection).unwrap();
+      }),
+    );
+
+    let data_source = DataSource::new(ScoringConfig::default());
+    data_source.set_scoring_strategy(ScoringStrategy::default());
+    report("DataSource::pull", time(|| data_source.pull(&storage).unwrap()));
+
+    Ok(())
+  });
+}
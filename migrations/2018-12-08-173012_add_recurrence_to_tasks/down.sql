@@ -0,0 +1,13 @@
+BEGIN TRANSACTION;
+  ALTER TABLE
+    tasks
+  DROP COLUMN
+    recurrence_value;
+
+  ALTER TABLE
+    tasks
+  DROP COLUMN
+    recurrence_type;
+
+  DROP TYPE task_recurrence_type;
+END TRANSACTION;
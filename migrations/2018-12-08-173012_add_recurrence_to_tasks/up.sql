@@ -0,0 +1,20 @@
+BEGIN TRANSACTION;
+  CREATE TYPE task_recurrence_type AS ENUM (
+    'daily',
+    'every_n_days',
+    'monthly',
+    'weekly'
+  );
+
+  -- NULL means the task does not recur.
+  ALTER TABLE
+    tasks
+  ADD COLUMN
+    recurrence_type task_recurrence_type;
+
+  -- Argument of the rule: N days, day of month, or weekday bitmask.
+  ALTER TABLE
+    tasks
+  ADD COLUMN
+    recurrence_value INTEGER NOT NULL DEFAULT 0;
+END TRANSACTION;
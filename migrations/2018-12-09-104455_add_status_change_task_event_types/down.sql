@@ -0,0 +1,3 @@
+-- There is no safe way to revert this transaction without destroying
+-- old events.
+ABORT;
@@ -0,0 +1,63 @@
+-- Same dance as when adding age_reset_requested: Postgres won't let us
+-- add enum values inside a transaction, so we swap in a new type.
+BEGIN TRANSACTION;
+  -- Create new version of task_event_type
+  CREATE TYPE task_event_type_new AS ENUM (
+    'task_effort_recorded',
+    'delay_requested',
+    'age_reset_requested',
+    'task_completed',
+    'task_abandoned',
+    'task_reopened'
+  );
+
+  -- Add it as a new column with a default value.
+  ALTER TABLE
+    task_events
+  ADD COLUMN
+    event_type_new task_event_type_new NOT NULL DEFAULT 'task_effort_recorded';
+
+  -- Copy over all values. Past status changes were never recorded, so
+  -- there is nothing to backfill.
+  UPDATE
+    task_events
+  SET
+    event_type_new = CASE
+      WHEN event_type = 'task_effort_recorded' THEN
+        'task_effort_recorded'::task_event_type_new
+      WHEN event_type = 'delay_requested' THEN
+        'delay_requested'::task_event_type_new
+      WHEN event_type = 'age_reset_requested' THEN
+        'age_reset_requested'::task_event_type_new
+      ELSE
+        -- This should never happen.
+        NULL
+      END;
+
+  -- Drop the default.
+  ALTER TABLE
+    task_events
+  ALTER COLUMN
+    event_type_new DROP DEFAULT;
+
+  -- Drop the old column.
+  ALTER TABLE
+    task_events
+  DROP COLUMN
+    event_type;
+
+  -- Rename the new column.
+  ALTER TABLE
+    task_events
+  RENAME COLUMN
+    event_type_new TO event_type;
+
+  -- Drop the old type.
+  DROP TYPE
+    task_event_type;
+
+  -- Rename the new type.
+  ALTER TYPE
+    task_event_type_new RENAME TO task_event_type;
+
+END TRANSACTION;
@@ -0,0 +1,3 @@
+BEGIN TRANSACTION;
+  DROP TABLE settings;
+END TRANSACTION;
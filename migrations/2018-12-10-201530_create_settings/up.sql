@@ -0,0 +1,7 @@
+BEGIN TRANSACTION;
+  -- Simple key/value store for preferences that outlive a session.
+  CREATE TABLE settings (
+    name VARCHAR PRIMARY KEY,
+    value VARCHAR NOT NULL
+  );
+END TRANSACTION;
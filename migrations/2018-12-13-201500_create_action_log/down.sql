@@ -0,0 +1,4 @@
+BEGIN TRANSACTION;
+  DROP TABLE action_log;
+  DROP TYPE action_log_kind;
+END TRANSACTION;
@@ -0,0 +1,28 @@
+BEGIN TRANSACTION;
+  CREATE TYPE action_log_kind AS ENUM (
+    'create_task',
+    'record_task_effort',
+    'request_task_age_reset',
+    'request_task_delay',
+    'update_duration',
+    'update_priority',
+    'update_recurrence',
+    'update_requires_internet',
+    'update_status',
+    'update_task_title'
+  );
+
+  -- Task actions, so they can still be undone after a restart. Undone
+  -- entries are kept until a new action makes them impossible to redo.
+  CREATE TABLE action_log (
+    id SERIAL PRIMARY KEY,
+    kind ACTION_LOG_KIND NOT NULL,
+    task_id INTEGER NOT NULL REFERENCES tasks (id),
+    old_value VARCHAR,
+    new_value VARCHAR,
+    task_event_id INTEGER REFERENCES task_events (id),
+    spawned_task_id INTEGER REFERENCES tasks (id),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    undone BOOLEAN NOT NULL DEFAULT FALSE
+  );
+END TRANSACTION;
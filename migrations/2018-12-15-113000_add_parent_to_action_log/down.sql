@@ -0,0 +1,4 @@
+ALTER TABLE
+  action_log
+DROP COLUMN
+  parent_id;
@@ -0,0 +1,15 @@
+BEGIN TRANSACTION;
+  ALTER TABLE
+    action_log
+  ADD COLUMN
+    parent_id INTEGER REFERENCES action_log (id);
+
+  -- Until now the log was a straight line.
+  UPDATE
+    action_log
+  SET
+    parent_id = (
+      SELECT MAX(older.id) FROM action_log AS older
+      WHERE older.id < action_log.id
+    );
+END TRANSACTION;
@@ -0,0 +1,3 @@
+BEGIN TRANSACTION;
+  DROP TABLE filter_presets;
+END TRANSACTION;
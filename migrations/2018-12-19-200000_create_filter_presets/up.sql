@@ -0,0 +1,7 @@
+BEGIN TRANSACTION;
+  -- Named filter queries to switch between, e.g. "on the train".
+  CREATE TABLE filter_presets (
+    name VARCHAR PRIMARY KEY,
+    query VARCHAR NOT NULL
+  );
+END TRANSACTION;
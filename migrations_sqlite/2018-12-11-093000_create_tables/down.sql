@@ -0,0 +1,3 @@
+DROP TABLE settings;
+DROP TABLE task_events;
+DROP TABLE tasks;
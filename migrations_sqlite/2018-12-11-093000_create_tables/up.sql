@@ -0,0 +1,52 @@
+-- SQLite has no enum types, so enums are text checked against the same
+-- values as the Postgres enums. Times are stored as UTC.
+CREATE TABLE tasks (
+  id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+  title VARCHAR(1024) NOT NULL,
+  status TEXT NOT NULL CHECK (status IN (
+    'abandoned',
+    'available_to_perform',
+    'completed'
+  )),
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  requires_internet BOOLEAN NOT NULL DEFAULT FALSE,
+  priority TEXT NOT NULL DEFAULT 'low' CHECK (priority IN (
+    'high',
+    'medium',
+    'low'
+  )),
+  duration TEXT NOT NULL DEFAULT 'short' CHECK (duration IN (
+    'long',
+    'medium',
+    'short'
+  )),
+  destroyed BOOLEAN NOT NULL DEFAULT FALSE,
+  -- NULL means the task does not recur.
+  recurrence_type TEXT CHECK (recurrence_type IN (
+    'daily',
+    'every_n_days',
+    'monthly',
+    'weekly'
+  )),
+  recurrence_value INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE task_events (
+  id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+  task_id INTEGER NOT NULL REFERENCES tasks (id),
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  destroyed BOOLEAN NOT NULL DEFAULT FALSE,
+  event_type TEXT NOT NULL CHECK (event_type IN (
+    'task_effort_recorded',
+    'delay_requested',
+    'age_reset_requested',
+    'task_completed',
+    'task_abandoned',
+    'task_reopened'
+  ))
+);
+
+CREATE TABLE settings (
+  name VARCHAR PRIMARY KEY NOT NULL,
+  value VARCHAR NOT NULL
+);
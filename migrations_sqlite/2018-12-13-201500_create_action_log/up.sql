@@ -0,0 +1,23 @@
+-- Same as the Postgres table, with the enum as checked text.
+CREATE TABLE action_log (
+  id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+  kind TEXT NOT NULL CHECK (kind IN (
+    'create_task',
+    'record_task_effort',
+    'request_task_age_reset',
+    'request_task_delay',
+    'update_duration',
+    'update_priority',
+    'update_recurrence',
+    'update_requires_internet',
+    'update_status',
+    'update_task_title'
+  )),
+  task_id INTEGER NOT NULL REFERENCES tasks (id),
+  old_value VARCHAR,
+  new_value VARCHAR,
+  task_event_id INTEGER REFERENCES task_events (id),
+  spawned_task_id INTEGER REFERENCES tasks (id),
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  undone BOOLEAN NOT NULL DEFAULT FALSE
+);
@@ -0,0 +1,4 @@
+ALTER TABLE
+  action_log
+ADD COLUMN
+  batch_id INTEGER REFERENCES action_log (id);
@@ -0,0 +1,4 @@
+CREATE TABLE filter_presets (
+  name VARCHAR PRIMARY KEY NOT NULL,
+  query VARCHAR NOT NULL
+);
@@ -1,11 +1,22 @@
+use error::Result;
+
 pub trait ForwardAction {
-  fn execute(&mut self);
+  fn execute(&mut self) -> Result<()>;
 }
 
 pub trait ReversableAction: ForwardAction {
-  fn redo(&mut self) {
-    self.execute();
+  fn redo(&mut self) -> Result<()> {
+    self.execute()
   }
 
-  fn unexecute(&mut self);
+  fn unexecute(&mut self) -> Result<()>;
+
+  // A short lowercase summary for messages and the undo history, e.g.
+  // "recorded effort on #12".
+  fn description(&self) -> String;
+
+  // The `action_log` entry this action is saved as, if it is saved.
+  fn log_id(&self) -> Option<i32> {
+    None
+  }
 }
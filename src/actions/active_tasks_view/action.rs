@@ -1,12 +1,14 @@
 use super::{
-  execution_logic, saved_scroller_state::SavedTasksScrolerState,
+  execution_logic,
+  saved_scroller_state::{NewScrollerTaskId, SavedTasksScrolerState},
 };
 use actions::{
-  FiltererAction, ForwardAction, ReversableAction, ScrollAction,
-  TaskAction, TasksScrollAction, UndoBufferAction,
+  CompositeAction, FiltererAction, ForwardAction, ReversableAction,
+  ScrollAction, TaskAction, TasksScrollAction, UndoBufferAction,
 };
-use commands::ActiveTasksViewCommand;
+use commands::{ActiveTasksViewCommand, CommandLineCommand, TaskCommand};
 use components::{Scroller, UndoBuffer};
+use error::Result;
 use std::rc::{Rc, Weak};
 use views::ActiveTasksView;
 
@@ -25,6 +27,16 @@ pub enum ActiveTasksViewAction {
     ta: TaskAction,
     view: Weak<ActiveTasksView>,
     scroller_state: SavedTasksScrolerState,
+    // Set once the action is saved to the action log.
+    log_id: Option<i32>,
+  },
+  // The same task command for every task listed, undone as one.
+  TaskBatch {
+    ca: CompositeAction<TaskAction>,
+    view: Weak<ActiveTasksView>,
+    scroller_state: SavedTasksScrolerState,
+    // One per task, once saved to the action log.
+    log_ids: Vec<i32>,
   },
   TasksScroll {
     tsa: TasksScrollAction,
@@ -43,16 +55,39 @@ impl ActiveTasksViewAction {
     use self::ActiveTasksViewCommand::*;
 
     match cmd {
-      Filterer(fc) => {
-        fc.to_action(&view.ui, &view.filterer).map(|fa| {
-          ActiveTasksViewAction::Filterer {
-            fa,
-            view: Rc::downgrade(&view),
-            scroller_state: SavedTasksScrolerState::new(&view.scroller),
+      CommandLine => {
+        let line = view.read_command_line()?;
+        match CommandLineCommand::parse(&line) {
+          Err(message) => {
+            view.task_results_window.show_message(&message);
+            None
           }
-        })
+          Ok(CommandLineCommand::Filter(criteria)) => {
+            FiltererAction::update_criteria(criteria, &view.filterer)
+              .map(|fa| Self::filterer(fa, view))
+          }
+        }
       }
 
+      Filterer(fc) => match fc.to_action(
+        &view.ui,
+        &view.storage,
+        &view.filterer,
+      )? {
+        Err(message) => {
+          view.task_results_window.show_message(&message);
+          None
+        }
+        Ok(fa) => Some(Self::filterer(fa, view)),
+      },
+
+      // Opening views, toggling columns, saving presets, searching and
+      // waiting for the command to apply to all is handled by the view
+      // itself.
+      ApplyToAll | CycleScoringStrategy | DeleteFilterPreset
+      | OpenView(..) | RepeatSearch(..) | SaveFilterPreset
+      | SearchTitles | ToggleScoreBreakdown => None,
+
       Scroll(sc) => {
         let scroller = Rc::clone(&view.scroller) as Rc<Scroller>;
 
@@ -65,22 +100,29 @@ impl ActiveTasksViewAction {
       }
 
       Task(tc) => tc
-        .to_action(&view.ui, &view.connection, || {
+        .to_action(&view.ui, &view.storage, || {
           view.scroller.current_task()
         }).map(|ta| ActiveTasksViewAction::Task {
           ta,
           view: Rc::downgrade(&Rc::clone(view)),
           scroller_state: SavedTasksScrolerState::new(&view.scroller),
+          log_id: None,
         }),
 
-      TasksScroll(tsc) => {
-        tsc.to_action(&view.ui, &view.scroller).map(|tsa| {
-          ActiveTasksViewAction::TasksScroll {
-            tsa,
-            view: Rc::downgrade(&Rc::clone(view)),
-          }
-        })
-      }
+      TasksScroll(tsc) => match tsc.to_action(
+        &view.ui,
+        &view.scroller,
+        view.task_results_window.page_height() as i32,
+      )? {
+        Err(message) => {
+          view.task_results_window.show_message(&message);
+          None
+        }
+        Ok(tsa) => Some(ActiveTasksViewAction::TasksScroll {
+          tsa,
+          view: Rc::downgrade(&Rc::clone(view)),
+        }),
+      },
 
       UndoBuffer(ubc) => {
         let uba = ubc.to_action(&view.undo_buffer);
@@ -89,12 +131,87 @@ impl ActiveTasksViewAction {
     }
   }
 
+  fn filterer(
+    fa: FiltererAction,
+    view: &Rc<ActiveTasksView>,
+  ) -> ActiveTasksViewAction {
+    ActiveTasksViewAction::Filterer {
+      fa,
+      view: Rc::downgrade(view),
+      scroller_state: SavedTasksScrolerState::new(&view.scroller),
+    }
+  }
+
+  // `tc` for every task listed. Tasks it would not change are skipped.
+  pub fn prepare_batch(
+    tc: TaskCommand,
+    view: &Rc<ActiveTasksView>,
+  ) -> Option<ActiveTasksViewAction> {
+    if !tc.can_apply_to_many() {
+      view
+        .task_results_window
+        .show_message("Can't apply that to all tasks");
+      return None;
+    }
+
+    let tas: Vec<_> = view
+      .scroller
+      .tasks()
+      .into_iter()
+      .filter_map(|task| {
+        tc.to_action(&view.ui, &view.storage, || Some(task.clone()))
+      }).collect();
+    if tas.is_empty() {
+      view.task_results_window.show_message("Nothing to change");
+      return None;
+    }
+
+    Some(ActiveTasksViewAction::TaskBatch {
+      ca: CompositeAction::new(tas, &view.storage),
+      view: Rc::downgrade(view),
+      scroller_state: SavedTasksScrolerState::new(&view.scroller),
+      log_ids: vec![],
+    })
+  }
+
+  // Wraps task actions read back from the action log, one per entry
+  // logged together. Nothing was focused on before or after them in
+  // this session, so undo and redo just follow the task or go to the
+  // top.
+  pub fn restore_from_log(
+    mut tas: Vec<TaskAction>,
+    view: &Rc<ActiveTasksView>,
+    log_ids: Vec<i32>,
+  ) -> ActiveTasksViewAction {
+    let scroller_state = SavedTasksScrolerState {
+      old_id: None,
+      new_id: NewScrollerTaskId::Saved(None),
+    };
+
+    if tas.len() == 1 {
+      ActiveTasksViewAction::Task {
+        ta: tas.remove(0),
+        view: Rc::downgrade(view),
+        scroller_state,
+        log_id: log_ids.first().cloned(),
+      }
+    } else {
+      ActiveTasksViewAction::TaskBatch {
+        ca: CompositeAction::new(tas, &view.storage),
+        view: Rc::downgrade(view),
+        scroller_state,
+        log_ids,
+      }
+    }
+  }
+
   pub fn maybe_add_to_undo_buffer(self, undo_buffer: &UndoBuffer) {
     use self::ActiveTasksViewAction::*;
     match &self {
       Filterer { .. } => undo_buffer.append_action(Box::new(self)),
       Scroll { .. } => return,
       Task { .. } => undo_buffer.append_action(Box::new(self)),
+      TaskBatch { .. } => undo_buffer.append_action(Box::new(self)),
       TasksScroll { .. } => return,
       UndoBuffer { .. } => return,
     }
@@ -102,7 +219,7 @@ impl ActiveTasksViewAction {
 }
 
 impl ForwardAction for ActiveTasksViewAction {
-  fn execute(&mut self) {
+  fn execute(&mut self) -> Result<()> {
     use self::ActiveTasksViewAction::*;
     match self {
       Filterer {
@@ -110,32 +227,41 @@ impl ForwardAction for ActiveTasksViewAction {
         view,
         scroller_state,
       } => {
-        execution_logic::execute_filterer_action(
-          fa,
-          view,
-          scroller_state,
-        );
-      }
-      Scroll { sa, .. } => {
-        sa.execute();
+        execution_logic::execute_filterer_action(fa, view, scroller_state)
       }
+      Scroll { sa, .. } => sa.execute(),
       Task {
         ta,
         view,
         scroller_state,
-      } => {
-        execution_logic::execute_task_action(ta, view, scroller_state);
-      }
-      TasksScroll { tsa, .. } => {
-        tsa.execute();
+        log_id,
+      } => execution_logic::execute_task_action(
+        ta,
+        view,
+        scroller_state,
+        log_id,
+      ),
+      TaskBatch {
+        ca,
+        view,
+        scroller_state,
+        log_ids,
+      } => execution_logic::execute_task_batch_action(
+        ca,
+        view,
+        scroller_state,
+        log_ids,
+      ),
+      TasksScroll { tsa, view } => {
+        execution_logic::execute_tasks_scroll_action(tsa, view)
       }
       UndoBuffer { uba } => uba.execute(),
-    };
+    }
   }
 }
 
 impl ReversableAction for ActiveTasksViewAction {
-  fn redo(&mut self) {
+  fn redo(&mut self) -> Result<()> {
     use self::ActiveTasksViewAction::*;
     match self {
       Filterer {
@@ -143,7 +269,7 @@ impl ReversableAction for ActiveTasksViewAction {
         view,
         scroller_state,
       } => {
-        execution_logic::redo_filterer_action(fa, view, scroller_state);
+        execution_logic::redo_filterer_action(fa, view, scroller_state)
       }
       Scroll { .. } => {
         panic!("Should not try to redo a Scroll action.");
@@ -152,19 +278,34 @@ impl ReversableAction for ActiveTasksViewAction {
         ta,
         view,
         scroller_state,
-      } => {
-        execution_logic::redo_task_action(ta, view, scroller_state);
-      }
+        log_id,
+      } => execution_logic::redo_task_action(
+        ta,
+        view,
+        scroller_state,
+        *log_id,
+      ),
+      TaskBatch {
+        ca,
+        view,
+        scroller_state,
+        log_ids,
+      } => execution_logic::redo_task_batch_action(
+        ca,
+        view,
+        scroller_state,
+        log_ids,
+      ),
       TasksScroll { .. } => {
         panic!("Should not try to redo a TasksScroll action.");
       }
       UndoBuffer { .. } => {
         panic!("Should not try to redo an UndoBuffer action.")
       }
-    };
+    }
   }
 
-  fn unexecute(&mut self) {
+  fn unexecute(&mut self) -> Result<()> {
     use self::ActiveTasksViewAction::*;
     match self {
       Filterer {
@@ -176,7 +317,7 @@ impl ReversableAction for ActiveTasksViewAction {
           fa,
           view,
           scroller_state,
-        );
+        )
       }
       Scroll { .. } => {
         panic!("Should not try to unexecute a Scroll action.")
@@ -185,13 +326,24 @@ impl ReversableAction for ActiveTasksViewAction {
         ta,
         view,
         scroller_state,
-      } => {
-        execution_logic::unexecute_task_action(
-          ta,
-          view,
-          scroller_state,
-        );
-      }
+        log_id,
+      } => execution_logic::unexecute_task_action(
+        ta,
+        view,
+        scroller_state,
+        *log_id,
+      ),
+      TaskBatch {
+        ca,
+        view,
+        scroller_state,
+        log_ids,
+      } => execution_logic::unexecute_task_batch_action(
+        ca,
+        view,
+        scroller_state,
+        log_ids,
+      ),
       TasksScroll { .. } => {
         panic!("Should not try to unexecute a TasksScroll action.")
       }
@@ -200,4 +352,33 @@ impl ReversableAction for ActiveTasksViewAction {
       }
     }
   }
+
+  fn description(&self) -> String {
+    use self::ActiveTasksViewAction::*;
+    match self {
+      Filterer { fa, .. } => fa.description(),
+      Scroll { .. } => {
+        panic!("Should not try to describe a Scroll action.")
+      }
+      Task { ta, .. } => ta.description(),
+      TaskBatch { ca, .. } => ca.description(),
+      TasksScroll { .. } => {
+        panic!("Should not try to describe a TasksScroll action.")
+      }
+      UndoBuffer { .. } => {
+        panic!("Should not try to describe an UndoBuffer action.")
+      }
+    }
+  }
+
+  fn log_id(&self) -> Option<i32> {
+    match self {
+      ActiveTasksViewAction::Task { log_id, .. } => *log_id,
+      // Later entries point at the first one.
+      ActiveTasksViewAction::TaskBatch { log_ids, .. } => {
+        log_ids.first().cloned()
+      }
+      _ => None,
+    }
+  }
 }
@@ -2,12 +2,14 @@ use super::saved_scroller_state::{
   NewScrollerTaskId, SavedTasksScrolerState,
 };
 use actions::{
-  FiltererAction, ForwardAction, ReversableAction, TaskAction,
-  TaskUpdateAction,
+  CompositeAction, FiltererAction, ForwardAction, ReversableAction,
+  TaskAction, TaskUpdateAction, TasksScrollAction,
 };
 use components::{Scroller, TasksScroller};
-use models::End;
+use error::Result;
+use models::{End, NewActionLogEntry};
 use std::rc::Weak;
+use util::capitalize;
 use views::ActiveTasksView;
 
 fn jump_to_task_id_or_top(scroller: &TasksScroller, task_id: i32) {
@@ -27,31 +29,102 @@ fn jump_to_task_id_option_or_top(
   }
 }
 
+// The action log mirrors the undo buffer so task actions can still be
+// undone after a restart. By the time it is written the action has
+// already happened, so a failure here is only reported.
+fn log_task_action(
+  view: &ActiveTasksView,
+  ta: &TaskAction,
+  log_id: &mut Option<i32>,
+) {
+  let entry = NewActionLogEntry {
+    parent_id: view.undo_buffer.current_log_id(),
+    ..ta.log_entry()
+  };
+  match view.storage.log_action(&entry) {
+    Ok(entry) => *log_id = Some(entry.id),
+    Err(err) => view.task_results_window.show_error(&err),
+  }
+}
+
+fn mark_logged_action(
+  view: &ActiveTasksView,
+  log_id: Option<i32>,
+  undone: bool,
+) {
+  if let Some(log_id) = log_id {
+    view.report(view.storage.update_action_log_undone(log_id, undone));
+  }
+}
+
+// All or none of the entries are written, so a batch is never half
+// logged.
+fn log_task_batch(
+  view: &ActiveTasksView,
+  ca: &CompositeAction<TaskAction>,
+  log_ids: &mut Vec<i32>,
+) {
+  let parent_id = view.undo_buffer.current_log_id();
+  let mut new_log_ids = vec![];
+  let result = view.storage.transaction(&mut || {
+    new_log_ids.clear();
+    for ta in &ca.actions {
+      let entry = NewActionLogEntry {
+        parent_id,
+        batch_id: new_log_ids.first().cloned(),
+        ..ta.log_entry()
+      };
+      new_log_ids.push(view.storage.log_action(&entry)?.id);
+    }
+    Ok(())
+  });
+
+  match result {
+    Ok(()) => *log_ids = new_log_ids,
+    Err(err) => view.task_results_window.show_error(&err),
+  }
+}
+
+fn mark_logged_batch(view: &ActiveTasksView, log_ids: &[i32], undone: bool) {
+  view.report(view.storage.transaction(&mut || {
+    for &log_id in log_ids {
+      view.storage.update_action_log_undone(log_id, undone)?;
+    }
+    Ok(())
+  }));
+}
+
 // == EXECUTE CODE ==
 
 pub fn execute_filterer_action(
   fa: &mut FiltererAction,
   view: &Weak<ActiveTasksView>,
   scroller_state: &mut SavedTasksScrolerState,
-) {
+) -> Result<()> {
   let view = view.upgrade().expect("Action should not outlive view");
 
   // First save scroller position.
   scroller_state.old_id = view.scroller.current_task_id();
 
   // Now execute filtering action.
-  fa.execute();
+  fa.execute()?;
+  view
+    .task_results_window
+    .show_message(&capitalize(&fa.description()));
 
   // Fetch new data when filterer is applied.
-  view.data_source.pull(&view.connection);
+  view.refresh();
   view.scroller.jump(End::Top);
+
+  Ok(())
 }
 
 pub fn execute_task_action(
   ta: &mut TaskAction,
   view: &Weak<ActiveTasksView>,
   scroller_state: &mut SavedTasksScrolerState,
-) {
+  log_id: &mut Option<i32>,
+) -> Result<()> {
   let view = view.upgrade().expect("Action should not outlive view");
 
   // First save scroller position.
@@ -59,10 +132,14 @@ pub fn execute_task_action(
   let old_result_idx = view.scroller.current_result_idx();
 
   // Now execute task action.
-  ta.execute();
+  ta.execute()?;
+  view
+    .task_results_window
+    .show_message(&capitalize(&ta.description()));
+  log_task_action(&view, ta, log_id);
 
   // Fetch data when any task is updated.
-  view.data_source.pull(&view.connection);
+  view.refresh();
 
   use self::TaskAction::*;
   use self::TaskUpdateAction::*;
@@ -79,6 +156,7 @@ pub fn execute_task_action(
 
     TaskUpdate(UpdateDuration { task_id, .. })
     | TaskUpdate(UpdatePriority { task_id, .. })
+    | TaskUpdate(UpdateRecurrence { task_id, .. })
     | TaskUpdate(UpdateRequiresInternet { task_id, .. })
     | TaskUpdate(UpdateStatus { task_id, .. })
     | TaskUpdate(UpdateTaskTitle { task_id, .. }) => {
@@ -90,6 +168,53 @@ pub fn execute_task_action(
       }
     }
   }
+
+  Ok(())
+}
+
+// The tasks may all leave the list, e.g. when completed, in which case
+// the scroller stays at the same index.
+pub fn execute_task_batch_action(
+  ca: &mut CompositeAction<TaskAction>,
+  view: &Weak<ActiveTasksView>,
+  scroller_state: &mut SavedTasksScrolerState,
+  log_ids: &mut Vec<i32>,
+) -> Result<()> {
+  let view = view.upgrade().expect("Action should not outlive view");
+
+  // First save scroller position.
+  scroller_state.old_id = view.scroller.current_task_id();
+
+  // Now execute the task actions.
+  ca.execute()?;
+  view
+    .task_results_window
+    .show_message(&capitalize(&ca.description()));
+  log_task_batch(&view, ca, log_ids);
+
+  // Fetch data when any task is updated.
+  view.refresh();
+
+  Ok(())
+}
+
+pub fn execute_tasks_scroll_action(
+  tsa: &mut TasksScrollAction,
+  view: &Weak<ActiveTasksView>,
+) -> Result<()> {
+  let view = view.upgrade().expect("Action should not outlive view");
+
+  tsa.execute()?;
+
+  if let TasksScrollAction::JumpToTask { task_id, .. } = tsa {
+    if view.scroller.current_task_id() != Some(*task_id) {
+      view
+        .task_results_window
+        .show_message(&format!("No task with id {}", task_id));
+    }
+  }
+
+  Ok(())
 }
 
 // == REDO CODE ==
@@ -98,38 +223,48 @@ pub fn redo_filterer_action(
   fa: &mut FiltererAction,
   view: &Weak<ActiveTasksView>,
   scroller_state: &mut SavedTasksScrolerState,
-) {
+) -> Result<()> {
   let view = view.upgrade().expect("Action should not outlive view");
 
   // First save scroller position.
   scroller_state.old_id = view.scroller.current_task_id();
 
   // Now execute filtering action.
-  fa.execute();
+  fa.execute()?;
+  view
+    .task_results_window
+    .show_message(&format!("Redid: {}", fa.description()));
 
   // Fetch new data when filterer is applied.
-  view.data_source.pull(&view.connection);
+  view.refresh();
 
   // Try to restore new scroller state.
   let new_task_id = scroller_state.unwrap_new_id();
   jump_to_task_id_option_or_top(&view.scroller, new_task_id);
+
+  Ok(())
 }
 
 pub fn redo_task_action(
   ta: &mut TaskAction,
   view: &Weak<ActiveTasksView>,
   scroller_state: &mut SavedTasksScrolerState,
-) {
+  log_id: Option<i32>,
+) -> Result<()> {
   let view = view.upgrade().expect("Action should not outlive view");
 
   // First save scroller position.
   scroller_state.old_id = view.scroller.current_task_id();
 
   // Now execute task action.
-  ta.execute();
+  ta.execute()?;
+  view
+    .task_results_window
+    .show_message(&format!("Redid: {}", ta.description()));
+  mark_logged_action(&view, log_id, false);
 
   // Fetch data when any task is updated.
-  view.data_source.pull(&view.connection);
+  view.refresh();
 
   use self::TaskAction::*;
   use self::TaskUpdateAction::*;
@@ -147,6 +282,7 @@ pub fn redo_task_action(
 
     TaskUpdate(UpdateDuration { task_id, .. })
     | TaskUpdate(UpdatePriority { task_id, .. })
+    | TaskUpdate(UpdateRecurrence { task_id, .. })
     | TaskUpdate(UpdateRequiresInternet { task_id, .. })
     | TaskUpdate(UpdateStatus { task_id, .. })
     | TaskUpdate(UpdateTaskTitle { task_id, .. }) => {
@@ -159,6 +295,36 @@ pub fn redo_task_action(
       }
     }
   }
+
+  Ok(())
+}
+
+pub fn redo_task_batch_action(
+  ca: &mut CompositeAction<TaskAction>,
+  view: &Weak<ActiveTasksView>,
+  scroller_state: &mut SavedTasksScrolerState,
+  log_ids: &[i32],
+) -> Result<()> {
+  let view = view.upgrade().expect("Action should not outlive view");
+
+  // First save scroller position.
+  scroller_state.old_id = view.scroller.current_task_id();
+
+  // Now execute the task actions.
+  ca.redo()?;
+  view
+    .task_results_window
+    .show_message(&format!("Redid: {}", ca.description()));
+  mark_logged_batch(&view, log_ids, false);
+
+  // Fetch data when any task is updated.
+  view.refresh();
+
+  // Try to restore id that had been focused on.
+  let new_task_id = scroller_state.unwrap_new_id();
+  jump_to_task_id_option_or_top(&view.scroller, new_task_id);
+
+  Ok(())
 }
 
 // == UNDO CODE ==
@@ -167,7 +333,7 @@ pub fn unexecute_filterer_action(
   fa: &mut FiltererAction,
   view: &Weak<ActiveTasksView>,
   scroller_state: &mut SavedTasksScrolerState,
-) {
+) -> Result<()> {
   let view = view.upgrade().expect("Action should not outlive view");
 
   // First save scroller position.
@@ -175,20 +341,26 @@ pub fn unexecute_filterer_action(
     NewScrollerTaskId::Saved(view.scroller.current_task_id());
 
   // Now execute filtering action.
-  fa.unexecute();
+  fa.unexecute()?;
+  view
+    .task_results_window
+    .show_message(&format!("Undid: {}", fa.description()));
 
   // Fetch new data when filterer is applied.
-  view.data_source.pull(&view.connection);
+  view.refresh();
 
   // Try to restore scroll position.
   jump_to_task_id_option_or_top(&view.scroller, scroller_state.old_id);
+
+  Ok(())
 }
 
 pub fn unexecute_task_action(
   ta: &mut TaskAction,
   view: &Weak<ActiveTasksView>,
   scroller_state: &mut SavedTasksScrolerState,
-) {
+  log_id: Option<i32>,
+) -> Result<()> {
   let view = view.upgrade().expect("Action should not outlive view");
 
   // First save scroller position.
@@ -196,10 +368,14 @@ pub fn unexecute_task_action(
     NewScrollerTaskId::Saved(view.scroller.current_task_id());
 
   // Now unexecute task action.
-  ta.unexecute();
+  ta.unexecute()?;
+  view
+    .task_results_window
+    .show_message(&format!("Undid: {}", ta.description()));
+  mark_logged_action(&view, log_id, true);
 
   // Fetch data when any task is updated.
-  view.data_source.pull(&view.connection);
+  view.refresh();
 
   use self::TaskAction::*;
   use self::TaskUpdateAction::*;
@@ -217,6 +393,7 @@ pub fn unexecute_task_action(
     | RequestTaskDelay { task_id, .. }
     | TaskUpdate(UpdateDuration { task_id, .. })
     | TaskUpdate(UpdatePriority { task_id, .. })
+    | TaskUpdate(UpdateRecurrence { task_id, .. })
     | TaskUpdate(UpdateRequiresInternet { task_id, .. })
     | TaskUpdate(UpdateStatus { task_id, .. })
     | TaskUpdate(UpdateTaskTitle { task_id, .. }) => {
@@ -224,4 +401,34 @@ pub fn unexecute_task_action(
       jump_to_task_id_or_top(&view.scroller, *task_id);
     }
   }
+
+  Ok(())
+}
+
+pub fn unexecute_task_batch_action(
+  ca: &mut CompositeAction<TaskAction>,
+  view: &Weak<ActiveTasksView>,
+  scroller_state: &mut SavedTasksScrolerState,
+  log_ids: &[i32],
+) -> Result<()> {
+  let view = view.upgrade().expect("Action should not outlive view");
+
+  // First save scroller position.
+  scroller_state.new_id =
+    NewScrollerTaskId::Saved(view.scroller.current_task_id());
+
+  // Now unexecute the task actions.
+  ca.unexecute()?;
+  view
+    .task_results_window
+    .show_message(&format!("Undid: {}", ca.description()));
+  mark_logged_batch(&view, log_ids, true);
+
+  // Fetch data when any task is updated.
+  view.refresh();
+
+  // Try to return to previously focused task.
+  jump_to_task_id_option_or_top(&view.scroller, scroller_state.old_id);
+
+  Ok(())
 }
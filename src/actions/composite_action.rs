@@ -0,0 +1,70 @@
+use actions::{ForwardAction, ReversableAction};
+use error::Result;
+use std::rc::Rc;
+use storage::Storage;
+
+// Several actions done and undone as one step. Each pass over them runs
+// in a single transaction, so a failure part way through leaves the
+// database as it was instead of half done.
+#[derive(Clone)]
+pub struct CompositeAction<A> {
+  pub actions: Vec<A>,
+  storage: Rc<dyn Storage>,
+}
+
+impl<A: ReversableAction> CompositeAction<A> {
+  pub fn new(
+    actions: Vec<A>,
+    storage: &Rc<dyn Storage>,
+  ) -> CompositeAction<A> {
+    CompositeAction {
+      actions,
+      storage: Rc::clone(storage),
+    }
+  }
+}
+
+impl<A: ReversableAction> ForwardAction for CompositeAction<A> {
+  fn execute(&mut self) -> Result<()> {
+    let actions = &mut self.actions;
+    self.storage.transaction(&mut || {
+      for action in actions.iter_mut() {
+        action.execute()?;
+      }
+      Ok(())
+    })
+  }
+}
+
+impl<A: ReversableAction> ReversableAction for CompositeAction<A> {
+  fn redo(&mut self) -> Result<()> {
+    let actions = &mut self.actions;
+    self.storage.transaction(&mut || {
+      for action in actions.iter_mut() {
+        action.redo()?;
+      }
+      Ok(())
+    })
+  }
+
+  fn unexecute(&mut self) -> Result<()> {
+    let actions = &mut self.actions;
+    self.storage.transaction(&mut || {
+      for action in actions.iter_mut().rev() {
+        action.unexecute()?;
+      }
+      Ok(())
+    })
+  }
+
+  // E.g. "recorded effort on #3 and 4 more".
+  fn description(&self) -> String {
+    match self.actions.split_first() {
+      None => String::from("nothing"),
+      Some((first, [])) => first.description(),
+      Some((first, rest)) => {
+        format!("{} and {} more", first.description(), rest.len())
+      }
+    }
+  }
+}
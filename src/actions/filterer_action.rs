@@ -1,97 +1,343 @@
 use actions::{ForwardAction, ReversableAction};
 use commands::FiltererCommand;
-use components::{filterer::FiltererRequiresInternetValue, Filterer};
+use components::filter_predicate::{
+  parse_durations, parse_priority, parse_span,
+};
+use components::{parse_filter_query, FilterPredicate, Filterer};
+use error::Result;
+use models::FilterPreset;
 use std::rc::Rc;
+use storage::Storage;
 use util::ui::UserInterface;
 
+// `None` if the user gave up, otherwise what they typed or why it can't
+// be used.
+type Input<T> = Option<::std::result::Result<T, String>>;
+
+// The preset goes with the criteria, so undo puts back both.
 #[derive(Clone)]
 pub enum FiltererAction {
-  UpdateRequiresInternet {
-    new_value: FiltererRequiresInternetValue,
-    old_value: FiltererRequiresInternetValue,
+  UpdateCriteria {
+    new_value: Option<FilterPredicate>,
+    new_preset: Option<String>,
+    old_value: Option<FilterPredicate>,
+    old_preset: Option<String>,
     filterer: Rc<Filterer>,
   },
 }
 
 impl ForwardAction for FiltererAction {
-  fn execute(&mut self) {
+  fn execute(&mut self) -> Result<()> {
     use self::FiltererAction::*;
 
     match self {
-      UpdateRequiresInternet {
+      UpdateCriteria {
         new_value,
+        new_preset,
         filterer,
         ..
       } => {
-        filterer.set_requires_internet_value(*new_value);
+        filterer.set_criteria(new_value.clone(), new_preset.clone());
       }
     }
+
+    Ok(())
   }
 }
 
 impl ReversableAction for FiltererAction {
-  fn unexecute(&mut self) {
+  fn unexecute(&mut self) -> Result<()> {
     use self::FiltererAction::*;
 
     match self {
-      UpdateRequiresInternet {
+      UpdateCriteria {
         old_value,
+        old_preset,
         filterer,
         ..
       } => {
-        filterer.set_requires_internet_value(*old_value);
+        filterer.set_criteria(old_value.clone(), old_preset.clone());
       }
     }
+
+    Ok(())
   }
+
+  fn description(&self) -> String {
+    use self::FiltererAction::*;
+
+    // A preset by its name, e.g. "filter [desk]\u{2192}[train]".
+    let describe =
+      |criteria: &Option<FilterPredicate>, preset: &Option<String>| {
+        match (preset, criteria) {
+          (Some(preset), _) => format!("[{}]", preset),
+          (None, None) => String::from("none"),
+          (None, Some(criteria)) => criteria.to_string(),
+        }
+      };
+    match self {
+      UpdateCriteria {
+        new_value,
+        new_preset,
+        old_value,
+        old_preset,
+        ..
+      } => format!(
+        "filter {}\u{2192}{}",
+        describe(old_value, old_preset),
+        describe(new_value, new_preset)
+      ),
+    }
+  }
+}
+
+fn read_value<T, F>(
+  ui: &UserInterface,
+  prompt: &str,
+  parse: F,
+  error: &str,
+) -> Input<T>
+where
+  F: Fn(&str) -> Option<T>,
+{
+  let text = ui.read_line(prompt)?;
+  let text = text.trim();
+  Some(parse(text).ok_or_else(|| format!("{}: {}", error, text)))
 }
 
+// `Ok(None)` for "any".
 fn read_requires_internet_value(
   ui: &UserInterface,
-) -> Option<FiltererRequiresInternetValue> {
-  let str_value = ui.read_line("Requires internet value: ");
-
-  use self::FiltererRequiresInternetValue::*;
-  // Gross. Cannot compare a String with a &str. So need to call
-  // `s.as_ref()`. But then also Option<String> will give `map` a
-  // String, and we want it to be giving the `&String` reference. Ugh.
-  match str_value.as_ref().map(|s| s.as_ref()) {
-    Some("yes") => Some(Yes),
-    Some("no") => Some(No),
-    Some("any") => Some(Any),
-    // Includes Ctrl-C
-    _ => None,
+) -> Input<Option<bool>> {
+  read_value(
+    ui,
+    "Requires internet value: ",
+    |text| match text {
+      "yes" => Some(Some(true)),
+      "no" => Some(Some(false)),
+      "any" => Some(None),
+      _ => None,
+    },
+    "Not yes, no or any",
+  )
+}
+
+// The predicate a `FilterBy...` command adds.
+fn read_predicate(
+  cmd: FiltererCommand,
+  ui: &UserInterface,
+) -> Input<FilterPredicate> {
+  use self::FilterPredicate::*;
+  use self::FiltererCommand::*;
+
+  let span_hint = "(e.g. 30m, 12h, 3d, 2w)";
+  match cmd {
+    FilterByDelays => read_value(
+      ui,
+      "Delayed at least how many times: ",
+      |text| text.parse().ok().map(DelayedAtLeast),
+      "Not a number",
+    ),
+    FilterByDuration => read_value(
+      ui,
+      "Durations (short, med, long, e.g. short,med): ",
+      |text| parse_durations(text).map(DurationIn),
+      "Not a list of durations",
+    ),
+    FilterByMaxPriority => read_value(
+      ui,
+      "Priority at most (low, med, high): ",
+      |text| parse_priority(text).map(PriorityAtMost),
+      "Not a priority",
+    ),
+    FilterByMinPriority => read_value(
+      ui,
+      "Priority at least (low, med, high): ",
+      |text| parse_priority(text).map(PriorityAtLeast),
+      "Not a priority",
+    ),
+    FilterByRecentEffort => read_value(
+      ui,
+      &format!("Effort recorded within {}: ", span_hint),
+      |text| parse_span(text).map(HasRecentEffort),
+      "Not a span of time",
+    ),
+    FilterByTitle => {
+      let text = ui.read_line("Title contains: ")?;
+      match text.trim() {
+        "" => None,
+        text => Some(Ok(TitleContains(String::from(text)))),
+      }
+    }
+    FilterOlderThan => read_value(
+      ui,
+      &format!("Older than {}: ", span_hint),
+      |text| parse_span(text).map(OlderThan),
+      "Not a span of time",
+    ),
+    FilterYoungerThan => read_value(
+      ui,
+      &format!("Younger than {}: ", span_hint),
+      |text| parse_span(text).map(YoungerThan),
+      "Not a span of time",
+    ),
+    ApplyPreset
+    | ClearFilter
+    | FilterByRequiresInternet
+    | NegateLastFilter
+    | OrLastFilters => panic!("Not a predicate: {:?}", cmd),
   }
 }
 
-fn new_requires_internet_filterer_action(
+// The criteria `cmd` would change `old_value` to.
+fn new_criteria(
+  cmd: FiltererCommand,
   ui: &UserInterface,
-  filterer: &Rc<Filterer>,
-) -> Option<FiltererAction> {
-  read_requires_internet_value(ui).and_then(|new_value| {
-    let old_value = filterer.requires_internet_value();
-    if old_value == new_value {
-      None
-    } else {
-      Some(FiltererAction::UpdateRequiresInternet {
-        new_value,
-        old_value,
-        filterer: Rc::clone(filterer),
-      })
+  old_value: Option<FilterPredicate>,
+) -> Input<Option<FilterPredicate>> {
+  use self::FiltererCommand::*;
+
+  let new_value = match cmd {
+    ClearFilter => None,
+    // Replaces whatever `net` filter there was, like a setting.
+    FilterByRequiresInternet => {
+      let value = match read_requires_internet_value(ui)? {
+        Err(message) => return Some(Err(message)),
+        Ok(value) => value,
+      };
+      let rest = old_value.and_then(|old_value| {
+        old_value.without_requires_internet()
+      });
+      match (rest, value) {
+        (rest, None) => rest,
+        (None, Some(value)) => Some(FilterPredicate::RequiresInternet(value)),
+        (Some(rest), Some(value)) => {
+          Some(rest.and(FilterPredicate::RequiresInternet(value)))
+        }
+      }
+    }
+    NegateLastFilter => match old_value {
+      None => return Some(Err(String::from("No filter to negate"))),
+      Some(old_value) => Some(old_value.negate_last()),
+    },
+    OrLastFilters => match old_value.and_then(FilterPredicate::or_last) {
+      None => return Some(Err(String::from("No two filters to or"))),
+      new_value => new_value,
+    },
+    cmd => {
+      let predicate = match read_predicate(cmd, ui)? {
+        Err(message) => return Some(Err(message)),
+        Ok(predicate) => predicate,
+      };
+      match old_value {
+        None => Some(predicate),
+        Some(old_value) => Some(old_value.and(predicate)),
+      }
     }
-  })
+  };
+
+  Some(Ok(new_value))
+}
+
+// Asks for one of the saved presets by name or by its number in the
+// list, e.g. "Preset (1 desk, 2 train): ".
+pub fn read_filter_preset(
+  ui: &UserInterface,
+  storage: &Rc<dyn Storage>,
+  prompt: &str,
+) -> Input<FilterPreset> {
+  let presets = match storage.filter_presets() {
+    Err(err) => return Some(Err(format!("Error: {}", err))),
+    Ok(presets) => presets,
+  };
+  if presets.is_empty() {
+    return Some(Err(String::from("No filter presets saved")));
+  }
+
+  let choices: Vec<_> = presets
+    .iter()
+    .enumerate()
+    .map(|(idx, preset)| format!("{} {}", idx + 1, preset.name))
+    .collect();
+  let text = ui.read_line(&format!("{} ({}): ", prompt, choices.join(", ")))?;
+  let text = text.trim();
+  if text.is_empty() {
+    return None;
+  }
+
+  let by_number = text
+    .parse::<usize>()
+    .ok()
+    .and_then(|num| num.checked_sub(1))
+    .and_then(|idx| presets.get(idx));
+  let preset = by_number.or_else(|| presets.iter().find(|p| p.name == text));
+  Some(
+    preset
+      .cloned()
+      .ok_or_else(|| format!("No preset: {}", text)),
+  )
 }
 
 impl FiltererAction {
   pub fn prepare_from_cmd(
     cmd: FiltererCommand,
     ui: &UserInterface,
+    storage: &Rc<dyn Storage>,
+    filterer: &Rc<Filterer>,
+  ) -> Input<FiltererAction> {
+    if let FiltererCommand::ApplyPreset = cmd {
+      return match read_filter_preset(ui, storage, "Preset")? {
+        Err(message) => Some(Err(message)),
+        Ok(preset) => Self::apply_preset(&preset, filterer).transpose(),
+      };
+    }
+
+    match new_criteria(cmd, ui, filterer.criteria())? {
+      Err(message) => Some(Err(message)),
+      Ok(new_value) => Self::update_criteria(new_value, filterer).map(Ok),
+    }
+  }
+
+  // Leaves the preset behind, since the criteria no longer match it.
+  // `None` if the criteria are already `new_value`.
+  pub fn update_criteria(
+    new_value: Option<FilterPredicate>,
     filterer: &Rc<Filterer>,
   ) -> Option<FiltererAction> {
-    use self::FiltererCommand::*;
-    match cmd {
-      FilterByRequiresInternet => {
-        new_requires_internet_filterer_action(ui, filterer)
-      }
+    if new_value == filterer.criteria() {
+      return None;
+    }
+
+    Some(Self::new(new_value, None, filterer))
+  }
+
+  // An error if the saved query no longer parses. `None` if the preset
+  // is already applied.
+  pub fn apply_preset(
+    preset: &FilterPreset,
+    filterer: &Rc<Filterer>,
+  ) -> ::std::result::Result<Option<FiltererAction>, String> {
+    let new_value = parse_filter_query(&preset.query)
+      .map_err(|message| format!("{}: {}", preset.name, message))?;
+    let new_preset = Some(preset.name.clone());
+    if new_value == filterer.criteria() && new_preset == filterer.preset() {
+      return Ok(None);
+    }
+
+    Ok(Some(Self::new(new_value, new_preset, filterer)))
+  }
+
+  fn new(
+    new_value: Option<FilterPredicate>,
+    new_preset: Option<String>,
+    filterer: &Rc<Filterer>,
+  ) -> FiltererAction {
+    FiltererAction::UpdateCriteria {
+      new_value,
+      new_preset,
+      old_value: filterer.criteria(),
+      old_preset: filterer.preset(),
+      filterer: Rc::clone(filterer),
     }
   }
 }
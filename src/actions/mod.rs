@@ -1,17 +1,24 @@
 mod action;
 mod active_tasks_view;
+mod composite_action;
 mod filterer_action;
 mod scroll_action;
 mod task_action;
 mod task_action_execution;
+mod task_action_log;
+mod task_detail_view_action;
+mod task_event_action;
 mod task_update_action;
 mod task_update_action_execution;
 mod undo_buffer_action;
 
 pub use self::action::{ForwardAction, ReversableAction};
 pub use self::active_tasks_view::ActiveTasksViewAction;
-pub use self::filterer_action::FiltererAction;
+pub use self::composite_action::CompositeAction;
+pub use self::filterer_action::{read_filter_preset, FiltererAction};
 pub use self::scroll_action::{ScrollAction, TasksScrollAction};
 pub use self::task_action::TaskAction;
+pub use self::task_detail_view_action::TaskDetailViewAction;
+pub use self::task_event_action::TaskEventAction;
 pub use self::task_update_action::TaskUpdateAction;
 pub use self::undo_buffer_action::UndoBufferAction;
@@ -1,5 +1,6 @@
 use actions::ForwardAction;
 use components::{Scroller, TasksScroller};
+use error::Result;
 use models::{Direction, End};
 use std::rc::Rc;
 
@@ -21,10 +22,15 @@ pub enum TasksScrollAction {
     task_id: i32,
     scroller: Rc<TasksScroller>,
   },
+  ScrollBy {
+    direction: Direction,
+    num_rows: i32,
+    scroller: Rc<TasksScroller>,
+  },
 }
 
 impl ForwardAction for ScrollAction {
-  fn execute(&mut self) {
+  fn execute(&mut self) -> Result<()> {
     use self::ScrollAction::*;
 
     match self {
@@ -34,17 +40,26 @@ impl ForwardAction for ScrollAction {
         scroller,
       } => scroller.scroll(*direction),
     }
+
+    Ok(())
   }
 }
 
 impl ForwardAction for TasksScrollAction {
-  fn execute(&mut self) {
+  fn execute(&mut self) -> Result<()> {
     use self::TasksScrollAction::*;
 
     match self {
       JumpToTask { task_id, scroller } => {
         scroller.jump_to_task_id(*task_id);
       }
+      ScrollBy {
+        direction,
+        num_rows,
+        scroller,
+      } => scroller.scroll_by(*direction, *num_rows),
     }
+
+    Ok(())
   }
 }
@@ -1,8 +1,8 @@
 use actions::TaskUpdateAction;
 use commands::TaskCommand;
-use diesel::pg::PgConnection;
 use models::{Task, TaskEvent};
 use std::rc::Rc;
+use storage::Storage;
 use util::ui::UserInterface;
 
 #[derive(Clone)]
@@ -10,25 +10,25 @@ pub enum TaskAction {
   CreateTask {
     task_title: String,
     task: Option<Task>,
-    connection: Rc<PgConnection>,
+    storage: Rc<dyn Storage>,
   },
 
   RecordTaskEffort {
     task_id: i32,
     task_event: Option<TaskEvent>,
-    connection: Rc<PgConnection>,
+    storage: Rc<dyn Storage>,
   },
 
   RequestTaskAgeReset {
     task_id: i32,
     task_event: Option<TaskEvent>,
-    connection: Rc<PgConnection>,
+    storage: Rc<dyn Storage>,
   },
 
   RequestTaskDelay {
     task_id: i32,
     task_event: Option<TaskEvent>,
-    connection: Rc<PgConnection>,
+    storage: Rc<dyn Storage>,
   },
 
   TaskUpdate(TaskUpdateAction),
@@ -38,7 +38,7 @@ impl TaskAction {
   pub fn prepare_from_cmd<F>(
     cmd: TaskCommand,
     ui: &UserInterface,
-    connection: &Rc<PgConnection>,
+    storage: &Rc<dyn Storage>,
     current_task_fn: F,
   ) -> Option<TaskAction>
   where
@@ -56,7 +56,7 @@ impl TaskAction {
         Some(TaskAction::CreateTask {
           task_title,
           task: None,
-          connection: Rc::clone(connection),
+          storage: Rc::clone(storage),
         })
       }
 
@@ -65,7 +65,7 @@ impl TaskAction {
         current_task_fn().map(|task| TaskAction::RecordTaskEffort {
           task_id: task.id,
           task_event: None,
-          connection: Rc::clone(connection),
+          storage: Rc::clone(storage),
         })
       }
 
@@ -74,7 +74,7 @@ impl TaskAction {
         current_task_fn().map(|task| TaskAction::RequestTaskAgeReset {
           task_id: task.id,
           task_event: None,
-          connection: Rc::clone(connection),
+          storage: Rc::clone(storage),
         })
       }
 
@@ -83,14 +83,14 @@ impl TaskAction {
         current_task_fn().map(|task| TaskAction::RequestTaskDelay {
           task_id: task.id,
           task_event: None,
-          connection: Rc::clone(connection),
+          storage: Rc::clone(storage),
         })
       }
 
       // Update a task attribute.
       TaskCommand::UpdateTask(cmd) => current_task_fn()
         .and_then(|task| {
-          TaskUpdateAction::prepare_from_cmd(cmd, &task, ui, connection)
+          TaskUpdateAction::prepare_from_cmd(cmd, &task, ui, storage)
         }).map(TaskAction::TaskUpdate),
     }
   }
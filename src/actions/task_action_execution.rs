@@ -1,8 +1,8 @@
 use actions::{ForwardAction, ReversableAction, TaskAction};
-use queries::{task as task_queries, task_event as te_queries};
+use error::Result;
 
 impl ForwardAction for TaskAction {
-  fn execute(&mut self) {
+  fn execute(&mut self) -> Result<()> {
     use self::TaskAction::*;
 
     match self {
@@ -10,12 +10,12 @@ impl ForwardAction for TaskAction {
       CreateTask {
         task_title,
         task,
-        connection,
+        storage,
       } => {
         if let Some(task) = task {
-          task_queries::update_destroyed(task.id, false, &connection);
+          storage.update_task_destroyed(task.id, false)?;
         } else {
-          *task = Some(task_queries::create(task_title, &connection));
+          *task = Some(storage.create_task(task_title)?);
         }
       }
 
@@ -23,17 +23,12 @@ impl ForwardAction for TaskAction {
       RecordTaskEffort {
         task_id,
         task_event,
-        connection,
+        storage,
       } => {
         if let Some(task_event) = task_event {
-          te_queries::update_destroyed(
-            task_event.id,
-            false,
-            &connection,
-          );
+          storage.update_task_event_destroyed(task_event.id, false)?;
         } else {
-          *task_event =
-            Some(te_queries::record_task_effort(*task_id, &connection));
+          *task_event = Some(storage.record_task_effort(*task_id)?);
         }
       }
 
@@ -41,17 +36,12 @@ impl ForwardAction for TaskAction {
       RequestTaskAgeReset {
         task_id,
         task_event,
-        connection,
+        storage,
       } => {
         if let Some(task_event) = task_event {
-          te_queries::update_destroyed(
-            task_event.id,
-            false,
-            &connection,
-          );
+          storage.update_task_event_destroyed(task_event.id, false)?;
         } else {
-          *task_event =
-            Some(te_queries::request_task_age_reset(*task_id, &connection));
+          *task_event = Some(storage.request_task_age_reset(*task_id)?);
         }
       }
 
@@ -59,47 +49,44 @@ impl ForwardAction for TaskAction {
       RequestTaskDelay {
         task_id,
         task_event,
-        connection,
+        storage,
       } => {
         if let Some(task_event) = task_event {
-          te_queries::update_destroyed(
-            task_event.id,
-            false,
-            &connection,
-          );
+          storage.update_task_event_destroyed(task_event.id, false)?;
         } else {
-          *task_event =
-            Some(te_queries::request_delay(*task_id, &connection));
+          *task_event = Some(storage.request_delay(*task_id)?);
         }
       }
 
       // Update a task attribute.
-      TaskUpdate(update_action) => update_action.execute(),
+      TaskUpdate(update_action) => update_action.execute()?,
     }
+
+    Ok(())
   }
 }
 
 impl ReversableAction for TaskAction {
-  fn unexecute(&mut self) {
+  fn unexecute(&mut self) -> Result<()> {
     use self::TaskAction::*;
 
     match self {
       // Undo task creation.
       CreateTask {
-        task, connection, ..
+        task, storage, ..
       } => {
         let task = match task {
           None => panic!("Cannot undo a never performed create action"),
           Some(task) => task,
         };
 
-        task_queries::update_destroyed(task.id, true, &connection);
+        storage.update_task_destroyed(task.id, true)?;
       }
 
       // Undo task effort creation.
       RecordTaskEffort {
         task_event,
-        connection,
+        storage,
         ..
       } => {
         let task_event = match task_event {
@@ -109,13 +96,13 @@ impl ReversableAction for TaskAction {
           Some(task_event) => task_event,
         };
 
-        te_queries::update_destroyed(task_event.id, true, &connection);
+        storage.update_task_event_destroyed(task_event.id, true)?;
       }
 
       // Undo age reset request.
       RequestTaskAgeReset {
         task_event,
-        connection,
+        storage,
         ..
       } => {
         let task_event = match task_event {
@@ -125,13 +112,13 @@ impl ReversableAction for TaskAction {
           Some(task_event) => task_event,
         };
 
-        te_queries::update_destroyed(task_event.id, true, &connection);
+        storage.update_task_event_destroyed(task_event.id, true)?;
       }
 
       // Undo delay request.
       RequestTaskDelay {
         task_event,
-        connection,
+        storage,
         ..
       } => {
         let task_event = match task_event {
@@ -141,11 +128,40 @@ impl ReversableAction for TaskAction {
           Some(task_event) => task_event,
         };
 
-        te_queries::update_destroyed(task_event.id, true, &connection);
+        storage.update_task_event_destroyed(task_event.id, true)?;
       }
 
       // Undo task attribute update.
-      TaskUpdate(update_action) => update_action.unexecute(),
+      TaskUpdate(update_action) => update_action.unexecute()?,
+    }
+
+    Ok(())
+  }
+
+  fn description(&self) -> String {
+    use self::TaskAction::*;
+
+    match self {
+      CreateTask {
+        task_title,
+        task: None,
+        ..
+      } => format!("created {:?}", task_title),
+      CreateTask {
+        task_title,
+        task: Some(task),
+        ..
+      } => format!("created #{} {:?}", task.id, task_title),
+      RecordTaskEffort { task_id, .. } => {
+        format!("recorded effort on #{}", task_id)
+      }
+      RequestTaskAgeReset { task_id, .. } => {
+        format!("requested age reset on #{}", task_id)
+      }
+      RequestTaskDelay { task_id, .. } => {
+        format!("requested delay on #{}", task_id)
+      }
+      TaskUpdate(tua) => tua.description(),
     }
   }
 }
@@ -0,0 +1,370 @@
+use actions::{TaskAction, TaskUpdateAction};
+use error::{Error, Result};
+use models::{
+  ActionLogEntry, ActionLogKind, NewActionLogEntry, TaskDuration,
+  TaskEvent, TaskPriority, TaskRecurrence, TaskStatus,
+};
+use std::fmt::Debug;
+use std::rc::Rc;
+use storage::Storage;
+
+// Converts executed task actions to and from `action_log` entries, so
+// they can be undone in a later session. Enum values are saved by their
+// Rust names, e.g. "Medium", and recurrences the way they are typed in.
+
+const DURATIONS: [TaskDuration; 3] =
+  [TaskDuration::Long, TaskDuration::Medium, TaskDuration::Short];
+const PRIORITIES: [TaskPriority; 3] =
+  [TaskPriority::High, TaskPriority::Medium, TaskPriority::Low];
+const STATUSES: [TaskStatus; 3] = [
+  TaskStatus::Abandoned,
+  TaskStatus::AvailableToPerform,
+  TaskStatus::Completed,
+];
+
+fn new_entry(kind: ActionLogKind, task_id: i32) -> NewActionLogEntry {
+  NewActionLogEntry {
+    kind,
+    task_id,
+    old_value: None,
+    new_value: None,
+    task_event_id: None,
+    spawned_task_id: None,
+    parent_id: None,
+    batch_id: None,
+  }
+}
+
+fn event_entry(
+  kind: ActionLogKind,
+  task_id: i32,
+  task_event: &Option<TaskEvent>,
+) -> NewActionLogEntry {
+  let task_event =
+    task_event.as_ref().expect("Action should have been executed.");
+  NewActionLogEntry {
+    task_event_id: Some(task_event.id),
+    ..new_entry(kind, task_id)
+  }
+}
+
+fn value_entry<T, F>(
+  kind: ActionLogKind,
+  task_id: i32,
+  old_value: T,
+  new_value: T,
+  to_text: F,
+) -> NewActionLogEntry
+where
+  F: Fn(T) -> Option<String>,
+{
+  NewActionLogEntry {
+    old_value: to_text(old_value),
+    new_value: to_text(new_value),
+    ..new_entry(kind, task_id)
+  }
+}
+
+fn enum_to_text<T: Debug>(value: T) -> Option<String> {
+  Some(format!("{:?}", value))
+}
+
+fn enum_from_text<T: Copy + Debug>(
+  text: Option<&str>,
+  values: &[T],
+) -> Option<T> {
+  let text = text?;
+  values
+    .iter()
+    .cloned()
+    .find(|value| format!("{:?}", value) == text)
+}
+
+fn recurrence_to_text(recurrence: Option<TaskRecurrence>) -> Option<String> {
+  recurrence.map(|recurrence| recurrence.to_string())
+}
+
+// `None` is a valid recurrence, so a failed parse is `None` and an
+// empty column is `Some(None)`.
+fn recurrence_from_text(
+  text: Option<&str>,
+) -> Option<Option<TaskRecurrence>> {
+  match text {
+    None => Some(None),
+    Some(text) => TaskRecurrence::parse(text).map(Some),
+  }
+}
+
+// Both values of an entry, or an error if either can't be read.
+fn values<T, F>(entry: &ActionLogEntry, from_text: F) -> Result<(T, T)>
+where
+  F: Fn(Option<&str>) -> Option<T>,
+{
+  let old_value = from_text(entry.old_value.as_deref());
+  let new_value = from_text(entry.new_value.as_deref());
+  match (old_value, new_value) {
+    (Some(old_value), Some(new_value)) => Ok((old_value, new_value)),
+    _ => Err(Error::BadActionLogEntry { id: entry.id }),
+  }
+}
+
+fn find_task_event(
+  entry: &ActionLogEntry,
+  storage: &Rc<dyn Storage>,
+) -> Result<Option<TaskEvent>> {
+  match entry.task_event_id {
+    None => Ok(None),
+    Some(id) => storage.find_task_event(id).map(Some),
+  }
+}
+
+impl TaskAction {
+  // Only makes sense once the action has been executed.
+  pub fn log_entry(&self) -> NewActionLogEntry {
+    use self::ActionLogKind as Kind;
+    use self::TaskAction::*;
+
+    match self {
+      CreateTask {
+        task_title, task, ..
+      } => {
+        let task = task.as_ref().expect("Task should have been created.");
+        NewActionLogEntry {
+          new_value: Some(task_title.clone()),
+          ..new_entry(Kind::CreateTask, task.id)
+        }
+      }
+      RecordTaskEffort {
+        task_id,
+        task_event,
+        ..
+      } => event_entry(Kind::RecordTaskEffort, *task_id, task_event),
+      RequestTaskAgeReset {
+        task_id,
+        task_event,
+        ..
+      } => event_entry(Kind::RequestTaskAgeReset, *task_id, task_event),
+      RequestTaskDelay {
+        task_id,
+        task_event,
+        ..
+      } => event_entry(Kind::RequestTaskDelay, *task_id, task_event),
+      TaskUpdate(tua) => tua.log_entry(),
+    }
+  }
+
+  // Rebuilds an executed action, or an undone one if `entry.undone`.
+  pub fn from_log_entry(
+    entry: &ActionLogEntry,
+    storage: &Rc<dyn Storage>,
+  ) -> Result<TaskAction> {
+    use self::ActionLogKind as Kind;
+    use self::TaskAction::*;
+
+    let task_id = entry.task_id;
+    let storage = Rc::clone(storage);
+    let action = match entry.kind {
+      // Only the title is logged, as the new value.
+      Kind::CreateTask => CreateTask {
+        task_title: entry
+          .new_value
+          .clone()
+          .ok_or(Error::BadActionLogEntry { id: entry.id })?,
+        task: Some(storage.find_task(task_id)?),
+        storage,
+      },
+      Kind::RecordTaskEffort => RecordTaskEffort {
+        task_id,
+        task_event: find_task_event(entry, &storage)?,
+        storage,
+      },
+      Kind::RequestTaskAgeReset => RequestTaskAgeReset {
+        task_id,
+        task_event: find_task_event(entry, &storage)?,
+        storage,
+      },
+      Kind::RequestTaskDelay => RequestTaskDelay {
+        task_id,
+        task_event: find_task_event(entry, &storage)?,
+        storage,
+      },
+      _ => TaskUpdate(TaskUpdateAction::from_log_entry(entry, &storage)?),
+    };
+
+    Ok(action)
+  }
+}
+
+impl TaskUpdateAction {
+  pub fn log_entry(&self) -> NewActionLogEntry {
+    use self::ActionLogKind as Kind;
+    use self::TaskUpdateAction::*;
+
+    match self {
+      UpdateDuration {
+        task_id,
+        old_value,
+        new_value,
+        ..
+      } => value_entry(
+        Kind::UpdateDuration,
+        *task_id,
+        *old_value,
+        *new_value,
+        enum_to_text,
+      ),
+      UpdatePriority {
+        task_id,
+        old_value,
+        new_value,
+        ..
+      } => value_entry(
+        Kind::UpdatePriority,
+        *task_id,
+        *old_value,
+        *new_value,
+        enum_to_text,
+      ),
+      UpdateRecurrence {
+        task_id,
+        old_value,
+        new_value,
+        ..
+      } => value_entry(
+        Kind::UpdateRecurrence,
+        *task_id,
+        *old_value,
+        *new_value,
+        recurrence_to_text,
+      ),
+      UpdateRequiresInternet {
+        task_id,
+        old_value,
+        new_value,
+        ..
+      } => value_entry(
+        Kind::UpdateRequiresInternet,
+        *task_id,
+        *old_value,
+        *new_value,
+        |value| Some(value.to_string()),
+      ),
+      UpdateStatus {
+        task_id,
+        old_value,
+        new_value,
+        task_event,
+        spawned_task,
+        ..
+      } => NewActionLogEntry {
+        task_event_id: task_event.as_ref().map(|task_event| task_event.id),
+        spawned_task_id: spawned_task.as_ref().map(|task| task.id),
+        ..value_entry(
+          Kind::UpdateStatus,
+          *task_id,
+          *old_value,
+          *new_value,
+          enum_to_text,
+        )
+      },
+      UpdateTaskTitle {
+        task_id,
+        old_value,
+        new_value,
+        ..
+      } => value_entry(
+        Kind::UpdateTaskTitle,
+        *task_id,
+        old_value.clone(),
+        new_value.clone(),
+        Some,
+      ),
+    }
+  }
+
+  fn from_log_entry(
+    entry: &ActionLogEntry,
+    storage: &Rc<dyn Storage>,
+  ) -> Result<TaskUpdateAction> {
+    use self::ActionLogKind as Kind;
+    use self::TaskUpdateAction::*;
+
+    let task_id = entry.task_id;
+    let storage = Rc::clone(storage);
+    let action = match entry.kind {
+      Kind::UpdateDuration => {
+        let (old_value, new_value) =
+          values(entry, |text| enum_from_text(text, &DURATIONS))?;
+        UpdateDuration {
+          task_id,
+          old_value,
+          new_value,
+          storage,
+        }
+      }
+      Kind::UpdatePriority => {
+        let (old_value, new_value) =
+          values(entry, |text| enum_from_text(text, &PRIORITIES))?;
+        UpdatePriority {
+          task_id,
+          old_value,
+          new_value,
+          storage,
+        }
+      }
+      Kind::UpdateRecurrence => {
+        let (old_value, new_value) = values(entry, recurrence_from_text)?;
+        UpdateRecurrence {
+          task_id,
+          old_value,
+          new_value,
+          storage,
+        }
+      }
+      Kind::UpdateRequiresInternet => {
+        let (old_value, new_value) =
+          values(entry, |text| text?.parse().ok())?;
+        UpdateRequiresInternet {
+          task_id,
+          old_value,
+          new_value,
+          storage,
+        }
+      }
+      Kind::UpdateStatus => {
+        let (old_value, new_value) =
+          values(entry, |text| enum_from_text(text, &STATUSES))?;
+        let spawned_task = match entry.spawned_task_id {
+          None => None,
+          Some(id) => Some(storage.find_task(id)?),
+        };
+        UpdateStatus {
+          task_id,
+          old_value,
+          new_value,
+          task_event: find_task_event(entry, &storage)?,
+          spawned_task,
+          storage,
+        }
+      }
+      Kind::UpdateTaskTitle => {
+        let (old_value, new_value) =
+          values(entry, |text| text.map(String::from))?;
+        UpdateTaskTitle {
+          task_id,
+          old_value,
+          new_value,
+          storage,
+        }
+      }
+      Kind::CreateTask
+      | Kind::RecordTaskEffort
+      | Kind::RequestTaskAgeReset
+      | Kind::RequestTaskDelay => {
+        panic!("Not a task update: {:?}", entry.kind)
+      }
+    };
+
+    Ok(action)
+  }
+}
@@ -0,0 +1,114 @@
+use actions::{
+  ForwardAction, ReversableAction, ScrollAction, TaskEventAction,
+  UndoBufferAction,
+};
+use commands::TaskDetailViewCommand;
+use components::{Scroller, UndoBuffer};
+use error::Result;
+use std::rc::{Rc, Weak};
+use views::TaskDetailView;
+
+#[derive(Clone)]
+pub enum TaskDetailViewAction {
+  Scroll {
+    sa: ScrollAction,
+  },
+  TaskEvent {
+    tea: TaskEventAction,
+    view: Weak<TaskDetailView>,
+  },
+  UndoBuffer {
+    uba: UndoBufferAction,
+  },
+}
+
+// The undo buffer is shared, so the view may have been closed since.
+// Whichever view the undo came from refreshes itself.
+fn refresh_view(view: &Weak<TaskDetailView>) {
+  if let Some(view) = view.upgrade() {
+    view.refresh();
+  }
+}
+
+impl TaskDetailViewAction {
+  pub fn prepare_from_command(
+    cmd: TaskDetailViewCommand,
+    view: &Rc<TaskDetailView>,
+  ) -> Option<TaskDetailViewAction> {
+    use self::TaskDetailViewCommand::*;
+
+    match cmd {
+      Scroll(sc) => {
+        let scroller = Rc::clone(&view.scroller) as Rc<dyn Scroller>;
+        sc.to_action(&scroller)
+          .map(|sa| TaskDetailViewAction::Scroll { sa })
+      }
+
+      TaskEvent(tec) => tec
+        .to_action(&view.storage, || view.current_task_event())
+        .map(|tea| TaskDetailViewAction::TaskEvent {
+          tea,
+          view: Rc::downgrade(view),
+        }),
+
+      UndoBuffer(ubc) => {
+        let uba = ubc.to_action(&view.undo_buffer);
+        Some(TaskDetailViewAction::UndoBuffer { uba })
+      }
+    }
+  }
+
+  pub fn maybe_add_to_undo_buffer(self, undo_buffer: &UndoBuffer) {
+    if let TaskDetailViewAction::TaskEvent { .. } = self {
+      undo_buffer.append_action(Box::new(self));
+    }
+  }
+}
+
+impl ForwardAction for TaskDetailViewAction {
+  fn execute(&mut self) -> Result<()> {
+    use self::TaskDetailViewAction::*;
+
+    match self {
+      Scroll { sa } => sa.execute(),
+      TaskEvent { tea, view } => {
+        tea.execute()?;
+        refresh_view(view);
+        Ok(())
+      }
+      UndoBuffer { uba } => uba.execute(),
+    }
+  }
+}
+
+impl ReversableAction for TaskDetailViewAction {
+  fn unexecute(&mut self) -> Result<()> {
+    use self::TaskDetailViewAction::*;
+
+    match self {
+      Scroll { .. } => {
+        panic!("Should not try to unexecute a Scroll action.")
+      }
+      TaskEvent { tea, view } => {
+        tea.unexecute()?;
+        refresh_view(view);
+        Ok(())
+      }
+      UndoBuffer { .. } => {
+        panic!("Should not try to unexecute an UndoBuffer action.")
+      }
+    }
+  }
+
+  fn description(&self) -> String {
+    use self::TaskDetailViewAction::*;
+
+    match self {
+      Scroll { .. } => panic!("Should not try to describe a Scroll action."),
+      TaskEvent { tea, .. } => tea.description(),
+      UndoBuffer { .. } => {
+        panic!("Should not try to describe an UndoBuffer action.")
+      }
+    }
+  }
+}
@@ -0,0 +1,75 @@
+use actions::{ForwardAction, ReversableAction};
+use commands::TaskEventCommand;
+use error::Result;
+use models::TaskEvent;
+use std::rc::Rc;
+use storage::Storage;
+
+#[derive(Clone)]
+pub enum TaskEventAction {
+  DestroyTaskEvent {
+    task_event_id: i32,
+    storage: Rc<dyn Storage>,
+  },
+}
+
+impl TaskEventAction {
+  pub fn prepare_from_cmd<F>(
+    cmd: TaskEventCommand,
+    storage: &Rc<dyn Storage>,
+    current_task_event_fn: F,
+  ) -> Option<TaskEventAction>
+  where
+    F: Fn() -> Option<TaskEvent>,
+  {
+    match cmd {
+      // Destroy an erroneous task event.
+      TaskEventCommand::DestroyTaskEvent => current_task_event_fn().map(
+        |task_event| TaskEventAction::DestroyTaskEvent {
+          task_event_id: task_event.id,
+          storage: Rc::clone(storage),
+        },
+      ),
+    }
+  }
+}
+
+impl ForwardAction for TaskEventAction {
+  fn execute(&mut self) -> Result<()> {
+    use self::TaskEventAction::*;
+
+    match self {
+      DestroyTaskEvent {
+        task_event_id,
+        storage,
+      } => {
+        storage.update_task_event_destroyed(*task_event_id, true)
+      }
+    }
+  }
+}
+
+impl ReversableAction for TaskEventAction {
+  fn unexecute(&mut self) -> Result<()> {
+    use self::TaskEventAction::*;
+
+    match self {
+      DestroyTaskEvent {
+        task_event_id,
+        storage,
+      } => {
+        storage.update_task_event_destroyed(*task_event_id, false)
+      }
+    }
+  }
+
+  fn description(&self) -> String {
+    use self::TaskEventAction::*;
+
+    match self {
+      DestroyTaskEvent { task_event_id, .. } => {
+        format!("destroyed event #{}", task_event_id)
+      }
+    }
+  }
+}
@@ -1,18 +1,21 @@
 use commands::TaskUpdateCommand;
-use diesel::pg::PgConnection;
 use models::*;
 use std::rc::Rc;
+use storage::Storage;
 use util::ui::UserInterface;
 
+// Some variants need to remember more than the old and new values. Those
+// list their extra fields after the value type.
 macro_rules! define_task_update_action {
-  ( $( ($value:ident, $type:ty) ),* ) => {
+  ( $( ($value:ident, $type:ty $(, $extra_field:ident: $extra_type:ty)*) ),* ) => {
     #[derive(Clone)]
     pub enum TaskUpdateAction { $(
       $value {
         task_id: i32,
         old_value: $type,
         new_value: $type,
-        connection: Rc<PgConnection>,
+        $($extra_field: $extra_type,)*
+        storage: Rc<dyn Storage>,
       },
     )* }
   }
@@ -21,13 +24,21 @@ macro_rules! define_task_update_action {
 define_task_update_action!(
   (UpdateDuration, TaskDuration),
   (UpdatePriority, TaskPriority),
+  (UpdateRecurrence, Option<TaskRecurrence>),
   (UpdateRequiresInternet, bool),
-  (UpdateStatus, TaskStatus),
+  // Status changes are recorded as task events. Completing a recurring
+  // task also spawns its next instance.
+  (
+    UpdateStatus,
+    TaskStatus,
+    task_event: Option<TaskEvent>,
+    spawned_task: Option<Task>
+  ),
   (UpdateTaskTitle, String)
 );
 
 macro_rules! prepare_action {
-  ( $enum_value:ident, $task_id:expr, $old_value:expr, $new_value:expr, $connection:expr ) => {
+  ( $enum_value:ident, $task_id:expr, $old_value:expr, $new_value:expr, $storage:expr $(, $extra_field:ident: $extra_value:expr)* ) => {
     if $old_value == $new_value {
       None
     } else {
@@ -35,18 +46,39 @@ macro_rules! prepare_action {
         task_id: $task_id,
         old_value: $old_value,
         new_value: $new_value,
-        connection: Rc::clone($connection),
+        $($extra_field: $extra_value,)*
+        storage: Rc::clone($storage),
       })
     }
   };
 }
 
+fn read_recurrence(
+  task: &Task,
+  ui: &UserInterface,
+) -> Option<Option<TaskRecurrence>> {
+  let current_value = match task.recurrence() {
+    None => String::from("none"),
+    Some(recurrence) => recurrence.to_string(),
+  };
+  let prompt = format!(
+    "Recurrence (daily, weekly:mon,thu, every:N, monthly:N, none) [{}]: ",
+    current_value
+  );
+
+  let str_value = ui.read_line(&prompt)?;
+  match str_value.trim() {
+    "none" => Some(None),
+    str_value => TaskRecurrence::parse(str_value).map(Some),
+  }
+}
+
 impl TaskUpdateAction {
   pub fn prepare_from_cmd(
     cmd: TaskUpdateCommand,
     task: &Task,
     ui: &UserInterface,
-    connection: &Rc<PgConnection>,
+    storage: &Rc<dyn Storage>,
   ) -> Option<TaskUpdateAction> {
     use self::TaskUpdateCommand as Cmd;
 
@@ -64,7 +96,21 @@ impl TaskUpdateAction {
           task.id,
           task.title.clone(),
           new_task_title,
-          connection
+          storage
+        )
+      }
+
+      // Edit how a task recurs.
+      Cmd::EditRecurrence => {
+        // Gives up on Ctrl-C or a rule that could not be parsed.
+        let new_recurrence = read_recurrence(task, ui)?;
+
+        prepare_action!(
+          UpdateRecurrence,
+          task.id,
+          task.recurrence(),
+          new_recurrence,
+          storage
         )
       }
 
@@ -74,7 +120,7 @@ impl TaskUpdateAction {
         task.id,
         task.requires_internet,
         !task.requires_internet,
-        connection
+        storage
       ),
 
       // Update a task's duration.
@@ -83,7 +129,7 @@ impl TaskUpdateAction {
         task.id,
         task.duration,
         task.duration.increment(direction),
-        connection
+        storage
       ),
 
       // Update a task's priority.
@@ -92,7 +138,7 @@ impl TaskUpdateAction {
         task.id,
         task.priority,
         task.priority.increment(direction),
-        connection
+        storage
       ),
 
       // Update a task's status.
@@ -101,7 +147,9 @@ impl TaskUpdateAction {
         task.id,
         task.status,
         new_task_status,
-        connection
+        storage,
+        task_event: None,
+        spawned_task: None
       ),
     }
   }
@@ -1,5 +1,7 @@
-use actions::TaskUpdateAction;
-use queries::task as task_queries;
+use actions::{ForwardAction, ReversableAction, TaskUpdateAction};
+use error::Result;
+use models::{Task, TaskEvent, TaskRecurrence, TaskStatus};
+use storage::Storage;
 
 macro_rules! update_match {
   // Notice that I need two sets, because one of them is for updating by
@@ -8,51 +10,209 @@ macro_rules! update_match {
     match $x {
       $(TaskUpdateAction::$enum_value {
         task_id,
-        connection,
+        storage,
         $value,
         ..
       } => {
-        task_queries::$update_fn(*task_id, *$value, connection);
+        storage.$update_fn(*task_id, *$value)?;
       })*
 
       $(TaskUpdateAction::$ref_enum_value {
         task_id,
-        connection,
+        storage,
         $value,
         ..
       } => {
-        task_queries::$ref_update_fn(*task_id, $value, connection);
+        storage.$ref_update_fn(*task_id, $value)?;
       })*
     }
   }
 }
 
-impl TaskUpdateAction {
-  pub fn execute(&mut self) {
+// Like other task events, the status event is revived rather than
+// recreated on redo.
+fn record_status_change(
+  task_id: i32,
+  new_status: TaskStatus,
+  task_event: &mut Option<TaskEvent>,
+  storage: &dyn Storage,
+) -> Result<()> {
+  if let Some(task_event) = task_event {
+    storage.update_task_event_destroyed(task_event.id, false)?;
+  } else {
+    *task_event = Some(storage.record_status_change(task_id, new_status)?);
+  }
+  Ok(())
+}
+
+// The next instance is created the first time a recurring task is
+// completed. After that it is merely revived on redo.
+fn spawn_next_recurrence(
+  task_id: i32,
+  spawned_task: &mut Option<Task>,
+  storage: &dyn Storage,
+) -> Result<()> {
+  if let Some(spawned_task) = spawned_task {
+    return storage.update_task_destroyed(spawned_task.id, false);
+  }
+
+  let task = storage.find_task(task_id)?;
+  if let Some(recurrence) = task.recurrence() {
+    let created_at = recurrence.next_occurrence(::chrono::Local::now());
+    *spawned_task = Some(storage.create_next_recurrence(&task, created_at)?);
+  }
+  Ok(())
+}
+
+fn describe_recurrence(recurrence: &Option<TaskRecurrence>) -> String {
+  match recurrence {
+    None => String::from("none"),
+    Some(recurrence) => recurrence.to_string(),
+  }
+}
+
+impl ForwardAction for TaskUpdateAction {
+  fn execute(&mut self) -> Result<()> {
     update_match!(
       self,
       new_value,
       (
         (UpdateDuration, update_duration),
         (UpdatePriority, update_priority),
+        (UpdateRecurrence, update_recurrence),
         (UpdateRequiresInternet, update_requires_internet),
         (UpdateStatus, update_status)
       ),
       ((UpdateTaskTitle, update_title))
-    )
+    );
+
+    if let TaskUpdateAction::UpdateStatus {
+      task_id,
+      new_value,
+      task_event,
+      spawned_task,
+      storage,
+      ..
+    } = self
+    {
+      record_status_change(*task_id, *new_value, task_event, &**storage)?;
+      if *new_value == TaskStatus::Completed {
+        spawn_next_recurrence(*task_id, spawned_task, &**storage)?;
+      }
+    }
+
+    Ok(())
   }
+}
 
-  pub fn unexecute(&mut self) {
+impl ReversableAction for TaskUpdateAction {
+  fn unexecute(&mut self) -> Result<()> {
     update_match!(
       self,
       old_value,
       (
         (UpdateDuration, update_duration),
         (UpdatePriority, update_priority),
+        (UpdateRecurrence, update_recurrence),
         (UpdateRequiresInternet, update_requires_internet),
         (UpdateStatus, update_status)
       ),
       ((UpdateTaskTitle, update_title))
+    );
+
+    if let TaskUpdateAction::UpdateStatus {
+      task_event,
+      spawned_task,
+      storage,
+      ..
+    } = self
+    {
+      if let Some(task_event) = task_event {
+        storage.update_task_event_destroyed(task_event.id, true)?;
+      }
+      if let Some(spawned_task) = spawned_task {
+        storage.update_task_destroyed(spawned_task.id, true)?;
+      }
+    }
+
+    Ok(())
+  }
+
+  fn description(&self) -> String {
+    use self::TaskUpdateAction::*;
+
+    let (task_id, attribute, old_value, new_value) = match self {
+      UpdateDuration {
+        task_id,
+        old_value,
+        new_value,
+        ..
+      } => (
+        task_id,
+        "duration",
+        format!("{:?}", old_value),
+        format!("{:?}", new_value),
+      ),
+      UpdatePriority {
+        task_id,
+        old_value,
+        new_value,
+        ..
+      } => (
+        task_id,
+        "priority",
+        format!("{:?}", old_value),
+        format!("{:?}", new_value),
+      ),
+      UpdateRecurrence {
+        task_id,
+        old_value,
+        new_value,
+        ..
+      } => (
+        task_id,
+        "recurrence",
+        describe_recurrence(old_value),
+        describe_recurrence(new_value),
+      ),
+      UpdateRequiresInternet {
+        task_id,
+        old_value,
+        new_value,
+        ..
+      } => (
+        task_id,
+        "requires internet",
+        old_value.to_string(),
+        new_value.to_string(),
+      ),
+      UpdateStatus {
+        task_id,
+        old_value,
+        new_value,
+        ..
+      } => (
+        task_id,
+        "status",
+        format!("{:?}", old_value),
+        format!("{:?}", new_value),
+      ),
+      UpdateTaskTitle {
+        task_id,
+        old_value,
+        new_value,
+        ..
+      } => (
+        task_id,
+        "title",
+        format!("{:?}", old_value),
+        format!("{:?}", new_value),
+      ),
+    };
+
+    format!(
+      "{} {}\u{2192}{} on #{}",
+      attribute, old_value, new_value, task_id
     )
   }
 }
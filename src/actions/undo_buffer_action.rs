@@ -1,6 +1,7 @@
 use actions::ForwardAction;
 use commands::UndoBufferCommand;
 use components::UndoBuffer;
+use error::Result;
 use std::rc::Rc;
 
 #[derive(Clone)]
@@ -22,12 +23,14 @@ impl UndoBufferAction {
 }
 
 impl ForwardAction for UndoBufferAction {
-  fn execute(&mut self) {
+  fn execute(&mut self) -> Result<()> {
     use self::UndoBufferCommand::*;
 
     match self.cmd {
+      Newer => self.undo_buffer.go_to_newer(),
+      Older => self.undo_buffer.go_to_older(),
       Redo => self.undo_buffer.redo(),
       Undo => self.undo_buffer.undo(),
-    };
+    }
   }
 }
@@ -1,20 +1,107 @@
+use commands::{ActiveTasksViewCommand, Keymap};
+use config::Config;
 use std::rc::Rc;
-use util::UserInterface;
-use views::ActiveTasksView;
+use storage::Storage;
+use util::{
+  line_buffer::{LineBuffer, Region, TerminalLine},
+  ui::ColorPair,
+  UserInterface,
+};
+use views::{ActiveTasksView, DoneTodayView, View, ViewResult};
+
+const QUIT_KEY: char = 'q';
+const SWITCH_ROOT_VIEW_KEY: char = '\t';
+// Handled here before any view sees them, so views can't bind them.
+pub const RESERVED_KEYS: [char; 2] = [QUIT_KEY, SWITCH_ROOT_VIEW_KEY];
 
 pub struct Application {
-  // TODO: Will someday become multiple views.
-  view: Rc<ActiveTasksView>,
+  // Tab cycles through these when no other view is pushed on top.
+  root_views: Vec<Rc<dyn View>>,
+  root_view_idx: usize,
+  // Views pushed on top of the current root view.
+  view_stack: Vec<Rc<dyn View>>,
+  title_bar: LineBuffer,
   ui: Rc<UserInterface>,
 }
 
 impl Application {
-  pub fn new() -> Application {
-    let ui = Rc::new(UserInterface::initscr());
-    Application {
-      view: ActiveTasksView::new(&ui),
+  pub fn new(
+    config: &Config,
+    keymap: Keymap<ActiveTasksViewCommand>,
+    ui: &Rc<UserInterface>,
+    storage: &Rc<dyn Storage>,
+  ) -> Application {
+    let ui = Rc::clone(ui);
+
+    let root_views: Vec<Rc<dyn View>> = vec![
+      ActiveTasksView::new(&ui, storage, config, keymap),
+      DoneTodayView::new(&ui, storage),
+    ];
+
+    let application = Application {
+      root_views,
+      root_view_idx: 0,
+      view_stack: vec![],
+      title_bar: LineBuffer::new(&ui, Region::rows(0, 1)),
       ui,
+    };
+    application.redraw();
+
+    application
+  }
+
+  fn current_view(&self) -> Rc<dyn View> {
+    let view = match self.view_stack.last() {
+      None => &self.root_views[self.root_view_idx],
+      Some(view) => view,
+    };
+
+    Rc::clone(view)
+  }
+
+  // Repaints everything, e.g. when a different view comes on screen.
+  // Each view keeps its own scroller, so nobody loses their position.
+  fn redraw(&self) {
+    self.ui.clear();
+    self.title_bar.mark_all_dirty();
+    self.redraw_title_bar();
+    self.current_view().redraw();
+  }
+
+  // Titles can change as keys are handled, so this is cheap enough to
+  // call after every key; the LineBuffer only repaints what changed.
+  fn redraw_title_bar(&self) {
+    let mut titles = vec![self.root_views[self.root_view_idx].title()];
+    titles.extend(self.view_stack.iter().map(|view| view.title()));
+    let text = format!(" {} \n", titles.join(" > "));
+    self
+      .title_bar
+      .replace_line(0, TerminalLine { text, color: ColorPair::Bold });
+    self.title_bar.redraw();
+  }
+
+  fn switch_root_view(&mut self) {
+    if !self.view_stack.is_empty() {
+      return;
+    }
+
+    self.root_view_idx = (self.root_view_idx + 1) % self.root_views.len();
+    self.redraw();
+  }
+
+  // Returns false when there is nothing left to pop and we should quit.
+  fn pop_view(&mut self) -> bool {
+    if self.view_stack.pop().is_none() {
+      return false;
     }
+
+    self.redraw();
+    true
+  }
+
+  fn push_view(&mut self, view: Rc<dyn View>) {
+    self.view_stack.push(view);
+    self.redraw();
   }
 
   pub fn run(&mut self) {
@@ -24,17 +111,19 @@ impl Application {
         Some(ch) => ch,
       };
 
-      if ch == 'q' {
-        break;
+      match ch {
+        QUIT_KEY => {
+          if !self.pop_view() {
+            break;
+          }
+        }
+        SWITCH_ROOT_VIEW_KEY => self.switch_root_view(),
+        ch => match self.current_view().handle_key(ch) {
+          ViewResult::KeyHandled => self.redraw_title_bar(),
+          ViewResult::PushView(view) => self.push_view(view),
+          ViewResult::RedrawScreen => self.redraw(),
+        },
       }
-
-      ActiveTasksView::handle_key(&self.view, ch);
     }
   }
 }
-
-impl Default for Application {
-  fn default() -> Application {
-    Application::new()
-  }
-}
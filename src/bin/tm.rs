@@ -1,7 +1,90 @@
 extern crate task_manager;
 
+use std::env;
+use std::fs::File;
+use std::path::PathBuf;
+use std::process;
+use std::rc::Rc;
 use task_manager::application::Application;
+use task_manager::commands::ActiveTasksViewCommand;
+use task_manager::config::Config;
+use task_manager::storage::open_storage;
+use task_manager::util::terminal::{
+  read_input_log, PancursesTerminal, RecordingTerminal, ReplayTerminal,
+  Terminal,
+};
+use task_manager::util::UserInterface;
+
+#[derive(Default)]
+struct Args {
+  config_path: Option<PathBuf>,
+  record_path: Option<PathBuf>,
+  replay_path: Option<PathBuf>,
+}
+
+// Every flag takes a path: `--config`, `--record` and `--replay`.
+fn parse_args() -> Args {
+  let mut args = Args::default();
+  let mut argv = env::args().skip(1);
+
+  while let Some(flag) = argv.next() {
+    let path = match flag.as_str() {
+      "--config" => &mut args.config_path,
+      "--record" => &mut args.record_path,
+      "--replay" => &mut args.replay_path,
+      _ => exit_with_error(format!("Unknown argument: {}", flag)),
+    };
+    match argv.next() {
+      None => exit_with_error(format!("{} needs a path", flag)),
+      Some(value) => *path = Some(PathBuf::from(value)),
+    }
+  }
+
+  args
+}
+
+fn exit_with_error(message: String) -> ! {
+  eprintln!("{}", message);
+  process::exit(1);
+}
 
 fn main() {
-  Application::new().run();
+  let args = parse_args();
+  let config = Config::load(args.config_path)
+    .unwrap_or_else(|err| exit_with_error(err));
+
+  // Check everything we can before curses takes over the terminal, so
+  // errors are readable.
+  let keymap = ActiveTasksViewCommand::keymap(&config.keybindings)
+    .unwrap_or_else(|err| exit_with_error(format!("Bad keymap: {}", err)));
+  let replay = args.replay_path.map(|path| {
+    read_input_log(&path).unwrap_or_else(|err| {
+      exit_with_error(format!("Bad replay file {:?}: {}", path, err))
+    })
+  });
+  let record_log = args.record_path.map(|path| {
+    File::create(&path).unwrap_or_else(|err| {
+      exit_with_error(format!("Can't record to {:?}: {}", path, err))
+    })
+  });
+  let storage = open_storage(&config.database_url)
+    .unwrap_or_else(|err| exit_with_error(err.to_string()));
+  if replay.is_some() {
+    // Whatever the replay does is rolled back on exit.
+    storage.make_disposable().unwrap_or_else(|err| {
+      exit_with_error(format!("Can't set up replay: {}", err))
+    });
+  }
+
+  let mut terminal: Rc<dyn Terminal> =
+    Rc::new(PancursesTerminal::initscr(&config.colors));
+  if let Some(inputs) = replay {
+    terminal = Rc::new(ReplayTerminal::new(terminal, inputs));
+  }
+  if let Some(log) = record_log {
+    terminal = Rc::new(RecordingTerminal::new(terminal, log));
+  }
+  let ui = Rc::new(UserInterface::new(terminal));
+
+  Application::new(&config, keymap, &ui, &storage).run();
 }
@@ -1,62 +1,135 @@
 use actions::ActiveTasksViewAction;
+use application::RESERVED_KEYS;
 use commands::{
-  FiltererCommand, ScrollCommand, TaskCommand, TaskUpdateCommand,
-  TasksScrollCommand, UndoBufferCommand,
+  FiltererCommand, Keybinding, Keymap, KeymapError, OpenViewCommand,
+  ScrollCommand, TaskCommand, TaskUpdateCommand, TasksScrollCommand,
+  UndoBufferCommand,
 };
 use models::{Direction, End, TaskStatus};
+use std::collections::HashMap;
 use std::rc::Rc;
 use views::ActiveTasksView;
 
 #[derive(Clone, Copy, Debug)]
 pub enum ActiveTasksViewCommand {
+  // The next task command goes to every task listed.
+  ApplyToAll,
+  // Reads a line at the `:` prompt.
+  CommandLine,
+  CycleScoringStrategy,
+  DeleteFilterPreset,
   Filterer(FiltererCommand),
+  OpenView(OpenViewCommand),
+  // Goes to the next or previous match of the latest title search.
+  RepeatSearch(Direction),
+  // Saves the current filter under a name, replacing any preset of the
+  // same name.
+  SaveFilterPreset,
   Scroll(ScrollCommand),
+  // Reads a fuzzy title search a key at a time, moving as it goes.
+  SearchTitles,
+  ToggleScoreBreakdown,
   TasksScroll(TasksScrollCommand),
   Task(TaskCommand),
   UndoBuffer(UndoBufferCommand),
 }
 
 impl ActiveTasksViewCommand {
-  pub fn from_key(ch: char) -> Option<ActiveTasksViewCommand> {
+  // Every command that can be bound to keys: its name in the keymap
+  // file, its default keys, and the command itself.
+  fn default_keybindings() -> Vec<Keybinding<ActiveTasksViewCommand>> {
     use self::{
       ActiveTasksViewCommand::*, Direction::*, End::*,
       FiltererCommand::*, ScrollCommand::*, TaskCommand::*,
       TaskStatus::*, TaskUpdateCommand::*, TasksScrollCommand::*,
+      UndoBufferCommand::*,
     };
 
-    let command = match ch {
-      'F' => Filterer(FilterByRequiresInternet),
-      '$' => ActiveTasksViewCommand::Scroll(Jump(Bottom)),
-      'g' => ActiveTasksViewCommand::Scroll(Jump(Top)),
-      '/' => ActiveTasksViewCommand::TasksScroll(JumpToTask),
-      'k' => {
-        ActiveTasksViewCommand::Scroll(ScrollCommand::Scroll(Decrease))
-      }
-      'j' => {
-        ActiveTasksViewCommand::Scroll(ScrollCommand::Scroll(Increase))
-      }
-      'n' => Task(CreateTask),
-      'r' => Task(RecordTaskEffort),
-      'l' => Task(RequestTaskDelay),
-      'L' => Task(RequestTaskAgeReset),
-      'e' => Task(UpdateTask(EditTaskTitle)),
-      'i' => Task(UpdateTask(ToggleRequiresInternet)),
-      'd' => Task(UpdateTask(UpdateDuration(Decrease))),
-      'D' => Task(UpdateTask(UpdateDuration(Increase))),
-      'p' => Task(UpdateTask(UpdatePriority(Decrease))),
-      'P' => Task(UpdateTask(UpdatePriority(Increase))),
-      'a' => Task(UpdateTask(UpdateStatus(Abandoned))),
-      'c' => Task(UpdateTask(UpdateStatus(Completed))),
-      'U' => {
-        ActiveTasksViewCommand::UndoBuffer(UndoBufferCommand::Redo)
-      }
-      'u' => {
-        ActiveTasksViewCommand::UndoBuffer(UndoBufferCommand::Undo)
-      }
-      _ => return None,
-    };
+    vec![
+      ("filter_by_requires_internet", "F", Filterer(FilterByRequiresInternet)),
+      ("filter_by_min_priority", "fp", Filterer(FilterByMinPriority)),
+      ("filter_by_max_priority", "fP", Filterer(FilterByMaxPriority)),
+      ("filter_by_duration", "fd", Filterer(FilterByDuration)),
+      ("filter_older_than", "fa", Filterer(FilterOlderThan)),
+      ("filter_younger_than", "fA", Filterer(FilterYoungerThan)),
+      ("filter_by_title", "ft", Filterer(FilterByTitle)),
+      ("filter_by_recent_effort", "fr", Filterer(FilterByRecentEffort)),
+      ("filter_by_delays", "fl", Filterer(FilterByDelays)),
+      ("negate_last_filter", "f!", Filterer(NegateLastFilter)),
+      ("or_last_filters", "f|", Filterer(OrLastFilters)),
+      ("clear_filter", "fc", Filterer(ClearFilter)),
+      ("apply_filter_preset", "fs", Filterer(ApplyPreset)),
+      ("save_filter_preset", "fS", SaveFilterPreset),
+      ("delete_filter_preset", "fx", DeleteFilterPreset),
+      ("command_line", ":", CommandLine),
+      ("help", "?", OpenView(OpenViewCommand::Help)),
+      ("toggle_score_breakdown", "s", ToggleScoreBreakdown),
+      ("cycle_scoring_strategy", "S", CycleScoringStrategy),
+      ("task_detail", "Enter", OpenView(OpenViewCommand::TaskDetail)),
+      ("undo_history", "h", OpenView(OpenViewCommand::UndoHistory)),
+      ("jump_to_bottom", "$", ActiveTasksViewCommand::Scroll(Jump(Bottom))),
+      ("jump_to_top", "g", ActiveTasksViewCommand::Scroll(Jump(Top))),
+      ("jump_to_task", "#", ActiveTasksViewCommand::TasksScroll(JumpToTask)),
+      ("page_down", "C-f", ActiveTasksViewCommand::TasksScroll(Page(Increase))),
+      ("page_up", "C-b", ActiveTasksViewCommand::TasksScroll(Page(Decrease))),
+      (
+        "half_page_down",
+        "C-d",
+        ActiveTasksViewCommand::TasksScroll(HalfPage(Increase)),
+      ),
+      (
+        "half_page_up",
+        "C-u",
+        ActiveTasksViewCommand::TasksScroll(HalfPage(Decrease)),
+      ),
+      ("search_titles", "/", SearchTitles),
+      ("search_next", "n", RepeatSearch(Increase)),
+      ("search_previous", "N", RepeatSearch(Decrease)),
+      (
+        "scroll_up",
+        "k",
+        ActiveTasksViewCommand::Scroll(ScrollCommand::Scroll(Decrease)),
+      ),
+      (
+        "scroll_down",
+        "j",
+        ActiveTasksViewCommand::Scroll(ScrollCommand::Scroll(Increase)),
+      ),
+      ("create_task", "o", Task(CreateTask)),
+      ("record_task_effort", "r", Task(RecordTaskEffort)),
+      ("request_task_delay", "l", Task(RequestTaskDelay)),
+      ("request_task_age_reset", "L", Task(RequestTaskAgeReset)),
+      ("edit_task_title", "e", Task(UpdateTask(EditTaskTitle))),
+      ("edit_recurrence", "R", Task(UpdateTask(EditRecurrence))),
+      (
+        "toggle_requires_internet",
+        "i",
+        Task(UpdateTask(ToggleRequiresInternet)),
+      ),
+      ("decrease_duration", "d", Task(UpdateTask(UpdateDuration(Decrease)))),
+      ("increase_duration", "D", Task(UpdateTask(UpdateDuration(Increase)))),
+      ("decrease_priority", "p", Task(UpdateTask(UpdatePriority(Decrease)))),
+      ("increase_priority", "P", Task(UpdateTask(UpdatePriority(Increase)))),
+      ("abandon_task", "a", Task(UpdateTask(UpdateStatus(Abandoned)))),
+      ("complete_task", "c", Task(UpdateTask(UpdateStatus(Completed)))),
+      ("apply_to_all", "*", ApplyToAll),
+      ("redo", "U", ActiveTasksViewCommand::UndoBuffer(Redo)),
+      ("undo", "u", ActiveTasksViewCommand::UndoBuffer(Undo)),
+      ("undo_older", "-", ActiveTasksViewCommand::UndoBuffer(Older)),
+      ("undo_newer", "+", ActiveTasksViewCommand::UndoBuffer(Newer)),
+    ]
+  }
 
-    Some(command)
+  // The defaults with the user's remappings applied on top. A remapped
+  // command no longer answers to its default keys.
+  pub fn keymap(
+    remappings: &HashMap<String, String>,
+  ) -> Result<Keymap<ActiveTasksViewCommand>, KeymapError> {
+    Keymap::new(
+      ActiveTasksViewCommand::default_keybindings(),
+      remappings,
+      &RESERVED_KEYS,
+    )
   }
 
   pub fn to_action(
@@ -66,3 +139,4 @@ impl ActiveTasksViewCommand {
     ActiveTasksViewAction::prepare_from_command(self, &view)
   }
 }
+
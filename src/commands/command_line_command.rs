@@ -0,0 +1,24 @@
+use components::{parse_filter_query, FilterPredicate};
+
+// What can be typed at the `:` prompt, vim style, e.g.
+// `filter prio>=med -net`.
+#[derive(Clone, Debug)]
+pub enum CommandLineCommand {
+  // `None` clears the filter.
+  Filter(Option<FilterPredicate>),
+}
+
+impl CommandLineCommand {
+  pub fn parse(line: &str) -> Result<CommandLineCommand, String> {
+    let line = line.trim();
+    let (name, rest) = match line.find(char::is_whitespace) {
+      None => (line, ""),
+      Some(idx) => line.split_at(idx),
+    };
+
+    match name {
+      "filter" => parse_filter_query(rest).map(CommandLineCommand::Filter),
+      _ => Err(format!("Unknown command: {}", name)),
+    }
+  }
+}
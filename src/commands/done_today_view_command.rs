@@ -0,0 +1,36 @@
+use actions::ScrollAction;
+use commands::ScrollCommand;
+use components::Scroller;
+use models::{Direction, End};
+use std::rc::Rc;
+use views::DoneTodayView;
+
+#[derive(Clone, Copy, Debug)]
+pub enum DoneTodayViewCommand {
+  Scroll(ScrollCommand),
+}
+
+impl DoneTodayViewCommand {
+  pub fn from_key(ch: char) -> Option<DoneTodayViewCommand> {
+    use self::{DoneTodayViewCommand::*, Direction::*, End::*};
+
+    let command = match ch {
+      '$' => Scroll(ScrollCommand::Jump(Bottom)),
+      'g' => Scroll(ScrollCommand::Jump(Top)),
+      'k' => Scroll(ScrollCommand::Scroll(Decrease)),
+      'j' => Scroll(ScrollCommand::Scroll(Increase)),
+      _ => return None,
+    };
+
+    Some(command)
+  }
+
+  pub fn to_action(self, view: &DoneTodayView) -> Option<ScrollAction> {
+    match self {
+      DoneTodayViewCommand::Scroll(sc) => {
+        let scroller = Rc::clone(&view.scroller) as Rc<dyn Scroller>;
+        sc.to_action(&scroller)
+      }
+    }
+  }
+}
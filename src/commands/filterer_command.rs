@@ -1,19 +1,38 @@
 use actions::FiltererAction;
 use components::Filterer;
 use std::rc::Rc;
+use storage::Storage;
 use util::ui::UserInterface;
 
+// Each `FilterBy...` is added to the filter with `and`. The last one
+// added can then be negated or made an alternative with `or`.
+// `ApplyPreset` replaces the whole filter with a saved one.
 #[derive(Clone, Copy, Debug)]
 pub enum FiltererCommand {
+  ApplyPreset,
+  ClearFilter,
+  FilterByDelays,
+  FilterByDuration,
+  FilterByMaxPriority,
+  FilterByMinPriority,
+  FilterByRecentEffort,
   FilterByRequiresInternet,
+  FilterByTitle,
+  FilterOlderThan,
+  FilterYoungerThan,
+  NegateLastFilter,
+  OrLastFilters,
 }
 
 impl FiltererCommand {
+  // `None` if the user gave up or the filter would stay the same, or an
+  // error message if what they typed can't be used.
   pub fn to_action(
     self,
     ui: &UserInterface,
+    storage: &Rc<dyn Storage>,
     filterer: &Rc<Filterer>,
-  ) -> Option<FiltererAction> {
-    FiltererAction::prepare_from_cmd(self, ui, filterer)
+  ) -> Option<Result<FiltererAction, String>> {
+    FiltererAction::prepare_from_cmd(self, ui, storage, filterer)
   }
 }
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::fmt;
+
+pub type KeySequence = Vec<char>;
+
+// Command name, keys, command.
+pub type Keybinding<C> = (&'static str, &'static str, C);
+
+pub enum KeymapMatch<C> {
+  Command(C),
+  // The keys so far start some sequence; wait for more.
+  Prefix,
+  NoMatch,
+}
+
+#[derive(Debug)]
+pub enum KeymapError {
+  UnknownCommand(String),
+  EmptyKeySequence(String),
+  ReservedKey {
+    command: String,
+    key: char,
+  },
+  Conflict {
+    command: String,
+    other_command: String,
+    keys: KeySequence,
+  },
+  Shadowed {
+    command: String,
+    keys: KeySequence,
+    other_command: String,
+    other_keys: KeySequence,
+  },
+}
+
+impl fmt::Display for KeymapError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    use self::KeymapError::*;
+
+    match self {
+      UnknownCommand(command) => {
+        write!(f, "Unknown command in keymap: {}", command)
+      }
+      EmptyKeySequence(command) => {
+        write!(f, "No keys given for {}", command)
+      }
+      ReservedKey { command, key } => write!(
+        f,
+        "{} uses {}, which is reserved",
+        command,
+        format_key_sequence(&[*key])
+      ),
+      Conflict {
+        command,
+        other_command,
+        keys,
+      } => write!(
+        f,
+        "{} and {} are both bound to {}",
+        command,
+        other_command,
+        format_key_sequence(keys)
+      ),
+      Shadowed {
+        command,
+        keys,
+        other_command,
+        other_keys,
+      } => write!(
+        f,
+        "{} ({}) can never be typed because {} ({}) starts it",
+        command,
+        format_key_sequence(keys),
+        other_command,
+        format_key_sequence(other_keys)
+      ),
+    }
+  }
+}
+
+// "Enter" and control keys like "C-d" are the only keys with names.
+// Anything else is typed as is, one key per character, so "gg" is a
+// sequence of two keys.
+pub fn parse_key_sequence(keys: &str) -> KeySequence {
+  if keys == "Enter" {
+    return vec!['\n'];
+  }
+  if let Some(key) = parse_control_key(keys) {
+    return vec![key];
+  }
+
+  keys.chars().collect()
+}
+
+// Ctrl-A is 1 and so on up to Ctrl-Z.
+fn parse_control_key(keys: &str) -> Option<char> {
+  let letter = keys.strip_prefix("C-")?;
+  let mut chars = letter.chars();
+  match (chars.next(), chars.next()) {
+    (Some(letter @ 'a'..='z'), None) => {
+      Some((letter as u8 - b'a' + 1) as char)
+    }
+    _ => None,
+  }
+}
+
+pub fn format_key_sequence(keys: &[char]) -> String {
+  keys
+    .iter()
+    .map(|key| match key {
+      '\n' => String::from("Enter"),
+      '\t' => String::from("Tab"),
+      '\u{1}'..='\u{1a}' => {
+        format!("C-{}", (*key as u8 - 1 + b'a') as char)
+      }
+      key => key.to_string(),
+    }).collect()
+}
+
+// Maps key sequences to commands. Built once at startup from a table
+// of defaults plus the user's remappings, and checked so that every
+// command can actually be typed.
+pub struct Keymap<C> {
+  bindings: Vec<(&'static str, KeySequence, C)>,
+}
+
+impl<C: Copy> Keymap<C> {
+  pub fn new(
+    defaults: Vec<Keybinding<C>>,
+    remappings: &HashMap<String, String>,
+    reserved_keys: &[char],
+  ) -> Result<Keymap<C>, KeymapError> {
+    let mut bindings: Vec<_> = defaults
+      .into_iter()
+      .map(|(name, keys, command)| (name, parse_key_sequence(keys), command))
+      .collect();
+
+    // Sorted so that errors come out the same way every time.
+    let mut remappings: Vec<_> = remappings.iter().collect();
+    remappings.sort();
+    for (name, keys) in remappings {
+      let binding = bindings
+        .iter_mut()
+        .find(|(binding_name, _, _)| binding_name == name)
+        .ok_or_else(|| KeymapError::UnknownCommand(name.clone()))?;
+      binding.1 = parse_key_sequence(keys);
+    }
+
+    let keymap = Keymap { bindings };
+    keymap.validate(reserved_keys)?;
+    Ok(keymap)
+  }
+
+  fn validate(&self, reserved_keys: &[char]) -> Result<(), KeymapError> {
+    for (name, keys, _) in &self.bindings {
+      if keys.is_empty() {
+        return Err(KeymapError::EmptyKeySequence(name.to_string()));
+      }
+
+      if let Some(key) = keys.iter().find(|key| reserved_keys.contains(key))
+      {
+        return Err(KeymapError::ReservedKey {
+          command: name.to_string(),
+          key: *key,
+        });
+      }
+    }
+
+    for (idx, (name, keys, _)) in self.bindings.iter().enumerate() {
+      for (other_name, other_keys, _) in &self.bindings[(idx + 1)..] {
+        if keys == other_keys {
+          return Err(KeymapError::Conflict {
+            command: name.to_string(),
+            other_command: other_name.to_string(),
+            keys: keys.clone(),
+          });
+        }
+
+        // A sequence that starts a longer one fires before the longer
+        // one can be finished.
+        let (shorter, longer) = if keys.len() < other_keys.len() {
+          ((name, keys), (other_name, other_keys))
+        } else {
+          ((other_name, other_keys), (name, keys))
+        };
+        if longer.1.starts_with(shorter.1) {
+          return Err(KeymapError::Shadowed {
+            command: longer.0.to_string(),
+            keys: longer.1.clone(),
+            other_command: shorter.0.to_string(),
+            other_keys: shorter.1.clone(),
+          });
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  pub fn lookup(&self, keys: &[char]) -> KeymapMatch<C> {
+    let mut is_prefix = false;
+    for (_, binding_keys, command) in &self.bindings {
+      if binding_keys.as_slice() == keys {
+        return KeymapMatch::Command(*command);
+      }
+      if binding_keys.starts_with(keys) {
+        is_prefix = true;
+      }
+    }
+
+    if is_prefix {
+      KeymapMatch::Prefix
+    } else {
+      KeymapMatch::NoMatch
+    }
+  }
+
+  pub fn keys_for(&self, name: &str) -> Option<&[char]> {
+    self
+      .bindings
+      .iter()
+      .find(|(binding_name, _, _)| *binding_name == name)
+      .map(|(_, keys, _)| keys.as_slice())
+  }
+}
@@ -1,11 +1,28 @@
 mod active_tasks_view_command;
+mod command_line_command;
+mod done_today_view_command;
 mod filterer_command;
+mod keymap;
+mod open_view_command;
 mod scroll_command;
 mod task_command;
+mod task_detail_view_command;
+mod task_event_command;
 mod undo_buffer_command;
+mod undo_history_view_command;
 
 pub use self::active_tasks_view_command::ActiveTasksViewCommand;
+pub use self::command_line_command::CommandLineCommand;
+pub use self::done_today_view_command::DoneTodayViewCommand;
 pub use self::filterer_command::FiltererCommand;
+pub use self::keymap::{
+  format_key_sequence, KeySequence, Keybinding, Keymap, KeymapError,
+  KeymapMatch,
+};
+pub use self::open_view_command::OpenViewCommand;
 pub use self::scroll_command::{ScrollCommand, TasksScrollCommand};
 pub use self::task_command::{TaskCommand, TaskUpdateCommand};
+pub use self::task_detail_view_command::TaskDetailViewCommand;
+pub use self::task_event_command::TaskEventCommand;
 pub use self::undo_buffer_command::UndoBufferCommand;
+pub use self::undo_history_view_command::UndoHistoryViewCommand;
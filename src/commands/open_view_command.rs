@@ -0,0 +1,7 @@
+// Commands that push a new view onto the `Application`'s view stack.
+#[derive(Clone, Copy, Debug)]
+pub enum OpenViewCommand {
+  Help,
+  TaskDetail,
+  UndoHistory,
+}
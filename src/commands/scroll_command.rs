@@ -12,7 +12,10 @@ pub enum ScrollCommand {
 
 #[derive(Clone, Copy, Debug)]
 pub enum TasksScrollCommand {
+  // Half as far as `Page`.
+  HalfPage(Direction),
   JumpToTask,
+  Page(Direction),
 }
 
 impl ScrollCommand {
@@ -34,29 +37,49 @@ impl ScrollCommand {
 }
 
 impl TasksScrollCommand {
+  // `None` if the user gave up, or an error message if what they typed
+  // can't be used. A page is as many tasks as fit on screen.
   pub fn to_action(
     self,
     ui: &UserInterface,
     scroller: &Rc<TasksScroller>,
-  ) -> Option<TasksScrollAction> {
+    page_height: i32,
+  ) -> Option<Result<TasksScrollAction, String>> {
+    let scroll_by = |direction, num_rows| {
+      Some(Ok(TasksScrollAction::ScrollBy {
+        direction,
+        num_rows: ::std::cmp::max(num_rows, 1),
+        scroller: Rc::clone(scroller),
+      }))
+    };
+
     match self {
+      TasksScrollCommand::HalfPage(direction) => {
+        scroll_by(direction, page_height / 2)
+      }
+      TasksScrollCommand::Page(direction) => scroll_by(direction, page_height),
       TasksScrollCommand::JumpToTask => {
         read_task_to_jump_to(ui).map(|task_id| {
-          TasksScrollAction::JumpToTask {
+          task_id.map(|task_id| TasksScrollAction::JumpToTask {
             task_id,
             scroller: Rc::clone(scroller),
-          }
+          })
         })
       }
     }
   }
 }
 
-fn read_task_to_jump_to(ui: &UserInterface) -> Option<i32> {
+fn read_task_to_jump_to(ui: &UserInterface) -> Option<Result<i32, String>> {
   let task_id_str = match ui.read_line("Task id to jump to: ") {
     None => return None,
     Some(task_id_str) => task_id_str,
   };
 
-  task_id_str.parse().ok()
+  let task_id_str = task_id_str.trim();
+  Some(
+    task_id_str
+      .parse()
+      .map_err(|_| format!("Not a task id: {}", task_id_str)),
+  )
 }
@@ -1,7 +1,7 @@
 use actions::TaskAction;
-use diesel::pg::PgConnection;
 use models::{Direction, Task, TaskStatus};
 use std::rc::Rc;
+use storage::Storage;
 use util::ui::UserInterface;
 
 #[derive(Clone, Copy, Debug)]
@@ -15,6 +15,7 @@ pub enum TaskCommand {
 
 #[derive(Clone, Copy, Debug)]
 pub enum TaskUpdateCommand {
+  EditRecurrence,
   EditTaskTitle,
   ToggleRequiresInternet,
   UpdateDuration(Direction),
@@ -23,15 +24,30 @@ pub enum TaskUpdateCommand {
 }
 
 impl TaskCommand {
+  // The ones that prompt can't, since that would be one prompt per task.
+  pub fn can_apply_to_many(self) -> bool {
+    use self::TaskUpdateCommand::*;
+
+    match self {
+      TaskCommand::CreateTask
+      | TaskCommand::UpdateTask(EditRecurrence)
+      | TaskCommand::UpdateTask(EditTaskTitle) => false,
+      TaskCommand::RecordTaskEffort
+      | TaskCommand::RequestTaskAgeReset
+      | TaskCommand::RequestTaskDelay
+      | TaskCommand::UpdateTask(_) => true,
+    }
+  }
+
   pub fn to_action<F>(
     self,
     ui: &UserInterface,
-    connection: &Rc<PgConnection>,
+    storage: &Rc<dyn Storage>,
     current_task_fn: F,
   ) -> Option<TaskAction>
   where
     F: Fn() -> Option<Task>,
   {
-    TaskAction::prepare_from_cmd(self, ui, connection, current_task_fn)
+    TaskAction::prepare_from_cmd(self, ui, storage, current_task_fn)
   }
 }
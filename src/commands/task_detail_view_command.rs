@@ -0,0 +1,41 @@
+use actions::TaskDetailViewAction;
+use commands::{ScrollCommand, TaskEventCommand, UndoBufferCommand};
+use models::{Direction, End};
+use std::rc::Rc;
+use views::TaskDetailView;
+
+#[derive(Clone, Copy, Debug)]
+pub enum TaskDetailViewCommand {
+  Scroll(ScrollCommand),
+  TaskEvent(TaskEventCommand),
+  UndoBuffer(UndoBufferCommand),
+}
+
+impl TaskDetailViewCommand {
+  pub fn from_key(ch: char) -> Option<TaskDetailViewCommand> {
+    use self::{
+      Direction::*, End::*, TaskDetailViewCommand::*,
+      TaskEventCommand::*,
+    };
+
+    let command = match ch {
+      '$' => Scroll(ScrollCommand::Jump(Bottom)),
+      'g' => Scroll(ScrollCommand::Jump(Top)),
+      'k' => Scroll(ScrollCommand::Scroll(Decrease)),
+      'j' => Scroll(ScrollCommand::Scroll(Increase)),
+      'x' => TaskEvent(DestroyTaskEvent),
+      'U' => UndoBuffer(UndoBufferCommand::Redo),
+      'u' => UndoBuffer(UndoBufferCommand::Undo),
+      _ => return None,
+    };
+
+    Some(command)
+  }
+
+  pub fn to_action(
+    self,
+    view: &Rc<TaskDetailView>,
+  ) -> Option<TaskDetailViewAction> {
+    TaskDetailViewAction::prepare_from_command(self, view)
+  }
+}
@@ -0,0 +1,26 @@
+use actions::TaskEventAction;
+use models::TaskEvent;
+use std::rc::Rc;
+use storage::Storage;
+
+#[derive(Clone, Copy, Debug)]
+pub enum TaskEventCommand {
+  DestroyTaskEvent,
+}
+
+impl TaskEventCommand {
+  pub fn to_action<F>(
+    self,
+    storage: &Rc<dyn Storage>,
+    current_task_event_fn: F,
+  ) -> Option<TaskEventAction>
+  where
+    F: Fn() -> Option<TaskEvent>,
+  {
+    TaskEventAction::prepare_from_cmd(
+      self,
+      storage,
+      current_task_event_fn,
+    )
+  }
+}
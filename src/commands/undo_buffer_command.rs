@@ -4,6 +4,9 @@ use std::rc::Rc;
 
 #[derive(Clone, Copy, Debug)]
 pub enum UndoBufferCommand {
+  // Move through the undo tree by time, across branches.
+  Newer,
+  Older,
   Redo,
   Undo,
 }
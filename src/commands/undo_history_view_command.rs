@@ -0,0 +1,26 @@
+use commands::ScrollCommand;
+use models::{Direction, End};
+
+#[derive(Clone, Copy, Debug)]
+pub enum UndoHistoryViewCommand {
+  // Undo or redo until the selected point is reached.
+  JumpToSelected,
+  Scroll(ScrollCommand),
+}
+
+impl UndoHistoryViewCommand {
+  pub fn from_key(ch: char) -> Option<UndoHistoryViewCommand> {
+    use self::{Direction::*, End::*, UndoHistoryViewCommand::*};
+
+    let command = match ch {
+      '\n' => JumpToSelected,
+      '$' => Scroll(ScrollCommand::Jump(Bottom)),
+      'g' => Scroll(ScrollCommand::Jump(Top)),
+      'k' => Scroll(ScrollCommand::Scroll(Decrease)),
+      'j' => Scroll(ScrollCommand::Scroll(Increase)),
+      _ => return None,
+    };
+
+    Some(command)
+  }
+}
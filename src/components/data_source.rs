@@ -1,21 +1,29 @@
-use components::result::TaskResult;
-use diesel::pg::PgConnection;
-use queries::task as task_queries;
-use std::cell::RefCell;
+use components::{
+  result::TaskResult,
+  scorer::{Scorer, ScoringStrategy},
+};
+use config::ScoringConfig;
+use error::Result;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
+use storage::Storage;
 
 type ResultsVec = Rc<Vec<TaskResult>>;
 type Callback = dyn Fn(&ResultsVec) -> ();
 
 pub struct DataSource {
   results: RefCell<Option<ResultsVec>>,
+  scoring_strategy: Cell<ScoringStrategy>,
+  scoring_config: ScoringConfig,
   callbacks: Vec<Box<Callback>>,
 }
 
 impl DataSource {
-  pub fn new() -> DataSource {
+  pub fn new(scoring_config: ScoringConfig) -> DataSource {
     DataSource {
       results: RefCell::new(None),
+      scoring_strategy: Cell::new(ScoringStrategy::default()),
+      scoring_config,
       callbacks: vec![],
     }
   }
@@ -24,23 +32,49 @@ impl DataSource {
     self.callbacks.push(callback);
   }
 
-  pub fn pull(&self, connection: &PgConnection) {
+  pub fn scoring_strategy(&self) -> ScoringStrategy {
+    self.scoring_strategy.get()
+  }
+
+  // Takes effect on the next pull.
+  pub fn set_scoring_strategy(&self, scoring_strategy: ScoringStrategy) {
+    self.scoring_strategy.set(scoring_strategy);
+  }
+
+  // The scorer for the current strategy.
+  pub fn scorer(&self) -> Box<dyn Scorer> {
+    self.scoring_strategy.get().scorer(self.scoring_config)
+  }
+
+  // Leaves the previous results in place if storage fails.
+  pub fn pull(&self, storage: &dyn Storage) -> Result<()> {
     let current_time = ::chrono::Utc::now();
+    let scorer = self.scorer();
+
+    // Two queries in all, rather than one per task.
+    let tasks = storage.all_available_to_perform()?;
+    let task_events = storage.task_events_by_task(&tasks)?;
 
-    let mut results: Vec<_> =
-      task_queries::all_available_to_perform(connection)
-        .into_iter()
-        .map(|task| {
-          TaskResult::from_task(task, current_time, connection)
-        }).collect();
+    let mut results: Vec<_> = tasks
+      .into_iter()
+      .zip(task_events)
+      .map(|(task, task_events)| {
+        TaskResult::from_task(
+          task,
+          task_events,
+          current_time,
+          scorer.as_ref(),
+        )
+      }).collect();
 
-    results.sort_by_key(|result| result.score);
+    results.sort_by_key(|result| result.score.total);
     results.reverse();
 
     {
       *self.results.borrow_mut() = Some(Rc::new(results));
     }
     self.push();
+    Ok(())
   }
 
   pub fn push(&self) {
@@ -55,9 +89,3 @@ impl DataSource {
     }
   }
 }
-
-impl Default for DataSource {
-  fn default() -> DataSource {
-    DataSource::new()
-  }
-}
@@ -0,0 +1,276 @@
+use components::result::TaskResult;
+use models::{TaskDuration, TaskEventType, TaskPriority};
+use std::fmt;
+
+type DateTime = ::chrono::DateTime<::chrono::Utc>;
+type Duration = ::chrono::Duration;
+
+// One test a listed task has to pass, or several combined. Displays as
+// a filter query that parses back to the same thing, e.g.
+// `prio>=med -net age>3d "taxes"`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FilterPredicate {
+  RequiresInternet(bool),
+  PriorityAtLeast(TaskPriority),
+  PriorityAtMost(TaskPriority),
+  DurationIn(Vec<TaskDuration>),
+  // By effort age, the age column.
+  OlderThan(Duration),
+  YoungerThan(Duration),
+  // Ignoring case.
+  TitleContains(String),
+  // Effort was recorded within the duration.
+  HasRecentEffort(Duration),
+  DelayedAtLeast(usize),
+  And(Box<FilterPredicate>, Box<FilterPredicate>),
+  Or(Box<FilterPredicate>, Box<FilterPredicate>),
+  Not(Box<FilterPredicate>),
+}
+
+fn priority_rank(priority: TaskPriority) -> u8 {
+  match priority {
+    TaskPriority::Low => 0,
+    TaskPriority::Medium => 1,
+    TaskPriority::High => 2,
+  }
+}
+
+impl FilterPredicate {
+  pub fn matches(&self, result: &TaskResult, current_time: DateTime) -> bool {
+    use self::FilterPredicate::*;
+
+    let task = &result.task;
+    match self {
+      RequiresInternet(value) => task.requires_internet == *value,
+      PriorityAtLeast(priority) => {
+        priority_rank(task.priority) >= priority_rank(*priority)
+      }
+      PriorityAtMost(priority) => {
+        priority_rank(task.priority) <= priority_rank(*priority)
+      }
+      DurationIn(durations) => durations.contains(&task.duration),
+      OlderThan(age) => result.task_effort_age > *age,
+      YoungerThan(age) => result.task_effort_age < *age,
+      TitleContains(text) => {
+        task.title.to_lowercase().contains(&text.to_lowercase())
+      }
+      HasRecentEffort(within) => result.task_events.iter().any(|event| {
+        event.event_type == TaskEventType::TaskEffortRecorded
+          && current_time - event.created_at < *within
+      }),
+      DelayedAtLeast(num_delays) => {
+        result
+          .task_events
+          .iter()
+          .filter(|event| event.event_type == TaskEventType::DelayRequested)
+          .count()
+          >= *num_delays
+      }
+      And(left, right) => {
+        left.matches(result, current_time)
+          && right.matches(result, current_time)
+      }
+      Or(left, right) => {
+        left.matches(result, current_time)
+          || right.matches(result, current_time)
+      }
+      Not(predicate) => !predicate.matches(result, current_time),
+    }
+  }
+
+  pub fn and(self, other: FilterPredicate) -> FilterPredicate {
+    FilterPredicate::And(Box::new(self), Box::new(other))
+  }
+
+  // Removes a `Not` rather than stacking another one.
+  pub fn negate(self) -> FilterPredicate {
+    use self::FilterPredicate::*;
+
+    match self {
+      Not(predicate) => *predicate,
+      RequiresInternet(value) => RequiresInternet(!value),
+      predicate => Not(Box::new(predicate)),
+    }
+  }
+
+  // Negates the last predicate added, i.e. the right hand side of the
+  // outermost `and` or `or`.
+  pub fn negate_last(self) -> FilterPredicate {
+    use self::FilterPredicate::*;
+
+    match self {
+      And(left, right) => And(left, Box::new(right.negate())),
+      Or(left, right) => Or(left, Box::new(right.negate())),
+      predicate => predicate.negate(),
+    }
+  }
+
+  // Turns the outermost `and` into an `or`, so that the last predicate
+  // added is an alternative to everything before it. `None` if there
+  // is no `and` to turn.
+  pub fn or_last(self) -> Option<FilterPredicate> {
+    match self {
+      FilterPredicate::And(left, right) => {
+        Some(FilterPredicate::Or(left, right))
+      }
+      _ => None,
+    }
+  }
+
+  // Drops the `net` tests that every task has to pass, so that a new
+  // one can take their place. `None` if nothing else is left.
+  pub fn without_requires_internet(self) -> Option<FilterPredicate> {
+    use self::FilterPredicate::*;
+
+    match self {
+      RequiresInternet(_) => None,
+      And(left, right) => match (
+        left.without_requires_internet(),
+        right.without_requires_internet(),
+      ) {
+        (Some(left), Some(right)) => Some(left.and(right)),
+        (predicate, None) | (None, predicate) => predicate,
+      },
+      predicate => Some(predicate),
+    }
+  }
+
+  // Higher binds tighter: `or`, then `and`, then `-`.
+  fn precedence(&self) -> u8 {
+    use self::FilterPredicate::*;
+
+    match self {
+      Or(..) => 0,
+      And(..) => 1,
+      Not(..) => 2,
+      _ => 3,
+    }
+  }
+
+  // Parenthesized if it binds looser than `precedence`.
+  fn fmt_operand(&self, f: &mut fmt::Formatter, precedence: u8) -> fmt::Result {
+    if self.precedence() < precedence {
+      write!(f, "({})", self)
+    } else {
+      write!(f, "{}", self)
+    }
+  }
+}
+
+impl fmt::Display for FilterPredicate {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    use self::FilterPredicate::*;
+
+    match self {
+      RequiresInternet(true) => write!(f, "net"),
+      RequiresInternet(false) => write!(f, "-net"),
+      PriorityAtLeast(priority) => {
+        write!(f, "prio>={}", priority_name(*priority))
+      }
+      PriorityAtMost(priority) => {
+        write!(f, "prio<={}", priority_name(*priority))
+      }
+      DurationIn(durations) => {
+        let names: Vec<_> =
+          durations.iter().map(|duration| duration_name(*duration)).collect();
+        write!(f, "dur:{}", names.join(","))
+      }
+      OlderThan(age) => write!(f, "age>{}", format_span(*age)),
+      YoungerThan(age) => write!(f, "age<{}", format_span(*age)),
+      TitleContains(text) => write!(
+        f,
+        "\"{}\"",
+        text.replace('\\', "\\\\").replace('"', "\\\"")
+      ),
+      HasRecentEffort(within) => write!(f, "effort<{}", format_span(*within)),
+      DelayedAtLeast(num_delays) => write!(f, "delays>={}", num_delays),
+      And(left, right) => {
+        left.fmt_operand(f, 1)?;
+        write!(f, " ")?;
+        right.fmt_operand(f, 2)
+      }
+      Or(left, right) => {
+        left.fmt_operand(f, 0)?;
+        write!(f, " or ")?;
+        right.fmt_operand(f, 1)
+      }
+      Not(predicate) => {
+        write!(f, "-")?;
+        predicate.fmt_operand(f, 2)
+      }
+    }
+  }
+}
+
+// The names below are what the user types as well as what is shown.
+// "medium" is also understood.
+
+const PRIORITIES: [TaskPriority; 3] =
+  [TaskPriority::Low, TaskPriority::Medium, TaskPriority::High];
+const DURATIONS: [TaskDuration; 3] =
+  [TaskDuration::Short, TaskDuration::Medium, TaskDuration::Long];
+
+pub fn priority_name(priority: TaskPriority) -> &'static str {
+  match priority {
+    TaskPriority::Low => "low",
+    TaskPriority::Medium => "med",
+    TaskPriority::High => "high",
+  }
+}
+
+pub fn duration_name(duration: TaskDuration) -> &'static str {
+  match duration {
+    TaskDuration::Short => "short",
+    TaskDuration::Medium => "med",
+    TaskDuration::Long => "long",
+  }
+}
+
+pub fn parse_priority(text: &str) -> Option<TaskPriority> {
+  let text = match text.trim() {
+    "medium" => "med",
+    text => text,
+  };
+  PRIORITIES
+    .iter()
+    .cloned()
+    .find(|&priority| priority_name(priority) == text)
+}
+
+// E.g. "short,med".
+pub fn parse_durations(text: &str) -> Option<Vec<TaskDuration>> {
+  text
+    .split(',')
+    .map(|name| {
+      let name = match name.trim() {
+        "medium" => "med",
+        name => name,
+      };
+      DURATIONS
+        .iter()
+        .cloned()
+        .find(|&duration| duration_name(duration) == name)
+    }).collect()
+}
+
+const SPAN_UNITS: [(char, i64); 4] =
+  [('w', 7 * 24 * 60), ('d', 24 * 60), ('h', 60), ('m', 1)];
+
+// A whole number of minutes, hours, days or weeks, e.g. "3d".
+pub fn parse_span(text: &str) -> Option<Duration> {
+  let text = text.trim();
+  let unit = text.chars().last()?;
+  let &(_, minutes) = SPAN_UNITS.iter().find(|(name, _)| *name == unit)?;
+  let num: u32 = text[..text.len() - unit.len_utf8()].parse().ok()?;
+  Some(Duration::minutes(i64::from(num) * minutes))
+}
+
+// In the largest unit that divides it evenly.
+pub fn format_span(span: Duration) -> String {
+  let total = span.num_minutes();
+  let &(unit, minutes) = SPAN_UNITS
+    .iter()
+    .find(|(_, minutes)| total % minutes == 0)
+    .expect("Everything is a whole number of minutes");
+  format!("{}{}", total / minutes, unit)
+}
@@ -0,0 +1,221 @@
+use components::filter_predicate::{
+  parse_durations, parse_priority, parse_span,
+};
+use components::FilterPredicate;
+use std::fmt;
+use std::iter::Peekable;
+use std::vec::IntoIter;
+
+// Parses what is typed after `:filter`, e.g.
+// `prio>=med dur:short -net age>3d "taxes"`. Terms next to each other
+// must all hold, `or` separates alternatives, `-` negates and brackets
+// group. `None` for an empty query, which lets everything through.
+pub fn parse_filter_query(
+  text: &str,
+) -> Result<Option<FilterPredicate>, String> {
+  let mut parser = Parser {
+    tokens: tokenize(text)?.into_iter().peekable(),
+  };
+  if parser.tokens.peek().is_none() {
+    return Ok(None);
+  }
+
+  let predicate = parser.parse_or()?;
+  match parser.tokens.next() {
+    None => Ok(Some(predicate)),
+    Some(token) => Err(format!("Unexpected {}", token)),
+  }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+  Open,
+  Close,
+  Minus,
+  Quoted(String),
+  Word(String),
+}
+
+impl fmt::Display for Token {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Token::Open => write!(f, "("),
+      Token::Close => write!(f, ")"),
+      Token::Minus => write!(f, "-"),
+      Token::Quoted(text) => write!(f, "{:?}", text),
+      Token::Word(word) => write!(f, "{}", word),
+    }
+  }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+  let mut tokens = vec![];
+  let mut chars = text.chars().peekable();
+  while let Some(&ch) = chars.peek() {
+    match ch {
+      ch if ch.is_whitespace() => {
+        chars.next();
+      }
+      '(' | ')' | '-' => {
+        chars.next();
+        tokens.push(match ch {
+          '(' => Token::Open,
+          ')' => Token::Close,
+          _ => Token::Minus,
+        });
+      }
+      // Backslash escapes the next character.
+      '"' => {
+        chars.next();
+        let mut quoted = String::new();
+        loop {
+          match chars.next() {
+            None => return Err(String::from("Unclosed quote")),
+            Some('"') => break,
+            Some('\\') => match chars.next() {
+              None => return Err(String::from("Unclosed quote")),
+              Some(ch) => quoted.push(ch),
+            },
+            Some(ch) => quoted.push(ch),
+          }
+        }
+        tokens.push(Token::Quoted(quoted));
+      }
+      _ => {
+        let mut word = String::new();
+        while let Some(&ch) = chars.peek() {
+          if ch.is_whitespace() || ch == '(' || ch == ')' || ch == '"' {
+            break;
+          }
+          word.push(ch);
+          chars.next();
+        }
+        tokens.push(Token::Word(word));
+      }
+    }
+  }
+  Ok(tokens)
+}
+
+struct Parser {
+  tokens: Peekable<IntoIter<Token>>,
+}
+
+impl Parser {
+  fn next_is_word(&mut self, word: &str) -> bool {
+    match self.tokens.peek() {
+      Some(Token::Word(next)) => next == word,
+      _ => false,
+    }
+  }
+
+  fn parse_or(&mut self) -> Result<FilterPredicate, String> {
+    let mut predicate = self.parse_and()?;
+    while self.next_is_word("or") {
+      self.tokens.next();
+      let right = self.parse_and()?;
+      predicate = FilterPredicate::Or(Box::new(predicate), Box::new(right));
+    }
+    Ok(predicate)
+  }
+
+  // `and` may be written out, but needn't be.
+  fn parse_and(&mut self) -> Result<FilterPredicate, String> {
+    let mut predicate = self.parse_not()?;
+    loop {
+      let at_end = match self.tokens.peek() {
+        None | Some(Token::Close) => true,
+        Some(Token::Word(word)) => word == "or",
+        _ => false,
+      };
+      if at_end {
+        break;
+      }
+
+      if self.next_is_word("and") {
+        self.tokens.next();
+      }
+      predicate = predicate.and(self.parse_not()?);
+    }
+    Ok(predicate)
+  }
+
+  fn parse_not(&mut self) -> Result<FilterPredicate, String> {
+    if self.tokens.peek() == Some(&Token::Minus) || self.next_is_word("not")
+    {
+      self.tokens.next();
+      return Ok(self.parse_not()?.negate());
+    }
+    self.parse_term()
+  }
+
+  fn parse_term(&mut self) -> Result<FilterPredicate, String> {
+    match self.tokens.next() {
+      None => Err(String::from("Filter ends too soon")),
+      Some(Token::Open) => {
+        let predicate = self.parse_or()?;
+        match self.tokens.next() {
+          Some(Token::Close) => Ok(predicate),
+          _ => Err(String::from("Missing )")),
+        }
+      }
+      Some(Token::Quoted(text)) => Ok(FilterPredicate::TitleContains(text)),
+      Some(Token::Word(word)) => parse_word(&word),
+      Some(token) => Err(format!("Unexpected {}", token)),
+    }
+  }
+}
+
+// Longer operators first, so `>=` isn't taken for `>`.
+const OPERATORS: [&str; 5] = [">=", "<=", ">", "<", ":"];
+
+// A single test, e.g. `prio>=med` or `net`.
+fn parse_word(word: &str) -> Result<FilterPredicate, String> {
+  use self::FilterPredicate::*;
+
+  if word == "net" {
+    return Ok(RequiresInternet(true));
+  }
+
+  let unknown = || format!("Unknown filter: {}", word);
+  let idx = word.find(&['>', '<', ':'][..]).ok_or_else(unknown)?;
+  let (field, rest) = word.split_at(idx);
+  let operator = OPERATORS
+    .iter()
+    .find(|operator| rest.starts_with(*operator))
+    .expect("Every operator starts with one of its characters");
+  let value = &rest[operator.len()..];
+
+  let bad_value = |what: &str| format!("Not {}: {}", what, value);
+  match (field, *operator) {
+    ("prio", ">=") => parse_priority(value)
+      .map(PriorityAtLeast)
+      .ok_or_else(|| bad_value("a priority")),
+    ("prio", "<=") => parse_priority(value)
+      .map(PriorityAtMost)
+      .ok_or_else(|| bad_value("a priority")),
+    ("prio", ":") => match parse_priority(value) {
+      None => Err(bad_value("a priority")),
+      Some(priority) => {
+        Ok(PriorityAtLeast(priority).and(PriorityAtMost(priority)))
+      }
+    },
+    ("dur", ":") => parse_durations(value)
+      .map(DurationIn)
+      .ok_or_else(|| bad_value("a list of durations")),
+    ("age", ">") => parse_span(value)
+      .map(OlderThan)
+      .ok_or_else(|| bad_value("a span of time")),
+    ("age", "<") => parse_span(value)
+      .map(YoungerThan)
+      .ok_or_else(|| bad_value("a span of time")),
+    ("effort", "<") => parse_span(value)
+      .map(HasRecentEffort)
+      .ok_or_else(|| bad_value("a span of time")),
+    ("delays", ">=") => value
+      .parse()
+      .map(DelayedAtLeast)
+      .map_err(|_| bad_value("a number")),
+    _ => Err(unknown()),
+  }
+}
@@ -1,4 +1,4 @@
-use components::result::TaskResult;
+use components::{result::TaskResult, FilterPredicate};
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -11,22 +11,12 @@ pub enum FiltererEvent {
   FiltererGotUpdatedResults,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub enum FiltererRequiresInternetValue {
-  Any,
-  No,
-  Yes,
-}
-
-impl Default for FiltererRequiresInternetValue {
-  fn default() -> FiltererRequiresInternetValue {
-    FiltererRequiresInternetValue::Any
-  }
-}
-
 #[derive(Clone, Debug, Default)]
 pub struct FiltererState {
-  requires_internet_value: FiltererRequiresInternetValue,
+  // `None` lets everything through.
+  criteria: Option<FilterPredicate>,
+  // The saved preset the criteria came from, until they are changed.
+  preset: Option<String>,
   results: ResultsVec,
 }
 
@@ -39,7 +29,8 @@ pub struct Filterer {
 impl Filterer {
   pub fn new() -> Filterer {
     let state = FiltererState {
-      requires_internet_value: FiltererRequiresInternetValue::Any,
+      criteria: None,
+      preset: None,
       results: Rc::new(vec![]),
     };
 
@@ -53,16 +44,6 @@ impl Filterer {
     self.callbacks.push(callback);
   }
 
-  fn _filter_result(&self, result: &TaskResult) -> bool {
-    use self::FiltererRequiresInternetValue::*;
-
-    match self.requires_internet_value() {
-      Any => true,
-      No => !result.task.requires_internet,
-      Yes => result.task.requires_internet,
-    }
-  }
-
   fn _push(&self, event: FiltererEvent) {
     let state = self.state.borrow();
     for callback in &self.callbacks {
@@ -71,10 +52,14 @@ impl Filterer {
   }
 
   pub fn refresh(&self, results: &ResultsVec) {
+    let current_time = ::chrono::Utc::now();
+    let criteria = self.criteria();
     let filtered_results: Vec<TaskResult> = results
       .iter()
-      .filter(|result| self._filter_result(result))
-      .cloned()
+      .filter(|result| match &criteria {
+        None => true,
+        Some(criteria) => criteria.matches(result, current_time),
+      }).cloned()
       .collect();
 
     {
@@ -84,19 +69,35 @@ impl Filterer {
     self._push(FiltererEvent::FiltererGotUpdatedResults);
   }
 
-  pub fn requires_internet_value(
-    &self,
-  ) -> FiltererRequiresInternetValue {
-    self.state.borrow().requires_internet_value
+  pub fn criteria(&self) -> Option<FilterPredicate> {
+    self.state.borrow().criteria.clone()
+  }
+
+  pub fn preset(&self) -> Option<String> {
+    self.state.borrow().preset.clone()
+  }
+
+  // A short summary of the criteria for the status line, e.g.
+  // "[desk] net prio>=med".
+  pub fn description(&self) -> String {
+    let state = self.state.borrow();
+    match (&state.preset, &state.criteria) {
+      (_, None) => String::from("no filter"),
+      (None, Some(criteria)) => criteria.to_string(),
+      (Some(preset), Some(criteria)) => format!("[{}] {}", preset, criteria),
+    }
   }
 
-  pub fn set_requires_internet_value(
+  // Both at once, so that switching presets is a single update.
+  pub fn set_criteria(
     &self,
-    new_value: FiltererRequiresInternetValue,
+    new_value: Option<FilterPredicate>,
+    preset: Option<String>,
   ) {
     {
       let mut state = self.state.borrow_mut();
-      state.requires_internet_value = new_value;
+      state.criteria = new_value;
+      state.preset = preset;
     }
 
     self._push(FiltererEvent::FiltererCriteriaUpdated);
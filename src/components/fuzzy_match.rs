@@ -0,0 +1,9 @@
+// Whether the characters of `query` appear in `text` in the same
+// order, ignoring case, e.g. "rvbdg" in "Review budget".
+pub fn fuzzy_matches(query: &str, text: &str) -> bool {
+  let mut text = text.chars().flat_map(char::to_lowercase);
+  query
+    .chars()
+    .flat_map(char::to_lowercase)
+    .all(|ch| text.any(|text_ch| text_ch == ch))
+}
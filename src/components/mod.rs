@@ -1,14 +1,27 @@
 mod data_source;
+pub mod filter_predicate;
+mod filter_query;
 pub mod filterer;
+mod fuzzy_match;
 mod result;
-mod scorer;
+pub mod scorer;
 mod scrollers;
+mod task_detail_window;
+mod task_event_results_window;
 mod task_results_window;
 mod undo_buffer;
+mod undo_history_window;
 
 pub use self::data_source::DataSource;
+pub use self::filter_predicate::FilterPredicate;
+pub use self::filter_query::parse_filter_query;
 pub use self::filterer::Filterer;
-pub use self::scorer::Scorer;
+pub use self::fuzzy_match::fuzzy_matches;
+pub use self::result::{TaskEventResult, TaskResult, UndoHistoryResult};
+pub use self::scorer::{Scorer, ScoringStrategy};
 pub use self::scrollers::{BaseScroller, Scroller, TasksScroller};
+pub use self::task_detail_window::TaskDetailWindow;
+pub use self::task_event_results_window::TaskEventResultsWindow;
 pub use self::task_results_window::TaskResultsWindow;
-pub use self::undo_buffer::UndoBuffer;
+pub use self::undo_buffer::{UndoBuffer, UndoTreeNode};
+pub use self::undo_history_window::UndoHistoryWindow;
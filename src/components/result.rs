@@ -1,7 +1,5 @@
-use components::Scorer;
-use diesel::pg::PgConnection;
+use components::scorer::{self, ScoreBreakdown, Scorer};
 use models::{Task, TaskEvent};
-use queries::task_event as te_queries;
 
 type DateTime = ::chrono::DateTime<::chrono::Utc>;
 
@@ -10,18 +8,20 @@ pub struct TaskResult {
   pub task: Task,
   pub task_events: Vec<TaskEvent>,
   pub task_effort_age: ::chrono::Duration,
-  pub score: i64,
+  pub score: ScoreBreakdown,
 }
 
 impl TaskResult {
+  // `task_events` must be newest first, as the queries return them.
   pub fn from_task(
     task: Task,
+    task_events: Vec<TaskEvent>,
     current_time: DateTime,
-    connection: &PgConnection,
+    scorer: &dyn Scorer,
   ) -> TaskResult {
-    let task_events = te_queries::task_events(&task, connection);
-    let task_effort_age = Scorer::task_effort_age(&task, &task_events, current_time);
-    let score = Scorer::score_task(
+    let task_effort_age =
+      scorer::task_effort_age(&task, &task_events, current_time);
+    let score = scorer.score_task(
       &task,
       &task_events,
       task_effort_age,
@@ -41,3 +41,18 @@ pub struct TaskEventResult {
   pub task: Task,
   pub task_event: TaskEvent,
 }
+
+// One point in the `UndoBuffer` tree that the undo history can go to.
+#[derive(Clone, Debug)]
+pub struct UndoHistoryResult {
+  // The action's number, 0 for the point before the first action.
+  pub num: usize,
+  // `None` for the point before the first action.
+  pub description: Option<String>,
+  // How many branches deep the action is, for indenting it.
+  pub branch_depth: usize,
+  // Whether the action is the first of its branch.
+  pub starts_branch: bool,
+  pub is_applied: bool,
+  pub is_current: bool,
+}
@@ -0,0 +1,44 @@
+use super::{
+  delay_amount, duration_multiplier, priority_multiplier, ScoreBreakdown,
+  Scorer,
+};
+use chrono::Duration;
+use config::ScoringConfig;
+use models::{Task, TaskEvent};
+
+const MS_PER_WEEK: f64 = 7.0 * 24.0 * 60.0 * 60.0 * 1000.0;
+// Keeps some precision when converting the score back to an integer.
+const SCALE: f64 = 1_000_000.0;
+
+// Urgency doubles every week a task goes untouched, so neglected tasks
+// eventually beat anything.
+pub struct ExponentialScorer(pub ScoringConfig);
+
+impl Scorer for ExponentialScorer {
+  fn score_task(
+    &self,
+    task: &Task,
+    task_events: &[TaskEvent],
+    task_effort_age: Duration,
+  ) -> ScoreBreakdown {
+    let effort_age_ms = task_effort_age.num_milliseconds();
+    let delay_deduction = delay_amount(task_events, &self.0);
+    let priority_multiplier = priority_multiplier(task, &self.0);
+    let duration_multiplier = duration_multiplier(task, &self.0);
+
+    let age_weeks = (effort_age_ms - delay_deduction) as f64 / MS_PER_WEEK;
+    let total = age_weeks.exp2()
+      * priority_multiplier
+      * duration_multiplier
+      * SCALE;
+
+    ScoreBreakdown {
+      effort_age_ms,
+      delay_deduction,
+      priority_multiplier,
+      duration_multiplier,
+      // Casting saturates, so ancient tasks simply tie at the top.
+      total: total as i64,
+    }
+  }
+}
@@ -0,0 +1,35 @@
+use super::{
+  delay_amount, duration_multiplier, priority_multiplier, ScoreBreakdown,
+  Scorer,
+};
+use chrono::Duration;
+use config::ScoringConfig;
+use models::{Task, TaskEvent};
+
+// The original formula: score grows linearly with effort age.
+pub struct LinearScorer(pub ScoringConfig);
+
+impl Scorer for LinearScorer {
+  fn score_task(
+    &self,
+    task: &Task,
+    task_events: &[TaskEvent],
+    task_effort_age: Duration,
+  ) -> ScoreBreakdown {
+    let effort_age_ms = task_effort_age.num_milliseconds();
+    let delay_deduction = delay_amount(task_events, &self.0);
+    let priority_multiplier = priority_multiplier(task, &self.0);
+    let duration_multiplier = duration_multiplier(task, &self.0);
+
+    let multiplier =
+      (priority_multiplier * duration_multiplier * 100.0) as i64;
+
+    ScoreBreakdown {
+      effort_age_ms,
+      delay_deduction,
+      priority_multiplier,
+      duration_multiplier,
+      total: (effort_age_ms - delay_deduction) * multiplier,
+    }
+  }
+}
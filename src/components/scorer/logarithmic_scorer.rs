@@ -0,0 +1,44 @@
+use super::{
+  delay_amount, duration_multiplier, priority_multiplier, ScoreBreakdown,
+  Scorer,
+};
+use chrono::Duration;
+use config::ScoringConfig;
+use models::{Task, TaskEvent};
+
+const MS_PER_HOUR: f64 = 60.0 * 60.0 * 1000.0;
+// Keeps some precision when converting the score back to an integer.
+const SCALE: f64 = 1_000_000.0;
+
+// Age matters less and less the older a task gets, so priority and
+// duration dominate among old tasks.
+pub struct LogarithmicScorer(pub ScoringConfig);
+
+impl Scorer for LogarithmicScorer {
+  fn score_task(
+    &self,
+    task: &Task,
+    task_events: &[TaskEvent],
+    task_effort_age: Duration,
+  ) -> ScoreBreakdown {
+    let effort_age_ms = task_effort_age.num_milliseconds();
+    let delay_deduction = delay_amount(task_events, &self.0);
+    let priority_multiplier = priority_multiplier(task, &self.0);
+    let duration_multiplier = duration_multiplier(task, &self.0);
+
+    let age_hours =
+      ((effort_age_ms - delay_deduction) as f64 / MS_PER_HOUR).max(0.0);
+    let total = age_hours.ln_1p()
+      * priority_multiplier
+      * duration_multiplier
+      * SCALE;
+
+    ScoreBreakdown {
+      effort_age_ms,
+      delay_deduction,
+      priority_multiplier,
+      duration_multiplier,
+      total: total as i64,
+    }
+  }
+}
@@ -0,0 +1,121 @@
+use chrono::{DateTime, Duration, Utc};
+use config::ScoringConfig;
+use models::{
+  Task, TaskDuration, TaskEvent, TaskEventType, TaskPriority,
+};
+
+mod exponential_scorer;
+mod linear_scorer;
+mod logarithmic_scorer;
+mod priority_first_scorer;
+mod scoring_strategy;
+
+pub use self::exponential_scorer::ExponentialScorer;
+pub use self::linear_scorer::LinearScorer;
+pub use self::logarithmic_scorer::LogarithmicScorer;
+pub use self::priority_first_scorer::PriorityFirstScorer;
+pub use self::scoring_strategy::ScoringStrategy;
+
+// Every factor that went into a task's score, so we can explain why a
+// task sits where it does.
+#[derive(Clone, Copy, Debug)]
+pub struct ScoreBreakdown {
+  pub effort_age_ms: i64,
+  pub delay_deduction: i64,
+  pub priority_multiplier: f64,
+  pub duration_multiplier: f64,
+  pub total: i64,
+}
+
+// Tasks are displayed by descending score. Implementations only decide
+// how age, delays, priority and duration are combined.
+pub trait Scorer {
+  fn score_task(
+    &self,
+    task: &Task,
+    task_events: &[TaskEvent],
+    task_effort_age: Duration,
+  ) -> ScoreBreakdown;
+}
+
+fn assert_is_sorted_backward(task_events: &[TaskEvent]) {
+  for idx in 1..task_events.len() {
+    if task_events[idx - 1].created_at < task_events[idx].created_at {
+      panic!("Expected tasks to be sorted.")
+    }
+  }
+}
+
+fn task_event_is_age_basis_event(task_event: &TaskEvent) -> bool {
+  match task_event.event_type {
+    TaskEventType::AgeResetRequested => true,
+    TaskEventType::DelayRequested => false,
+    TaskEventType::TaskAbandoned => false,
+    TaskEventType::TaskCompleted => false,
+    TaskEventType::TaskEffortRecorded => true,
+    // A reopened task starts aging afresh.
+    TaskEventType::TaskReopened => true,
+  }
+}
+
+pub fn delay_amount(
+  task_events: &[TaskEvent],
+  scoring_config: &ScoringConfig,
+) -> i64 {
+  assert_is_sorted_backward(task_events);
+
+  // Find and count all delay events since the age basis was set.
+  let num_delay_events = task_events
+    .iter()
+    .take_while(|te| !task_event_is_age_basis_event(te))
+    .filter(|te| te.event_type == TaskEventType::DelayRequested)
+    .count();
+
+  // In milliseconds, like the effort age it is taken off.
+  Duration::days(num_delay_events as i64 * scoring_config.delay_days)
+    .num_milliseconds()
+}
+
+fn last_effort_age_basis(
+  task: &Task,
+  task_events: &[TaskEvent],
+) -> DateTime<Utc> {
+  assert_is_sorted_backward(task_events);
+
+  // Find most recent event that "reset" the task age basis.
+  let latest_task_effort = task_events
+    .iter()
+    .find(|te| task_event_is_age_basis_event(te));
+
+  match latest_task_effort {
+    None => task.created_at,
+    Some(te) => te.created_at,
+  }
+}
+
+pub fn task_effort_age(
+  task: &Task,
+  task_events: &[TaskEvent],
+  current_time: DateTime<Utc>,
+) -> Duration {
+  current_time
+    .signed_duration_since(last_effort_age_basis(task, task_events))
+}
+
+fn priority_multiplier(task: &Task, scoring_config: &ScoringConfig) -> f64 {
+  let factor = scoring_config.base_priority_factor;
+  match task.priority {
+    TaskPriority::Low => 1.0,
+    TaskPriority::Medium => factor,
+    TaskPriority::High => factor * factor,
+  }
+}
+
+fn duration_multiplier(task: &Task, scoring_config: &ScoringConfig) -> f64 {
+  let factor = scoring_config.base_priority_factor;
+  match task.duration {
+    TaskDuration::Short => factor * factor,
+    TaskDuration::Medium => factor,
+    TaskDuration::Long => 1.0,
+  }
+}
@@ -0,0 +1,41 @@
+use super::{delay_amount, duration_multiplier, ScoreBreakdown, Scorer};
+use chrono::Duration;
+use config::ScoringConfig;
+use models::{Task, TaskEvent, TaskPriority};
+
+// Far larger than any age based score, so priorities never mix.
+const PRIORITY_TIER_SIZE: i64 = 1_000_000_000_000_000;
+
+// Every task of a higher priority comes before any task of a lower
+// priority. Within a priority, tasks are ordered like `LinearScorer`
+// without the priority multiplier.
+pub struct PriorityFirstScorer(pub ScoringConfig);
+
+impl Scorer for PriorityFirstScorer {
+  fn score_task(
+    &self,
+    task: &Task,
+    task_events: &[TaskEvent],
+    task_effort_age: Duration,
+  ) -> ScoreBreakdown {
+    let effort_age_ms = task_effort_age.num_milliseconds();
+    let delay_deduction = delay_amount(task_events, &self.0);
+    let duration_multiplier = duration_multiplier(task, &self.0);
+
+    let priority_tier = match task.priority {
+      TaskPriority::Low => 0,
+      TaskPriority::Medium => 1,
+      TaskPriority::High => 2,
+    };
+    let multiplier = (duration_multiplier * 100.0) as i64;
+
+    ScoreBreakdown {
+      effort_age_ms,
+      delay_deduction,
+      priority_multiplier: 1.0,
+      duration_multiplier,
+      total: priority_tier * PRIORITY_TIER_SIZE
+        + (effort_age_ms - delay_deduction) * multiplier,
+    }
+  }
+}
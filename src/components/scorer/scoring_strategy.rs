@@ -0,0 +1,63 @@
+use config::ScoringConfig;
+use super::{
+  ExponentialScorer, LinearScorer, LogarithmicScorer,
+  PriorityFirstScorer, Scorer,
+};
+
+// The available `Scorer`s. This is what gets persisted between
+// sessions.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ScoringStrategy {
+  #[default]
+  Linear,
+  Logarithmic,
+  Exponential,
+  PriorityFirst,
+}
+
+const ALL_STRATEGIES: [ScoringStrategy; 4] = [
+  ScoringStrategy::Linear,
+  ScoringStrategy::Logarithmic,
+  ScoringStrategy::Exponential,
+  ScoringStrategy::PriorityFirst,
+];
+
+impl ScoringStrategy {
+  pub fn from_name(name: &str) -> Option<ScoringStrategy> {
+    ALL_STRATEGIES
+      .iter()
+      .find(|strategy| strategy.name() == name)
+      .cloned()
+  }
+
+  pub fn name(self) -> &'static str {
+    use self::ScoringStrategy::*;
+
+    match self {
+      Linear => "linear",
+      Logarithmic => "logarithmic",
+      Exponential => "exponential",
+      PriorityFirst => "priority-first",
+    }
+  }
+
+  // Cycles through the strategies.
+  pub fn next(self) -> ScoringStrategy {
+    let idx = ALL_STRATEGIES
+      .iter()
+      .position(|strategy| *strategy == self)
+      .expect("Every strategy should be listed");
+    ALL_STRATEGIES[(idx + 1) % ALL_STRATEGIES.len()]
+  }
+
+  pub fn scorer(self, scoring_config: ScoringConfig) -> Box<dyn Scorer> {
+    use self::ScoringStrategy::*;
+
+    match self {
+      Linear => Box::new(LinearScorer(scoring_config)),
+      Logarithmic => Box::new(LogarithmicScorer(scoring_config)),
+      Exponential => Box::new(ExponentialScorer(scoring_config)),
+      PriorityFirst => Box::new(PriorityFirstScorer(scoring_config)),
+    }
+  }
+}
@@ -34,6 +34,11 @@ impl<ResultType: Clone> BaseScroller<ResultType> {
   }
 
   pub fn jump(&self, end: End) {
+    // There is nowhere to go in an empty list.
+    if self.num_results() == 0 {
+      return;
+    }
+
     let old_result_idx = self.current_result_idx();
     match end {
       End::Top => self._set_current_result_idx(0),
@@ -79,13 +84,23 @@ impl<ResultType: Clone> BaseScroller<ResultType> {
   }
 
   pub fn scroll(&self, direction: Direction) {
+    self.scroll_by(direction, 1)
+  }
+
+  // Stops at either end.
+  pub fn scroll_by(&self, direction: Direction, num_rows: i32) {
+    // There is nowhere to go in an empty list.
+    if self.num_results() == 0 {
+      return;
+    }
+
     let old_result_idx = self.current_result_idx();
     match direction {
       Direction::Decrease => {
-        self._set_current_result_idx(old_result_idx - 1)
+        self._set_current_result_idx(old_result_idx - num_rows)
       }
       Direction::Increase => {
-        self._set_current_result_idx(old_result_idx + 1)
+        self._set_current_result_idx(old_result_idx + num_rows)
       }
     };
 
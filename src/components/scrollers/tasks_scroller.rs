@@ -1,5 +1,5 @@
 use super::{BaseScroller, Scroller, ScrollerEvent};
-use components::result::TaskResult;
+use components::{fuzzy_matches, result::TaskResult};
 use models::{Direction, End, Task};
 use std::rc::Rc;
 
@@ -30,6 +30,16 @@ impl TasksScroller {
       .map(|result| result.task.clone())
   }
 
+  // Every task listed, top first.
+  pub fn tasks(&self) -> Vec<Task> {
+    self
+      .base
+      .results()
+      .iter()
+      .map(|result| result.task.clone())
+      .collect()
+  }
+
   pub fn current_task_id(&self) -> Option<i32> {
     let results = self.base.results();
 
@@ -68,6 +78,52 @@ impl TasksScroller {
     }
   }
 
+  pub fn scroll_by(&self, direction: Direction, num_rows: i32) {
+    self.base.scroll_by(direction, num_rows)
+  }
+
+  // Positions of the tasks whose titles fuzzy match `query`, top first.
+  pub fn title_matches(&self, query: &str) -> Vec<i32> {
+    self
+      .base
+      .results()
+      .iter()
+      .enumerate()
+      .filter(|(_, result)| fuzzy_matches(query, &result.task.title))
+      .map(|(idx, _)| idx as i32)
+      .collect()
+  }
+
+  // Jumps to the first match at `from_idx` or beyond it in `direction`,
+  // wrapping around at the end. False if no title matches.
+  pub fn jump_to_title_match(
+    &self,
+    query: &str,
+    from_idx: i32,
+    direction: Direction,
+  ) -> bool {
+    let matches = self.title_matches(query);
+    let found = match direction {
+      Direction::Increase => matches
+        .iter()
+        .find(|&&idx| idx >= from_idx)
+        .or_else(|| matches.first()),
+      Direction::Decrease => matches
+        .iter()
+        .rev()
+        .find(|&&idx| idx <= from_idx)
+        .or_else(|| matches.last()),
+    };
+
+    match found {
+      None => false,
+      Some(&idx) => {
+        self.base.set_current_result_idx(idx);
+        true
+      }
+    }
+  }
+
   pub fn refresh(&self, results: &ResultsVec) {
     let old_task_id = self.current_task_id();
     let old_result_idx = self.base.current_result_idx();
@@ -90,9 +146,11 @@ impl TasksScroller {
     old_task_id: Option<i32>,
     old_result_idx: i32,
   ) {
-    // First try to match to prev task's id. Find that idx.
+    // First try to match to prev task's id. Find that idx. No event is
+    // pushed, since followers still have the old results; `refresh`
+    // sends the new ones right after.
     if let Some(old_task_id) = old_task_id {
-      if self.jump_to_task_id(old_task_id) {
+      if self._jump_to_task_id(old_task_id) {
         return;
       }
     }
@@ -0,0 +1,187 @@
+use chrono::{Local, Utc};
+use components::{
+  result::TaskResult,
+  scrollers::{ScrollerEvent, ScrollerState},
+};
+use models::TaskEvent;
+use error::Error;
+use std::cell::RefCell;
+use std::rc::Rc;
+use util::{
+  format_task_age,
+  line_buffer::{LineBuffer, Region, TerminalLine},
+  ui::{ColorPair, UserInterface},
+};
+
+type DateTime = ::chrono::DateTime<Utc>;
+
+fn format_time(time: DateTime) -> String {
+  time
+    .with_timezone(&Local)
+    .format("%Y-%m-%d %H:%M")
+    .to_string()
+}
+
+fn format_time_ago(time: DateTime) -> String {
+  let age = Utc::now().signed_duration_since(time);
+  format!("{} ago", format_task_age(age))
+}
+
+// Shows every field of a single task, followed by its events.
+pub struct TaskDetailWindow {
+  line_buffer: LineBuffer,
+  task_result: RefCell<Option<TaskResult>>,
+  scroller_state: RefCell<Option<ScrollerState<TaskEvent>>>,
+  // Shown on its own row below the events.
+  message: RefCell<Option<String>>,
+}
+
+impl TaskDetailWindow {
+  pub fn new(
+    ui: &Rc<UserInterface>,
+    region: Region,
+  ) -> TaskDetailWindow {
+    TaskDetailWindow {
+      line_buffer: LineBuffer::new(ui, region),
+      task_result: RefCell::new(None),
+      scroller_state: RefCell::new(None),
+      message: RefCell::new(None),
+    }
+  }
+
+  // Must be set before the scroller pushes the task's events.
+  pub fn set_task_result(&self, task_result: TaskResult) {
+    *self.task_result.borrow_mut() = Some(task_result);
+  }
+
+  // Makes the next redraw repaint every line. Needed when this
+  // window's view comes back on screen.
+  pub fn invalidate(&self) {
+    self.line_buffer.mark_all_dirty();
+  }
+
+  pub fn redraw(&self, event: ScrollerEvent<TaskEvent>) {
+    match event {
+      ScrollerEvent::ChangedScrollPosition { new_state, .. }
+      | ScrollerEvent::GotNewScrollResults { state: new_state } => {
+        *self.scroller_state.borrow_mut() = Some(new_state);
+      }
+    }
+
+    self.full_redraw();
+    self.line_buffer.redraw();
+  }
+
+  fn task_lines(&self) -> Vec<String> {
+    let task_result = self.task_result.borrow();
+    let task_result = task_result
+      .as_ref()
+      .expect("task_result should be set before trying to draw it");
+    let task = &task_result.task;
+
+    let recurrence = match task.recurrence() {
+      None => String::from("none"),
+      Some(recurrence) => recurrence.to_string(),
+    };
+
+    let score = task_result.score;
+    let fields = vec![
+      ("id", task.id.to_string()),
+      ("title", task.title.clone()),
+      ("status", format!("{:?}", task.status)),
+      ("priority", format!("{:?}", task.priority)),
+      ("duration", format!("{:?}", task.duration)),
+      ("internet", task.requires_internet.to_string()),
+      ("recurrence", recurrence),
+      (
+        "created",
+        format!(
+          "{} ({})",
+          format_time(task.created_at),
+          format_time_ago(task.created_at)
+        ),
+      ),
+      ("effort age", format_task_age(task_result.task_effort_age)),
+      ("effort age ms", score.effort_age_ms.to_string()),
+      ("delay", format!("-{}", score.delay_deduction)),
+      ("priority mult", format!("x{:.3}", score.priority_multiplier)),
+      ("duration mult", format!("x{:.3}", score.duration_multiplier)),
+      ("score", score.total.to_string()),
+    ];
+
+    fields
+      .into_iter()
+      .map(|(name, value)| format!(" {:13} | {} \n", name, value))
+      .collect()
+  }
+
+  // Stays up until `clear_message`, so it survives redraws.
+  pub fn show_error(&self, err: &Error) {
+    *self.message.borrow_mut() = Some(format!("Error: {}", err));
+    self.full_redraw();
+    self.line_buffer.redraw();
+  }
+
+  pub fn clear_message(&self) {
+    if self.message.borrow_mut().take().is_some() {
+      self.full_redraw();
+      self.line_buffer.redraw();
+    }
+  }
+
+  pub fn full_redraw(&self) {
+    let state = self
+      .scroller_state
+      .borrow()
+      .clone()
+      .expect("scroller_state should be set before trying to use it");
+
+    let mut idx = 0;
+    for text in self.task_lines() {
+      self.line_buffer.replace_line(
+        idx,
+        TerminalLine {
+          text,
+          color: ColorPair::Default,
+        },
+      );
+      idx += 1;
+    }
+
+    self.line_buffer.clear_line(idx);
+    idx += 1;
+    let text = format!(
+      " {time:16} | {ago:12} | {event:9} \n",
+      time = "time",
+      ago = "ago",
+      event = "event",
+    );
+    self
+      .line_buffer
+      .replace_line(idx, TerminalLine { text, color: ColorPair::Bold });
+    idx += 1;
+
+    for (event_idx, task_event) in state.results.iter().enumerate() {
+      let color = if event_idx as i32 == state.current_result_idx {
+        ColorPair::Highlight
+      } else {
+        ColorPair::Default
+      };
+
+      let text = format!(
+        " {time:16} | {ago:12} | {event:9}\n",
+        time = format_time(task_event.created_at),
+        ago = format_time_ago(task_event.created_at),
+        event = task_event.event_type.label(),
+      );
+      self
+        .line_buffer
+        .replace_line(idx, TerminalLine { text, color });
+      idx += 1;
+    }
+
+    self
+      .line_buffer
+      .display_message(idx, self.message.borrow().as_deref());
+  }
+}
@@ -0,0 +1,124 @@
+use chrono::Local;
+use components::{
+  scrollers::{ScrollerEvent, ScrollerState},
+  TaskEventResult,
+};
+use error::Error;
+use std::cell::RefCell;
+use std::rc::Rc;
+use util::{
+  line_buffer::{LineBuffer, Region, TerminalLine},
+  ui::{ColorPair, UserInterface},
+};
+
+// Displays one line per task event, e.g. for the "done today" view.
+pub struct TaskEventResultsWindow {
+  line_buffer: LineBuffer,
+  scroller_state: RefCell<Option<ScrollerState<TaskEventResult>>>,
+  // Shown on its own row below the results.
+  message: RefCell<Option<String>>,
+}
+
+impl TaskEventResultsWindow {
+  pub fn new(
+    ui: &Rc<UserInterface>,
+    region: Region,
+  ) -> TaskEventResultsWindow {
+    TaskEventResultsWindow {
+      line_buffer: LineBuffer::new(ui, region),
+      scroller_state: RefCell::new(None),
+      message: RefCell::new(None),
+    }
+  }
+
+  fn scroller_state(&self) -> ScrollerState<TaskEventResult> {
+    self
+      .scroller_state
+      .borrow()
+      .clone()
+      .expect("scroller_state should be set before trying to use it")
+  }
+
+  pub fn redraw(&self, event: ScrollerEvent<TaskEventResult>) {
+    match event {
+      ScrollerEvent::ChangedScrollPosition { new_state, .. }
+      | ScrollerEvent::GotNewScrollResults { state: new_state } => {
+        *self.scroller_state.borrow_mut() = Some(new_state);
+      }
+    }
+
+    self.full_redraw();
+    self.line_buffer.redraw();
+  }
+
+  // Makes the next redraw repaint every line. Needed when this
+  // window's view comes back on screen.
+  pub fn invalidate(&self) {
+    self.line_buffer.mark_all_dirty();
+  }
+
+  // Stays up until `clear_message`, so it survives redraws.
+  pub fn show_error(&self, err: &Error) {
+    *self.message.borrow_mut() = Some(format!("Error: {}", err));
+    self.full_redraw();
+    self.line_buffer.redraw();
+  }
+
+  pub fn clear_message(&self) {
+    if self.message.borrow_mut().take().is_some() {
+      self.full_redraw();
+      self.line_buffer.redraw();
+    }
+  }
+
+  pub fn full_redraw(&self) {
+    let state = self.scroller_state();
+    let title_width = state
+      .results
+      .iter()
+      .map(|r| r.task.title.len())
+      .max()
+      .unwrap_or(0);
+    let title_width = ::std::cmp::max(5, title_width + 2);
+
+    let text = format!(
+      " {time:5} | {event:9} | {title:title_width$} \n",
+      time = "time",
+      event = "event",
+      title = "title",
+      title_width = title_width,
+    );
+    self
+      .line_buffer
+      .replace_line(0, TerminalLine { text, color: ColorPair::Bold });
+
+    for (idx, result) in state.results.iter().enumerate() {
+      let color = if idx as i32 == state.current_result_idx {
+        ColorPair::Highlight
+      } else {
+        ColorPair::Default
+      };
+
+      let text = format!(
+        " {time:5} | {event:9} | {title:title_width$}\n",
+        time = result
+          .task_event
+          .created_at
+          .with_timezone(&Local)
+          .format("%H:%M")
+          .to_string(),
+        event = result.task_event.event_type.label(),
+        title = result.task.title,
+        title_width = title_width,
+      );
+      self
+        .line_buffer
+        .replace_line(idx + 1, TerminalLine { text, color });
+    }
+
+    self.line_buffer.display_message(
+      state.results.len() + 1,
+      self.message.borrow().as_deref(),
+    );
+  }
+}
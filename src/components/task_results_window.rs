@@ -1,70 +1,86 @@
-use chrono::Duration;
 use components::{
+  fuzzy_matches,
   result::TaskResult,
   scrollers::{ScrollerEvent, ScrollerState},
 };
-use std::cell::RefCell;
-use std::ops::DerefMut;
+use error::Error;
+use std::cell::{Cell, RefCell};
+use std::cmp::{max, min};
+use std::ops::{DerefMut, Range};
 use std::rc::Rc;
 use util::{
-  line_buffer::{TerminalLine, LineBuffer},
+  format_task_age,
+  line_buffer::{LineBuffer, Region, TerminalLine},
   ui::{ColorPair, UserInterface}
 };
 
 type ResultsVec = Rc<Vec<TaskResult>>;
 
-fn format_task_age(age: Duration) -> String {
-  let weeks = age.num_weeks();
-  let days = age.num_days() - 7 * age.num_weeks();
-  let hours = age.num_hours() - 24 * age.num_days();
-  let mins = age.num_minutes() - 60 * age.num_hours();
-
-  if weeks > 0 {
-    format!("{}w {}d", weeks, days)
-  } else if days > 0 {
-    format!("{}d {}h", days, hours)
-  } else if hours > 0 {
-    format!("{}h {}m", hours, mins)
-  } else if mins > 0 {
-    format!("{}m", mins)
-  } else {
-    String::from("now")
-  }
-}
-
 pub struct TaskResultsWindow {
   line_buffer: LineBuffer,
   scroller_state: RefCell<Option<ScrollerState<TaskResult>>>,
+  // The result shown on the first row. Only as many results as fit
+  // under the column names are drawn.
+  first_visible_idx: Cell<usize>,
+  show_score_breakdown: Cell<bool>,
+  // One row, shared by the latest message and the status summary.
+  status_line: LineBuffer,
+  // What just happened, e.g. "Recorded effort on #12".
+  message: RefCell<Option<String>>,
+  // Filter, result count, undo position and so on.
+  status: RefCell<String>,
+  // The filter query, shown after the column names.
+  filter: RefCell<Option<String>>,
+  // Titles matching the title search are shown in bold.
+  search: RefCell<Option<String>>,
 }
 
 // TODO: Can I clean this code up at all?
 impl TaskResultsWindow {
-  pub fn new(ui: &Rc<UserInterface>) -> TaskResultsWindow {
+  pub fn new(
+    ui: &Rc<UserInterface>,
+    region: Region,
+    status_region: Region,
+  ) -> TaskResultsWindow {
     TaskResultsWindow {
-      line_buffer: LineBuffer::new(ui),
+      line_buffer: LineBuffer::new(ui, region),
       scroller_state: RefCell::new(None),
+      first_visible_idx: Cell::new(0),
+      show_score_breakdown: Cell::new(false),
+      status_line: LineBuffer::new(ui, status_region),
+      message: RefCell::new(None),
+      status: RefCell::new(String::new()),
+      filter: RefCell::new(None),
+      search: RefCell::new(None),
     }
   }
 
+  // Empty until the first pull succeeds.
   fn results(&self) -> ResultsVec {
+    match self.scroller_state.borrow().as_ref() {
+      None => Rc::new(vec![]),
+      Some(state) => state.results.clone(),
+    }
+  }
+
+  fn current_result_idx(&self) -> usize {
     self
       .scroller_state
       .borrow()
       .as_ref()
       .expect(
-        "scroller_state should be set before trying to use results",
-      ).results
-      .clone()
+        "scroller_state should be set before trying to use result idx",
+      ).current_result_idx as usize
   }
 
-  fn current_result_idx(&self) -> usize {
+  // Like `current_result_idx`, but 0 before the first pull or for an
+  // empty list.
+  fn current_result_idx_or_top(&self) -> usize {
     self
       .scroller_state
       .borrow()
       .as_ref()
-      .expect(
-        "scroller_state should be set before trying to use result idx",
-      ).current_result_idx as usize
+      .map_or(0, |state| max(state.current_result_idx, 0) as usize)
   }
 
   fn save_scroller_state(&self, state: ScrollerState<TaskResult>) {
@@ -90,7 +106,24 @@ impl TaskResultsWindow {
           },
       } => {
         self.save_current_result_idx(current_result_idx);
-        self.incremental_redraw(old_result_idx as usize, current_result_idx as usize);
+        // A move of more than a row, like a page, keeps the current
+        // result at the same height on screen if it can.
+        let distance = current_result_idx - old_result_idx;
+        let first_visible_idx = self.first_visible_idx.get();
+        let first_visible_idx = if distance.abs() > 1 {
+          max(first_visible_idx as i32 + distance, 0) as usize
+        } else {
+          first_visible_idx
+        };
+
+        if self.scroll_into_view(first_visible_idx) {
+          self.full_redraw();
+        } else {
+          self.incremental_redraw(
+            old_result_idx as usize,
+            current_result_idx as usize,
+          );
+        }
       }
       ScrollerEvent::GotNewScrollResults { state } => {
         self.save_scroller_state(state);
@@ -101,12 +134,124 @@ impl TaskResultsWindow {
     self.line_buffer.redraw();
   }
 
+  // Makes the next redraw repaint every line. Needed when this
+  // window's view comes back on screen.
+  pub fn invalidate(&self) {
+    self.line_buffer.mark_all_dirty();
+    self.status_line.mark_all_dirty();
+  }
+
+  // Shows or hides the columns explaining each task's score.
+  pub fn toggle_score_breakdown(&self) {
+    self
+      .show_score_breakdown
+      .set(!self.show_score_breakdown.get());
+    self.full_redraw();
+    self.line_buffer.redraw();
+  }
+
+  // Stays up until `clear_message`, so it survives redraws.
+  pub fn show_message(&self, message: &str) {
+    *self.message.borrow_mut() = Some(message.to_string());
+    self.redraw_status_line();
+  }
+
+  pub fn show_error(&self, err: &Error) {
+    self.show_message(&format!("Error: {}", err));
+  }
+
+  pub fn clear_message(&self) {
+    if self.message.borrow_mut().take().is_some() {
+      self.redraw_status_line();
+    }
+  }
+
+  // Only repaints if something changed, so this is cheap to call after
+  // every key.
+  pub fn set_status(&self, status: String) {
+    *self.status.borrow_mut() = status;
+    self.redraw_status_line();
+  }
+
+  // Only repaints the header if the filter changed.
+  pub fn set_filter(&self, filter: Option<String>) {
+    if *self.filter.borrow() == filter {
+      return;
+    }
+
+    *self.filter.borrow_mut() = filter;
+    self.display_header();
+    self.line_buffer.redraw();
+  }
+
+  // Only repaints if the search changed, since every row may need to.
+  pub fn set_search(&self, search: Option<String>) {
+    if *self.search.borrow() == search {
+      return;
+    }
+
+    *self.search.borrow_mut() = search;
+    self.full_redraw();
+    self.line_buffer.redraw();
+  }
+
+  fn redraw_status_line(&self) {
+    let status = self.status.borrow();
+    let text = match self.message.borrow().as_ref() {
+      None => format!(" {} \n", status),
+      Some(message) => format!(" {} | {} \n", message, status),
+    };
+
+    self.status_line.replace_line(
+      0,
+      TerminalLine {
+        text,
+        color: ColorPair::Bold,
+      },
+    );
+    self.status_line.redraw();
+  }
+
+  // How many results fit under the column names.
+  pub fn page_height(&self) -> usize {
+    self.line_buffer.height().saturating_sub(1)
+  }
+
+  // The indexes of the results on screen.
+  pub fn visible_range(&self) -> Range<usize> {
+    let first_visible_idx = self.first_visible_idx.get();
+    first_visible_idx
+      ..min(first_visible_idx + self.page_height(), self.results().len())
+  }
+
+  // Starts the rows at `first_visible_idx`, or as near to it as keeps
+  // the current result on screen without leaving blank rows at the
+  // bottom. True if the rows moved.
+  fn scroll_into_view(&self, first_visible_idx: usize) -> bool {
+    let page_height = max(self.page_height(), 1);
+    let current_result_idx = self.current_result_idx_or_top();
+    let last_first_idx = self.results().len().saturating_sub(page_height);
+
+    let mut new_first_idx = min(first_visible_idx, last_first_idx);
+    if current_result_idx < new_first_idx {
+      new_first_idx = current_result_idx;
+    } else if current_result_idx >= new_first_idx + page_height {
+      new_first_idx = current_result_idx + 1 - page_height;
+    }
+
+    self.first_visible_idx.replace(new_first_idx) != new_first_idx
+  }
+
+  // Also catches up with the terminal having been resized.
   pub fn full_redraw(&self) {
+    self.scroll_into_view(self.first_visible_idx.get());
     self.display_header();
-    for (idx, result) in self.results().iter().enumerate() {
-      self.display_result(idx, result);
+    let results = self.results();
+    let visible_range = self.visible_range();
+    for idx in visible_range.clone() {
+      self.display_result(idx, &results[idx]);
     }
-    self.line_buffer.truncate(self.results().len() + 1);
+    self.line_buffer.truncate(visible_range.len() + 1);
   }
 
   pub fn max_title_len(&self) -> usize {
@@ -124,19 +269,17 @@ impl TaskResultsWindow {
     current_result_idx: usize,
   ) {
     let results = &self.results();
-    self.display_result(
-      old_result_idx,
-      &results[old_result_idx],
-    );
-    self.display_result(
-      current_result_idx,
-      &results[current_result_idx],
-    );
+    let visible_range = self.visible_range();
+    for &idx in &[old_result_idx, current_result_idx] {
+      if visible_range.contains(&idx) {
+        self.display_result(idx, &results[idx]);
+      }
+    }
   }
 
   fn display_header(&self) {
     let text = format!(
-      " {title:title_width$} | {priority:5} | {durration:5} | {age:8} | {status:6} | {requires_internet:6} \n",
+      " {title:title_width$} | {priority:5} | {durration:5} | {age:8} | {status:6} | {requires_internet:6} | {recurrence:14}{score_breakdown}{filter} \n",
       title = "title",
       title_width = ::std::cmp::max(5, self.max_title_len() + 2),
       priority = "prior",
@@ -144,6 +287,23 @@ impl TaskResultsWindow {
       age = "age",
       status = "stat",
       requires_internet = "net",
+      recurrence = "recur",
+      score_breakdown = if self.show_score_breakdown.get() {
+        format!(
+          " | {age_ms:>12} | {delay:>8} | {prio:>6} | {dur:>6} | {score:>16}",
+          age_ms = "age ms",
+          delay = "delay",
+          prio = "prio x",
+          dur = "dur x",
+          score = "score",
+        )
+      } else {
+        String::new()
+      },
+      filter = match self.filter.borrow().as_ref() {
+        None => String::new(),
+        Some(filter) => format!("   :filter {}", filter),
+      },
     );
 
     self.line_buffer.replace_line(0, TerminalLine { text, color: ColorPair::Bold });
@@ -151,8 +311,14 @@ impl TaskResultsWindow {
 
   fn display_result(&self, idx: usize, result: &TaskResult) {
     // Choose appropriate color.
+    let matches_search = match self.search.borrow().as_ref() {
+      None => false,
+      Some(search) => fuzzy_matches(search, &result.task.title),
+    };
     let color = if idx == self.current_result_idx() {
       ColorPair::Highlight
+    } else if matches_search {
+      ColorPair::Bold
     } else {
       ColorPair::Default
     };
@@ -190,9 +356,28 @@ impl TaskResultsWindow {
       "-net"
     };
 
+    let recurrence = match result.task.recurrence() {
+      None => String::new(),
+      Some(recurrence) => recurrence.to_string(),
+    };
+
+    let score_breakdown = if self.show_score_breakdown.get() {
+      let score = result.score;
+      format!(
+        " | {age_ms:>12} | {delay:>8} | {prio:>6.3} | {dur:>6.3} | {score:>16}",
+        age_ms = score.effort_age_ms,
+        delay = -score.delay_deduction,
+        prio = score.priority_multiplier,
+        dur = score.duration_multiplier,
+        score = score.total,
+      )
+    } else {
+      String::new()
+    };
+
     // Display the task line.
     let text = format!(
-      " {title:title_width$} | {priority:5} | {duration:5} | {age:8} | {status:6} | {requires_internet:6}\n",
+      " {title:title_width$} | {priority:5} | {duration:5} | {age:8} | {status:6} | {requires_internet:6} | {recurrence:14.14}{score_breakdown}\n",
       title = result.task.title,
       title_width = ::std::cmp::max(5, self.max_title_len() + 2),
       priority = priority,
@@ -200,9 +385,12 @@ impl TaskResultsWindow {
       age = format_task_age(result.task_effort_age),
       status = status,
       requires_internet = requires_internet,
+      recurrence = recurrence,
+      score_breakdown = score_breakdown,
     );
 
-    // Print the line!
-    self.line_buffer.replace_line(idx + 1, TerminalLine { text, color });
+    // Print the line, below the header.
+    let row = idx - self.first_visible_idx.get() + 1;
+    self.line_buffer.replace_line(row, TerminalLine { text, color });
   }
 }
@@ -1,69 +1,254 @@
 use actions::ReversableAction;
+use error::Result;
 use std::cell::{Cell, RefCell};
 
+// Actions are numbered from 1 in the order they were appended. Number 0
+// is the start, before any of them, so it has no action.
+struct UndoNode {
+  action: Option<Box<dyn ReversableAction>>,
+  parent: usize,
+  // Where `redo` goes from here: the child most recently done, redone or
+  // undone.
+  redo_child: Option<usize>,
+}
+
+impl UndoNode {
+  fn action(&self) -> &dyn ReversableAction {
+    self.action.as_ref().expect("The start has no action").as_ref()
+  }
+
+  fn action_mut(&mut self) -> &mut dyn ReversableAction {
+    self.action.as_mut().expect("The start has no action").as_mut()
+  }
+}
+
+// What the undo history shows of one action.
+#[derive(Clone, Debug)]
+pub struct UndoTreeNode {
+  pub num: usize,
+  // The number of the action this one was done after, 0 if none.
+  pub parent: usize,
+  pub description: String,
+}
+
+// Like vim's undo tree: doing something after undoing starts a new
+// branch instead of throwing away what was undone.
 pub struct UndoBuffer {
-  actions: RefCell<Vec<Box<dyn ReversableAction>>>,
-  idx: Cell<Option<usize>>,
+  nodes: RefCell<Vec<UndoNode>>,
+  // The last applied action.
+  current: Cell<usize>,
+}
+
+fn start_node() -> UndoNode {
+  UndoNode {
+    action: None,
+    parent: 0,
+    redo_child: None,
+  }
 }
 
 impl UndoBuffer {
   pub fn new() -> UndoBuffer {
     UndoBuffer {
-      actions: RefCell::new(vec![]),
-      idx: Cell::new(None),
+      nodes: RefCell::new(vec![start_node()]),
+      current: Cell::new(0),
     }
   }
 
-  pub fn redo(&self) {
-    let redo_idx = match self.idx.get() {
-      None => 0,
-      Some(idx) => idx + 1,
-    };
+  // The position only moves once the action has succeeded, so a failed
+  // undo or redo can simply be tried again.
+  pub fn redo(&self) -> Result<()> {
+    let redo_child = self.nodes.borrow()[self.current.get()].redo_child;
+    match redo_child {
+      None => Ok(()),
+      Some(child) => self.redo_to(child),
+    }
+  }
 
+  // `child` has to be a child of the current action.
+  fn redo_to(&self, child: usize) -> Result<()> {
     {
-      let mut actions = self.actions.borrow_mut();
-      if redo_idx >= actions.len() {
-        return;
-      }
-
-      let action = &mut actions[redo_idx];
-      action.redo();
+      let mut nodes = self.nodes.borrow_mut();
+      nodes[child].action_mut().redo()?;
+      nodes[self.current.get()].redo_child = Some(child);
     }
 
-    self.idx.set(Some(redo_idx));
+    self.current.set(child);
+    Ok(())
   }
 
-  pub fn undo(&self) {
-    let undo_idx = match self.idx.get() {
-      None => return,
-      Some(idx) => idx,
+  pub fn undo(&self) -> Result<()> {
+    let current = self.current.get();
+    if current == 0 {
+      return Ok(());
+    }
+
+    let parent = {
+      let mut nodes = self.nodes.borrow_mut();
+      nodes[current].action_mut().unexecute()?;
+
+      // Redo comes back down the branch just undone.
+      let parent = nodes[current].parent;
+      nodes[parent].redo_child = Some(current);
+      parent
     };
 
-    {
-      let mut actions = self.actions.borrow_mut();
-      let action = &mut actions[undo_idx];
-      action.unexecute();
+    self.current.set(parent);
+    Ok(())
+  }
+
+  // How many actions are currently applied, out of how many there are
+  // to undo or redo on the current branch.
+  pub fn position(&self) -> (usize, usize) {
+    let branch = self.branch();
+    let current = self.current.get();
+    let num_applied = branch
+      .iter()
+      .position(|&num| num == current)
+      .map_or(0, |idx| idx + 1);
+    (num_applied, branch.len())
+  }
+
+  // The actions from the start to the end of the current branch, i.e.
+  // as far as `redo` would go.
+  fn branch(&self) -> Vec<usize> {
+    let nodes = self.nodes.borrow();
+    let mut branch = vec![];
+    let mut num = 0;
+    while let Some(child) = nodes[num].redo_child {
+      branch.push(child);
+      num = child;
     }
+    branch
+  }
 
-    self.idx.set(if undo_idx > 0 {
-      Some(undo_idx - 1)
-    } else {
-      None
-    });
+  // The actions from the start up to `num`.
+  fn path_to(&self, num: usize) -> Vec<usize> {
+    let nodes = self.nodes.borrow();
+    let mut path = vec![];
+    let mut num = num;
+    while num != 0 {
+      path.push(num);
+      num = nodes[num].parent;
+    }
+    path.reverse();
+    path
   }
 
-  pub fn append_action(&self, action: Box<dyn ReversableAction>) {
-    let mut actions = self.actions.borrow_mut();
-    if let Some(idx) = self.idx.get() {
-      // Notice I want to keep the first `idx + 1` actions because I
-      // want `idx` to remain a valid index.
-      actions.truncate(idx + 1);
+  // Every action ever appended, in the order they were.
+  pub fn tree(&self) -> Vec<UndoTreeNode> {
+    let nodes = self.nodes.borrow();
+    (1..nodes.len())
+      .map(|num| UndoTreeNode {
+        num,
+        parent: nodes[num].parent,
+        description: nodes[num].action().description(),
+      }).collect()
+  }
+
+  // The number of the last applied action, 0 at the start.
+  pub fn current(&self) -> usize {
+    self.current.get()
+  }
+
+  // Undoes back to where the current branch and the one with action
+  // `num` split, then redoes down to `num`. Stops at the first failure,
+  // leaving the position wherever it got to.
+  pub fn go_to(&self, num: usize) -> Result<()> {
+    let path = self.path_to(num);
+    while self.current.get() != 0 && !path.contains(&self.current.get()) {
+      self.undo()?;
+    }
+
+    let current = self.current.get();
+    let num_done = path
+      .iter()
+      .position(|&num| num == current)
+      .map_or(0, |idx| idx + 1);
+    for &num in &path[num_done..] {
+      self.redo_to(num)?;
+    }
+    Ok(())
+  }
+
+  // Goes to the point just before the current one in time, whichever
+  // branch that is on. Vim's `g-`.
+  pub fn go_to_older(&self) -> Result<()> {
+    match self.current.get() {
+      0 => Ok(()),
+      current => self.go_to(current - 1),
+    }
+  }
+
+  // Vim's `g+`.
+  pub fn go_to_newer(&self) -> Result<()> {
+    let newer = self.current.get() + 1;
+    if newer < self.nodes.borrow().len() {
+      self.go_to(newer)
     } else {
-      actions.truncate(0);
+      Ok(())
+    }
+  }
+
+  // The action log entry of the last applied action that has one.
+  pub fn current_log_id(&self) -> Option<i32> {
+    let nodes = self.nodes.borrow();
+    let mut num = self.current.get();
+    while num != 0 {
+      if let Some(log_id) = nodes[num].action().log_id() {
+        return Some(log_id);
+      }
+      num = nodes[num].parent;
+    }
+    None
+  }
+
+  // Replaces the tree, e.g. with one saved by a previous session. Each
+  // action comes with its parent's number, which has to be lower than
+  // its own, and `current` is the last applied one.
+  pub fn restore(
+    &self,
+    actions: Vec<(Box<dyn ReversableAction>, usize)>,
+    current: usize,
+  ) {
+    assert!(current <= actions.len());
+    {
+      let mut nodes = self.nodes.borrow_mut();
+      *nodes = vec![start_node()];
+      for (action, parent) in actions {
+        let num = nodes.len();
+        assert!(parent < num);
+        nodes[parent].redo_child = Some(num);
+        nodes.push(UndoNode {
+          action: Some(action),
+          parent,
+          redo_child: None,
+        });
+      }
     }
 
-    actions.push(action);
-    self.idx.set(Some(actions.len() - 1));
+    // Redo always goes through the applied actions.
+    let path = self.path_to(current);
+    let mut nodes = self.nodes.borrow_mut();
+    for &num in &path {
+      let parent = nodes[num].parent;
+      nodes[parent].redo_child = Some(num);
+    }
+    self.current.set(current);
+  }
+
+  // Starts a new branch if anything had been undone.
+  pub fn append_action(&self, action: Box<dyn ReversableAction>) {
+    let mut nodes = self.nodes.borrow_mut();
+    let num = nodes.len();
+    let current = self.current.get();
+    nodes.push(UndoNode {
+      action: Some(action),
+      parent: current,
+      redo_child: None,
+    });
+    nodes[current].redo_child = Some(num);
+    self.current.set(num);
   }
 }
 
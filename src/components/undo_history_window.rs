@@ -0,0 +1,126 @@
+use components::{
+  scrollers::{ScrollerEvent, ScrollerState},
+  UndoHistoryResult,
+};
+use error::Error;
+use std::cell::RefCell;
+use std::rc::Rc;
+use util::{
+  line_buffer::{LineBuffer, Region, TerminalLine},
+  ui::{ColorPair, UserInterface},
+};
+
+// Displays one line per point in the `UndoBuffer`. Later branches are
+// indented under the action they split off from.
+pub struct UndoHistoryWindow {
+  line_buffer: LineBuffer,
+  scroller_state: RefCell<Option<ScrollerState<UndoHistoryResult>>>,
+  // Shown on its own row below the results.
+  message: RefCell<Option<String>>,
+}
+
+// E.g. "  \u{2514} " for the first action of a branch two deep.
+fn branch_indent(result: &UndoHistoryResult) -> String {
+  let mut indent = "  ".repeat(result.branch_depth);
+  if result.starts_branch {
+    indent.truncate(indent.len() - 2);
+    indent.push_str("\u{2514} ");
+  }
+  indent
+}
+
+impl UndoHistoryWindow {
+  pub fn new(ui: &Rc<UserInterface>, region: Region) -> UndoHistoryWindow {
+    UndoHistoryWindow {
+      line_buffer: LineBuffer::new(ui, region),
+      scroller_state: RefCell::new(None),
+      message: RefCell::new(None),
+    }
+  }
+
+  fn scroller_state(&self) -> ScrollerState<UndoHistoryResult> {
+    self
+      .scroller_state
+      .borrow()
+      .clone()
+      .expect("scroller_state should be set before trying to use it")
+  }
+
+  pub fn redraw(&self, event: ScrollerEvent<UndoHistoryResult>) {
+    match event {
+      ScrollerEvent::ChangedScrollPosition { new_state, .. }
+      | ScrollerEvent::GotNewScrollResults { state: new_state } => {
+        *self.scroller_state.borrow_mut() = Some(new_state);
+      }
+    }
+
+    self.full_redraw();
+    self.line_buffer.redraw();
+  }
+
+  // Makes the next redraw repaint every line. Needed when this
+  // window's view comes back on screen.
+  pub fn invalidate(&self) {
+    self.line_buffer.mark_all_dirty();
+  }
+
+  // Stays up until `clear_message`, so it survives redraws.
+  pub fn show_error(&self, err: &Error) {
+    *self.message.borrow_mut() = Some(format!("Error: {}", err));
+    self.full_redraw();
+    self.line_buffer.redraw();
+  }
+
+  pub fn clear_message(&self) {
+    if self.message.borrow_mut().take().is_some() {
+      self.full_redraw();
+      self.line_buffer.redraw();
+    }
+  }
+
+  pub fn full_redraw(&self) {
+    let state = self.scroller_state();
+
+    let text = format!(
+      "   {num:>3} | {state:7} | {action} \n",
+      num = "#",
+      state = "state",
+      action = "action",
+    );
+    self
+      .line_buffer
+      .replace_line(0, TerminalLine { text, color: ColorPair::Bold });
+
+    for (idx, result) in state.results.iter().enumerate() {
+      let color = if idx as i32 == state.current_result_idx {
+        ColorPair::Highlight
+      } else {
+        ColorPair::Default
+      };
+
+      let (state, action) = match &result.description {
+        None => ("", "(start)"),
+        Some(description) if result.is_applied => {
+          ("applied", description.as_str())
+        }
+        Some(description) => ("undone", description.as_str()),
+      };
+      let text = format!(
+        " {marker} {num:>3} | {state:7} | {indent}{action}\n",
+        marker = if result.is_current { ">" } else { " " },
+        num = result.num,
+        state = state,
+        indent = branch_indent(result),
+        action = action,
+      );
+      self
+        .line_buffer
+        .replace_line(idx + 1, TerminalLine { text, color });
+    }
+
+    self.line_buffer.display_message(
+      state.results.len() + 1,
+      self.message.borrow().as_deref(),
+    );
+  }
+}
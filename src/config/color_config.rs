@@ -0,0 +1,60 @@
+use pancurses;
+use serde::de::{Deserialize, Deserializer, Error};
+
+// A foreground/background pair. Colors are named as in curses, plus
+// "default" for the terminal's own color.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ColorConfig {
+  #[serde(deserialize_with = "deserialize_color")]
+  pub foreground: i16,
+  #[serde(deserialize_with = "deserialize_color")]
+  pub background: i16,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColorsConfig {
+  pub default: ColorConfig,
+  pub highlight: ColorConfig,
+}
+
+impl Default for ColorsConfig {
+  fn default() -> ColorsConfig {
+    ColorsConfig {
+      // -1 means "default color".
+      default: ColorConfig {
+        foreground: -1,
+        background: -1,
+      },
+      highlight: ColorConfig {
+        foreground: -1,
+        background: pancurses::COLOR_BLUE,
+      },
+    }
+  }
+}
+
+fn deserialize_color<'de, D>(deserializer: D) -> Result<i16, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let name = String::deserialize(deserializer)?;
+
+  let color = match name.as_str() {
+    "default" => -1,
+    "black" => pancurses::COLOR_BLACK,
+    "red" => pancurses::COLOR_RED,
+    "green" => pancurses::COLOR_GREEN,
+    "yellow" => pancurses::COLOR_YELLOW,
+    "blue" => pancurses::COLOR_BLUE,
+    "magenta" => pancurses::COLOR_MAGENTA,
+    "cyan" => pancurses::COLOR_CYAN,
+    "white" => pancurses::COLOR_WHITE,
+    _ => {
+      return Err(D::Error::custom(format!("unknown color: {}", name)))
+    }
+  };
+
+  Ok(color)
+}
@@ -0,0 +1,103 @@
+mod color_config;
+mod scoring_config;
+
+pub use self::color_config::{ColorConfig, ColorsConfig};
+pub use self::scoring_config::ScoringConfig;
+
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use toml;
+
+const CONFIG_FILE: &str = "config.toml";
+const KEYMAP_FILE: &str = "keymap.toml";
+
+// Look how I don't specify even localhost. By doing so, I will use a
+// Unix domain socket, which means I won't be blocked by my firewall.
+const DEFAULT_DATABASE_URL: &str = "postgres://ruggeri@:5432/task_manager";
+
+// Everything a user may want to change without editing source. Any
+// field missing from the file keeps its default.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+  pub database_url: String,
+  pub scoring: ScoringConfig,
+  pub colors: ColorsConfig,
+  // A file of command name => keys, e.g. `jump_to_top = "gg"`. Defaults
+  // to `keymap.toml` next to the config file.
+  pub keymap_file: Option<PathBuf>,
+  // Same as the keymap file, and wins over it.
+  pub keybindings: HashMap<String, String>,
+}
+
+impl Config {
+  // Reads the file given by `--config`, else the one in the XDG config
+  // directory if there is one. `DATABASE_URL` trumps the file. Errors
+  // say which file couldn't be read or parsed.
+  pub fn load(path: Option<PathBuf>) -> Result<Config, String> {
+    let mut config = match path {
+      Some(path) => Config::from_file(&path)?,
+      None => match config_dir() {
+        Some(ref dir) if dir.join(CONFIG_FILE).exists() => {
+          Config::from_file(&dir.join(CONFIG_FILE))?
+        }
+        _ => Config::default(),
+      },
+    };
+
+    let keymap_file = config.keymap_file.clone().or_else(|| {
+      config_dir()
+        .map(|dir| dir.join(KEYMAP_FILE))
+        .filter(|path| path.exists())
+    });
+    if let Some(keymap_file) = keymap_file {
+      let mut keybindings: HashMap<String, String> = read_toml(&keymap_file)?;
+      keybindings.extend(config.keybindings.drain());
+      config.keybindings = keybindings;
+    }
+
+    if let Ok(database_url) = env::var("DATABASE_URL") {
+      config.database_url = database_url;
+    }
+
+    Ok(config)
+  }
+
+  fn from_file(path: &PathBuf) -> Result<Config, String> {
+    read_toml(path)
+  }
+}
+
+fn read_toml<T: DeserializeOwned>(path: &PathBuf) -> Result<T, String> {
+  let contents = fs::read_to_string(path)
+    .map_err(|err| format!("Error reading {}: {}", path.display(), err))?;
+
+  toml::from_str(&contents)
+    .map_err(|err| format!("Error parsing {}: {}", path.display(), err))
+}
+
+impl Default for Config {
+  fn default() -> Config {
+    Config {
+      database_url: String::from(DEFAULT_DATABASE_URL),
+      scoring: ScoringConfig::default(),
+      colors: ColorsConfig::default(),
+      keymap_file: None,
+      keybindings: HashMap::new(),
+    }
+  }
+}
+
+// `$XDG_CONFIG_HOME/task-manager`, where XDG_CONFIG_HOME falls back to
+// `~/.config` as the spec says.
+fn config_dir() -> Option<PathBuf> {
+  let config_home = match env::var_os("XDG_CONFIG_HOME") {
+    Some(ref dir) if !dir.is_empty() => PathBuf::from(dir),
+    _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+  };
+
+  Some(config_home.join("task-manager"))
+}
@@ -0,0 +1,19 @@
+// Knobs shared by every `Scorer`.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScoringConfig {
+  // Each step up in priority (or down in duration) multiplies the
+  // score by this.
+  pub base_priority_factor: f64,
+  // How far back each delay request pushes a task.
+  pub delay_days: i64,
+}
+
+impl Default for ScoringConfig {
+  fn default() -> ScoringConfig {
+    ScoringConfig {
+      base_priority_factor: 1.42,
+      delay_days: 1,
+    }
+  }
+}
@@ -0,0 +1,59 @@
+use diesel;
+use std::fmt;
+use std::result;
+
+// Anything that can go wrong talking to storage. Actions hand these
+// back up to the view, which shows them instead of crashing.
+#[derive(Debug)]
+pub enum Error {
+  Connect {
+    database_url: String,
+    source: diesel::ConnectionError,
+  },
+  Query(diesel::result::Error),
+  // An update or lookup by id found nothing. Usually the row was
+  // destroyed or deleted behind our back.
+  NoSuchRow { table: &'static str, id: i32 },
+  // A saved action whose values can't be read back, e.g. because it
+  // was written by a newer version.
+  BadActionLogEntry { id: i32 },
+}
+
+pub type Result<T> = result::Result<T, Error>;
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Error::Connect {
+        database_url,
+        source,
+      } => write!(f, "Error connecting to {}: {}", database_url, source),
+      Error::Query(err) => write!(f, "Database error: {}", err),
+      Error::NoSuchRow { table, id } => {
+        write!(f, "No row with id {} in {}", id, table)
+      }
+      Error::BadActionLogEntry { id } => {
+        write!(f, "Can't read action log entry {}", id)
+      }
+    }
+  }
+}
+
+impl From<diesel::result::Error> for Error {
+  fn from(err: diesel::result::Error) -> Error {
+    Error::Query(err)
+  }
+}
+
+// For updates, which should touch exactly the one row they looked up.
+pub fn expect_one_row(
+  table: &'static str,
+  id: i32,
+  num_updated: usize,
+) -> Result<()> {
+  if num_updated == 1 {
+    Ok(())
+  } else {
+    Err(Error::NoSuchRow { table, id })
+  }
+}
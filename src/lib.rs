@@ -5,13 +5,20 @@ extern crate diesel;
 extern crate diesel_derive_enum;
 extern crate pancurses;
 extern crate rustyline;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate toml;
 
 pub mod actions;
 pub mod application;
 pub mod commands;
 pub mod components;
+pub mod config;
+pub mod error;
 pub mod models;
 pub mod queries;
 pub mod schema;
+pub mod storage;
 pub mod util;
 pub mod views;
@@ -0,0 +1,43 @@
+// Because Queryable triggers a Rust lang warning. They'll fix this in a
+// future release.
+#![allow(proc_macro_derive_resolution_fallback)]
+
+use models::ActionLogKind;
+
+type DateTime = ::chrono::DateTime<::chrono::Utc>;
+
+// A task action saved so it can still be undone after a restart. Values
+// are stored as text; what they mean depends on `kind`.
+#[derive(Clone, Debug, Queryable)]
+pub struct ActionLogEntry {
+  pub id: i32,
+  pub kind: ActionLogKind,
+  pub task_id: i32,
+  pub old_value: Option<String>,
+  pub new_value: Option<String>,
+  // The event the action recorded, if any.
+  pub task_event_id: Option<i32>,
+  // The next instance of a recurring task that completing it created.
+  pub spawned_task_id: Option<i32>,
+  pub created_at: DateTime,
+  pub undone: bool,
+  // The entry that was the last applied one when this action was done,
+  // which makes the log a tree like the `UndoBuffer`.
+  pub parent_id: Option<i32>,
+  // Actions done as one, e.g. to every filtered task, are logged one
+  // entry each. All but the first point at the first.
+  pub batch_id: Option<i32>,
+}
+
+// What an action hands to `Storage::log_action`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NewActionLogEntry {
+  pub kind: ActionLogKind,
+  pub task_id: i32,
+  pub old_value: Option<String>,
+  pub new_value: Option<String>,
+  pub task_event_id: Option<i32>,
+  pub spawned_task_id: Option<i32>,
+  pub parent_id: Option<i32>,
+  pub batch_id: Option<i32>,
+}
@@ -0,0 +1,14 @@
+// Which `TaskAction` an action log entry records.
+#[derive(Clone, Copy, DbEnum, Debug, Eq, PartialEq)]
+pub enum ActionLogKind {
+  CreateTask,
+  RecordTaskEffort,
+  RequestTaskAgeReset,
+  RequestTaskDelay,
+  UpdateDuration,
+  UpdatePriority,
+  UpdateRecurrence,
+  UpdateRequiresInternet,
+  UpdateStatus,
+  UpdateTaskTitle,
+}
@@ -0,0 +1,10 @@
+// Because Queryable triggers a Rust lang warning. They'll fix this in a
+// future release.
+#![allow(proc_macro_derive_resolution_fallback)]
+
+// A filter saved under a name. The query is in the `:filter` language.
+#[derive(Clone, Debug, Queryable)]
+pub struct FilterPreset {
+  pub name: String,
+  pub query: String,
+}
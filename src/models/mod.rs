@@ -1,25 +1,35 @@
+mod action_log_entry;
+mod action_log_kind;
 mod direction;
 mod end;
+mod filter_preset;
 mod task;
 mod task_duration;
 mod task_event;
 mod task_event_type;
 mod task_priority;
+mod task_recurrence;
 mod task_status;
 
 // For the schema file.
 pub mod mappings {
+  pub use super::action_log_kind::ActionLogKindMapping;
   pub use super::task_duration::TaskDurationMapping;
   pub use super::task_event_type::TaskEventTypeMapping;
   pub use super::task_priority::TaskPriorityMapping;
+  pub use super::task_recurrence::TaskRecurrenceTypeMapping;
   pub use super::task_status::TaskStatusMapping;
 }
 
+pub use self::action_log_entry::{ActionLogEntry, NewActionLogEntry};
+pub use self::action_log_kind::ActionLogKind;
 pub use self::direction::Direction;
 pub use self::end::End;
+pub use self::filter_preset::FilterPreset;
 pub use self::task::Task;
 pub use self::task_duration::TaskDuration;
 pub use self::task_event::TaskEvent;
 pub use self::task_event_type::TaskEventType;
 pub use self::task_priority::TaskPriority;
+pub use self::task_recurrence::{TaskRecurrence, TaskRecurrenceType};
 pub use self::task_status::TaskStatus;
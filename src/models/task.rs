@@ -2,7 +2,10 @@
 // future release.
 #![allow(proc_macro_derive_resolution_fallback)]
 
-use models::{TaskDuration, TaskPriority, TaskStatus};
+use models::{
+  TaskDuration, TaskPriority, TaskRecurrence, TaskRecurrenceType,
+  TaskStatus,
+};
 use schema::tasks;
 
 type DateTime = ::chrono::DateTime<::chrono::Utc>;
@@ -17,4 +20,15 @@ pub struct Task {
   pub priority: TaskPriority,
   pub duration: TaskDuration,
   pub destroyed: bool,
+  pub recurrence_type: Option<TaskRecurrenceType>,
+  pub recurrence_value: i32,
+}
+
+impl Task {
+  pub fn recurrence(&self) -> Option<TaskRecurrence> {
+    TaskRecurrence::from_columns(
+      self.recurrence_type,
+      self.recurrence_value,
+    )
+  }
 }
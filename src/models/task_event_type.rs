@@ -1,6 +1,35 @@
+use models::TaskStatus;
+
 #[derive(Clone, Copy, DbEnum, Debug, Eq, PartialEq)]
 pub enum TaskEventType {
   AgeResetRequested,
   DelayRequested,
+  TaskAbandoned,
+  TaskCompleted,
   TaskEffortRecorded,
+  TaskReopened,
+}
+
+impl TaskEventType {
+  // The event recording a change to the given status.
+  pub fn from_status(status: TaskStatus) -> TaskEventType {
+    match status {
+      TaskStatus::Abandoned => TaskEventType::TaskAbandoned,
+      TaskStatus::AvailableToPerform => TaskEventType::TaskReopened,
+      TaskStatus::Completed => TaskEventType::TaskCompleted,
+    }
+  }
+
+  pub fn label(self) -> &'static str {
+    use self::TaskEventType::*;
+
+    match self {
+      AgeResetRequested => "age reset",
+      DelayRequested => "delay",
+      TaskAbandoned => "abandoned",
+      TaskCompleted => "completed",
+      TaskEffortRecorded => "effort",
+      TaskReopened => "reopened",
+    }
+  }
 }
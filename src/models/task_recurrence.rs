@@ -0,0 +1,199 @@
+use chrono::{
+  Date, DateTime, Datelike, Duration, Local, NaiveDate, Utc, Weekday,
+};
+use std::fmt;
+
+const WEEKDAY_NAMES: [(&str, Weekday); 7] = [
+  ("mon", Weekday::Mon),
+  ("tue", Weekday::Tue),
+  ("wed", Weekday::Wed),
+  ("thu", Weekday::Thu),
+  ("fri", Weekday::Fri),
+  ("sat", Weekday::Sat),
+  ("sun", Weekday::Sun),
+];
+
+// This is what is stored in the `recurrence_type` column. The
+// `recurrence_value` column holds the argument of the rule.
+#[derive(Clone, Copy, DbEnum, Debug, Eq, PartialEq)]
+pub enum TaskRecurrenceType {
+  Daily,
+  EveryNDays,
+  Monthly,
+  Weekly,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TaskRecurrence {
+  Daily,
+  // Counted from the moment the task is completed.
+  EveryNDays { num_days: u32 },
+  // Clamped to the last day of shorter months.
+  Monthly { day: u32 },
+  // Bitmask of weekdays. Monday is the lowest bit.
+  Weekly { weekdays: u8 },
+}
+
+fn weekday_bit(weekday: Weekday) -> u8 {
+  1 << weekday.num_days_from_monday()
+}
+
+fn parse_weekdays(s: &str) -> Option<u8> {
+  let mut weekdays = 0;
+  for name in s.split(',') {
+    let name = name.trim();
+    let weekday = WEEKDAY_NAMES
+      .iter()
+      .find(|(weekday_name, _)| *weekday_name == name)
+      .map(|(_, weekday)| *weekday)?;
+    weekdays |= weekday_bit(weekday);
+  }
+
+  Some(weekdays)
+}
+
+fn parse_positive(s: &str) -> Option<u32> {
+  s.trim().parse().ok().filter(|n| *n > 0)
+}
+
+fn days_in_month(date: NaiveDate) -> u32 {
+  let (year, month) = if date.month() == 12 {
+    (date.year() + 1, 1)
+  } else {
+    (date.year(), date.month() + 1)
+  };
+
+  NaiveDate::from_ymd(year, month, 1).pred().day()
+}
+
+fn start_of_day(date: Date<Local>) -> DateTime<Utc> {
+  date.and_hms(0, 0, 0).with_timezone(&Utc)
+}
+
+impl TaskRecurrence {
+  pub fn from_columns(
+    recurrence_type: Option<TaskRecurrenceType>,
+    recurrence_value: i32,
+  ) -> Option<TaskRecurrence> {
+    use self::TaskRecurrenceType::*;
+
+    recurrence_type.map(|recurrence_type| match recurrence_type {
+      Daily => TaskRecurrence::Daily,
+      EveryNDays => TaskRecurrence::EveryNDays {
+        num_days: recurrence_value as u32,
+      },
+      Monthly => TaskRecurrence::Monthly {
+        day: recurrence_value as u32,
+      },
+      Weekly => TaskRecurrence::Weekly {
+        weekdays: recurrence_value as u8,
+      },
+    })
+  }
+
+  pub fn to_columns(
+    recurrence: Option<TaskRecurrence>,
+  ) -> (Option<TaskRecurrenceType>, i32) {
+    use self::TaskRecurrence::*;
+
+    match recurrence {
+      None => (None, 0),
+      Some(Daily) => (Some(TaskRecurrenceType::Daily), 0),
+      Some(EveryNDays { num_days }) => {
+        (Some(TaskRecurrenceType::EveryNDays), num_days as i32)
+      }
+      Some(Monthly { day }) => {
+        (Some(TaskRecurrenceType::Monthly), day as i32)
+      }
+      Some(Weekly { weekdays }) => {
+        (Some(TaskRecurrenceType::Weekly), i32::from(weekdays))
+      }
+    }
+  }
+
+  // Accepts the same format that `Display` produces: `daily`,
+  // `weekly:mon,thu`, `every:3` or `monthly:15`.
+  pub fn parse(s: &str) -> Option<TaskRecurrence> {
+    use self::TaskRecurrence::*;
+
+    let s = s.trim();
+    let (kind, arg) = match s.find(':') {
+      None => (s, None),
+      Some(idx) => (&s[..idx], Some(&s[(idx + 1)..])),
+    };
+
+    match (kind, arg) {
+      ("daily", None) => Some(Daily),
+      ("every", Some(arg)) => {
+        parse_positive(arg).map(|num_days| EveryNDays { num_days })
+      }
+      ("monthly", Some(arg)) => parse_positive(arg)
+        .filter(|day| *day <= 31)
+        .map(|day| Monthly { day }),
+      ("weekly", Some(arg)) => parse_weekdays(arg)
+        .filter(|weekdays| *weekdays != 0)
+        .map(|weekdays| Weekly { weekdays }),
+      _ => None,
+    }
+  }
+
+  // When the next instance of a task completed at `completed_at`
+  // becomes due.
+  pub fn next_occurrence(
+    self,
+    completed_at: DateTime<Local>,
+  ) -> DateTime<Utc> {
+    use self::TaskRecurrence::*;
+
+    let today = completed_at.date();
+    match self {
+      Daily => start_of_day(today.succ()),
+      EveryNDays { num_days } => {
+        (completed_at + Duration::days(i64::from(num_days)))
+          .with_timezone(&Utc)
+      }
+      Monthly { day } => {
+        let mut date = today.succ();
+        loop {
+          let day_in_month =
+            ::std::cmp::min(day, days_in_month(date.naive_local()));
+          if date.day() == day_in_month {
+            break start_of_day(date);
+          }
+          date = date.succ();
+        }
+      }
+      Weekly { weekdays } => {
+        let mut date = today.succ();
+        // Lands a week from today if nothing earlier matches.
+        for _ in 0..6 {
+          if weekday_bit(date.weekday()) & weekdays != 0 {
+            break;
+          }
+          date = date.succ();
+        }
+        start_of_day(date)
+      }
+    }
+  }
+}
+
+impl fmt::Display for TaskRecurrence {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    use self::TaskRecurrence::*;
+
+    match *self {
+      Daily => write!(f, "daily"),
+      EveryNDays { num_days } => write!(f, "every:{}", num_days),
+      Monthly { day } => write!(f, "monthly:{}", day),
+      Weekly { weekdays } => {
+        let names: Vec<&str> = WEEKDAY_NAMES
+          .iter()
+          .filter(|(_, weekday)| weekday_bit(*weekday) & weekdays != 0)
+          .map(|(name, _)| *name)
+          .collect();
+        write!(f, "weekly:{}", names.join(","))
+      }
+    }
+  }
+}
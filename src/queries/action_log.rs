@@ -0,0 +1,72 @@
+// Because Queryable triggers a Rust lang warning. They'll fix this in a
+// future release.
+#![allow(proc_macro_derive_resolution_fallback)]
+
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use error::{expect_one_row, Error, Result};
+use models::{ActionLogEntry, ActionLogKind, NewActionLogEntry};
+use schema::action_log;
+
+#[derive(Insertable)]
+#[table_name = "action_log"]
+struct NewActionLogRow<'a> {
+  kind: ActionLogKind,
+  task_id: i32,
+  old_value: Option<&'a str>,
+  new_value: Option<&'a str>,
+  task_event_id: Option<i32>,
+  spawned_task_id: Option<i32>,
+  parent_id: Option<i32>,
+  batch_id: Option<i32>,
+}
+
+// The entries of the newest `limit` actions, oldest first. The limit
+// counts the first entry of each batch, then the rest of those batches
+// are fetched with them.
+pub fn recent(
+  limit: i64,
+  connection: &PgConnection,
+) -> Result<Vec<ActionLogEntry>> {
+  use schema::action_log::dsl::*;
+
+  let first_ids = action_log
+    .filter(batch_id.is_null())
+    .order(id.desc())
+    .limit(limit)
+    .select(id)
+    .load::<i32>(connection)?;
+
+  action_log
+    .filter(id.eq_any(&first_ids).or(batch_id.eq_any(&first_ids)))
+    .order(id.asc())
+    .load::<ActionLogEntry>(connection)
+    .map_err(Error::from)
+}
+
+pub fn create(
+  entry: &NewActionLogEntry,
+  connection: &PgConnection,
+) -> Result<ActionLogEntry> {
+  let new_row = NewActionLogRow {
+    kind: entry.kind,
+    task_id: entry.task_id,
+    old_value: entry.old_value.as_deref(),
+    new_value: entry.new_value.as_deref(),
+    task_event_id: entry.task_event_id,
+    spawned_task_id: entry.spawned_task_id,
+    parent_id: entry.parent_id,
+    batch_id: entry.batch_id,
+  };
+
+  diesel::insert_into(action_log::table)
+    .values(&new_row)
+    .get_result(connection)
+    .map_err(Error::from)
+}
+
+define_update_attribute_fns!(
+  action_log,
+  (update_undone, bool, undone)
+);
@@ -0,0 +1,59 @@
+// Because Queryable triggers a Rust lang warning. They'll fix this in a
+// future release.
+#![allow(proc_macro_derive_resolution_fallback)]
+
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use error::{Error, Result};
+use models::FilterPreset;
+use schema::filter_presets;
+
+#[derive(Insertable)]
+#[table_name = "filter_presets"]
+struct NewFilterPreset<'a> {
+  name: &'a str,
+  query: &'a str,
+}
+
+// By name.
+pub fn all(connection: &PgConnection) -> Result<Vec<FilterPreset>> {
+  use schema::filter_presets::dsl::*;
+
+  filter_presets
+    .order(name)
+    .load(connection)
+    .map_err(Error::from)
+}
+
+// Inserts the preset, or overwrites it if it already exists.
+pub fn save(
+  preset_name: &str,
+  new_query: &str,
+  connection: &PgConnection,
+) -> Result<()> {
+  use schema::filter_presets::dsl::*;
+
+  let new_preset = NewFilterPreset {
+    name: preset_name,
+    query: new_query,
+  };
+
+  diesel::insert_into(filter_presets)
+    .values(&new_preset)
+    .on_conflict(name)
+    .do_update()
+    .set(query.eq(new_query))
+    .execute(connection)?;
+
+  Ok(())
+}
+
+// Deleting a preset that isn't there does nothing.
+pub fn delete(preset_name: &str, connection: &PgConnection) -> Result<()> {
+  use schema::filter_presets::dsl::*;
+
+  diesel::delete(filter_presets.find(preset_name)).execute(connection)?;
+
+  Ok(())
+}
@@ -4,17 +4,14 @@ macro_rules! define_update_attribute_fn {
       id: i32,
       new_value: $value_type,
       connection: &PgConnection,
-    ) {
+    ) -> Result<()> {
       use schema::$table::dsl;
 
       let num_updated = diesel::update(dsl::$table.find(id))
         .set(dsl::$field_name.eq(new_value))
-        .execute(connection)
-        .expect("Error updating task");
+        .execute(connection)?;
 
-      if num_updated != 1 {
-        panic!("Expected to update exactly one task");
-      }
+      expect_one_row(stringify!($table), id, num_updated)
     }
   };
 }
@@ -27,5 +24,8 @@ macro_rules! define_update_attribute_fns {
   }
 }
 
+pub mod action_log;
+pub mod filter_preset;
+pub mod setting;
 pub mod task;
 pub mod task_event;
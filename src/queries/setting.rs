@@ -0,0 +1,53 @@
+// Because Queryable triggers a Rust lang warning. They'll fix this in a
+// future release.
+#![allow(proc_macro_derive_resolution_fallback)]
+
+use diesel;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use error::{Error, Result};
+use schema::settings;
+
+#[derive(Insertable)]
+#[table_name = "settings"]
+struct NewSetting<'a> {
+  name: &'a str,
+  value: &'a str,
+}
+
+pub fn get(
+  setting_name: &str,
+  connection: &PgConnection,
+) -> Result<Option<String>> {
+  use schema::settings::dsl::*;
+
+  settings
+    .find(setting_name)
+    .select(value)
+    .get_result(connection)
+    .optional()
+    .map_err(Error::from)
+}
+
+// Inserts the setting, or overwrites it if it already exists.
+pub fn set(
+  setting_name: &str,
+  new_value: &str,
+  connection: &PgConnection,
+) -> Result<()> {
+  use schema::settings::dsl::*;
+
+  let new_setting = NewSetting {
+    name: setting_name,
+    value: new_value,
+  };
+
+  diesel::insert_into(settings)
+    .values(&new_setting)
+    .on_conflict(name)
+    .do_update()
+    .set(value.eq(new_value))
+    .execute(connection)?;
+
+  Ok(())
+}
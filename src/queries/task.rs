@@ -5,9 +5,15 @@
 use diesel;
 use diesel::pg::PgConnection;
 use diesel::prelude::*;
-use models::{Task, TaskDuration, TaskPriority, TaskStatus};
+use error::{expect_one_row, Error, Result};
+use models::{
+  Task, TaskDuration, TaskPriority, TaskRecurrence, TaskRecurrenceType,
+  TaskStatus,
+};
 use schema::tasks;
 
+type DateTime = ::chrono::DateTime<::chrono::Utc>;
+
 #[derive(Insertable)]
 #[table_name = "tasks"]
 struct NewTask {
@@ -15,21 +21,36 @@ struct NewTask {
   status: TaskStatus,
 }
 
+#[derive(Insertable)]
+#[table_name = "tasks"]
+struct NewRecurrenceTask<'a> {
+  title: &'a str,
+  status: TaskStatus,
+  created_at: DateTime,
+  requires_internet: bool,
+  priority: TaskPriority,
+  duration: TaskDuration,
+  recurrence_type: Option<TaskRecurrenceType>,
+  recurrence_value: i32,
+}
+
 pub fn all_available_to_perform(
   connection: &PgConnection,
-) -> Vec<Task> {
+) -> Result<Vec<Task>> {
   use schema::tasks::dsl::*;
   tasks
     .filter(
       status
         .eq(TaskStatus::AvailableToPerform)
-        .and(destroyed.eq(false)),
+        .and(destroyed.eq(false))
+        // Upcoming instances of recurring tasks are not yet due.
+        .and(created_at.le(diesel::dsl::now)),
     ).order(id)
     .load::<Task>(connection)
-    .unwrap()
+    .map_err(Error::from)
 }
 
-pub fn create(title: &str, connection: &PgConnection) -> Task {
+pub fn create(title: &str, connection: &PgConnection) -> Result<Task> {
   let new_task = NewTask {
     title: String::from(title),
     status: TaskStatus::AvailableToPerform,
@@ -38,7 +59,58 @@ pub fn create(title: &str, connection: &PgConnection) -> Task {
   diesel::insert_into(::schema::tasks::table)
     .values(&new_task)
     .get_result(connection)
-    .expect("Error creating task")
+    .map_err(Error::from)
+}
+
+// Creates the instance of a recurring task that becomes due at
+// `created_at`.
+pub fn create_next_recurrence(
+  task: &Task,
+  created_at: DateTime,
+  connection: &PgConnection,
+) -> Result<Task> {
+  let new_task = NewRecurrenceTask {
+    title: &task.title,
+    status: TaskStatus::AvailableToPerform,
+    created_at,
+    requires_internet: task.requires_internet,
+    priority: task.priority,
+    duration: task.duration,
+    recurrence_type: task.recurrence_type,
+    recurrence_value: task.recurrence_value,
+  };
+
+  diesel::insert_into(::schema::tasks::table)
+    .values(&new_task)
+    .get_result(connection)
+    .map_err(Error::from)
+}
+
+pub fn find(id: i32, connection: &PgConnection) -> Result<Task> {
+  use schema::tasks::dsl;
+
+  dsl::tasks
+    .find(id)
+    .get_result(connection)
+    .optional()?
+    .ok_or(Error::NoSuchRow { table: "tasks", id })
+}
+
+pub fn update_recurrence(
+  id: i32,
+  new_value: Option<TaskRecurrence>,
+  connection: &PgConnection,
+) -> Result<()> {
+  use schema::tasks::dsl;
+
+  let (new_type, new_value) = TaskRecurrence::to_columns(new_value);
+  let num_updated = diesel::update(dsl::tasks.find(id))
+    .set((
+      dsl::recurrence_type.eq(new_type),
+      dsl::recurrence_value.eq(new_value),
+    )).execute(connection)?;
+
+  expect_one_row("tasks", id, num_updated)
 }
 
 define_update_attribute_fns!(
@@ -5,9 +5,12 @@
 use diesel;
 use diesel::pg::PgConnection;
 use diesel::prelude::*;
-use models::{Task, TaskEvent, TaskEventType};
+use error::{expect_one_row, Error, Result};
+use models::{Task, TaskEvent, TaskEventType, TaskStatus};
 use schema::task_events;
 
+type DateTime = ::chrono::DateTime<::chrono::Utc>;
+
 #[derive(Insertable)]
 #[table_name = "task_events"]
 struct NewTaskEvent {
@@ -15,23 +18,77 @@ struct NewTaskEvent {
   event_type: TaskEventType,
 }
 
+// Finds destroyed events too, e.g. to redo recording them.
+pub fn find(id: i32, connection: &PgConnection) -> Result<TaskEvent> {
+  use schema::task_events::dsl;
+
+  dsl::task_events
+    .find(id)
+    .get_result(connection)
+    .optional()?
+    .ok_or(Error::NoSuchRow {
+      table: "task_events",
+      id,
+    })
+}
+
 pub fn task_events(
   task: &Task,
   connection: &PgConnection,
-) -> Vec<TaskEvent> {
+) -> Result<Vec<TaskEvent>> {
   use schema::task_events::dsl::*;
 
   TaskEvent::belonging_to(task)
     .filter(destroyed.eq(false))
     .order((created_at.desc(), id))
     .load::<TaskEvent>(connection)
-    .unwrap()
+    .map_err(Error::from)
+}
+
+// Events of many tasks in one query, grouped to line up with `tasks`.
+// Each group is newest first, like `task_events`.
+pub fn task_events_by_task(
+  tasks: &[Task],
+  connection: &PgConnection,
+) -> Result<Vec<Vec<TaskEvent>>> {
+  use schema::task_events::dsl::*;
+
+  TaskEvent::belonging_to(tasks)
+    .filter(destroyed.eq(false))
+    .order((created_at.desc(), id))
+    .load::<TaskEvent>(connection)
+    .map(|all_task_events| all_task_events.grouped_by(tasks))
+    .map_err(Error::from)
+}
+
+// Completions and efforts recorded since the given time, oldest first.
+pub fn done_since(
+  since: DateTime,
+  connection: &PgConnection,
+) -> Result<Vec<(TaskEvent, Task)>> {
+  use schema::{task_events, tasks};
+
+  task_events::table
+    .inner_join(tasks::table)
+    .filter(
+      task_events::destroyed
+        .eq(false)
+        .and(tasks::destroyed.eq(false))
+        .and(task_events::created_at.ge(since))
+        .and(
+          task_events::event_type
+            .eq(TaskEventType::TaskCompleted)
+            .or(task_events::event_type.eq(TaskEventType::TaskEffortRecorded)),
+        ),
+    ).order(task_events::created_at)
+    .load::<(TaskEvent, Task)>(connection)
+    .map_err(Error::from)
 }
 
 pub fn record_task_effort(
   task_id: i32,
   connection: &PgConnection,
-) -> TaskEvent {
+) -> Result<TaskEvent> {
   let new_te = NewTaskEvent {
     task_id,
     event_type: TaskEventType::TaskEffortRecorded,
@@ -40,13 +97,13 @@ pub fn record_task_effort(
   diesel::insert_into(::schema::task_events::table)
     .values(&new_te)
     .get_result(connection)
-    .unwrap()
+    .map_err(Error::from)
 }
 
 pub fn request_delay(
   task_id: i32,
   connection: &PgConnection,
-) -> TaskEvent {
+) -> Result<TaskEvent> {
   let new_te = NewTaskEvent {
     task_id,
     event_type: TaskEventType::DelayRequested,
@@ -55,13 +112,13 @@ pub fn request_delay(
   diesel::insert_into(::schema::task_events::table)
     .values(&new_te)
     .get_result(connection)
-    .unwrap()
+    .map_err(Error::from)
 }
 
 pub fn request_task_age_reset(
   task_id: i32,
   connection: &PgConnection,
-) -> TaskEvent {
+) -> Result<TaskEvent> {
   let new_te = NewTaskEvent {
     task_id,
     event_type: TaskEventType::AgeResetRequested,
@@ -70,7 +127,23 @@ pub fn request_task_age_reset(
   diesel::insert_into(::schema::task_events::table)
     .values(&new_te)
     .get_result(connection)
-    .unwrap()
+    .map_err(Error::from)
+}
+
+pub fn record_status_change(
+  task_id: i32,
+  new_status: TaskStatus,
+  connection: &PgConnection,
+) -> Result<TaskEvent> {
+  let new_te = NewTaskEvent {
+    task_id,
+    event_type: TaskEventType::from_status(new_status),
+  };
+
+  diesel::insert_into(::schema::task_events::table)
+    .values(&new_te)
+    .get_result(connection)
+    .map_err(Error::from)
 }
 
 define_update_attribute_fns!(
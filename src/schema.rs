@@ -2,6 +2,39 @@
 // future release.
 #![allow(proc_macro_derive_resolution_fallback)]
 
+table! {
+    use diesel::sql_types::*;
+    use models::mappings::ActionLogKindMapping;
+
+    action_log (id) {
+        id -> Int4,
+        kind -> ActionLogKindMapping,
+        task_id -> Int4,
+        old_value -> Nullable<Varchar>,
+        new_value -> Nullable<Varchar>,
+        task_event_id -> Nullable<Int4>,
+        spawned_task_id -> Nullable<Int4>,
+        created_at -> Timestamptz,
+        undone -> Bool,
+        parent_id -> Nullable<Int4>,
+        batch_id -> Nullable<Int4>,
+    }
+}
+
+table! {
+    filter_presets (name) {
+        name -> Varchar,
+        query -> Varchar,
+    }
+}
+
+table! {
+    settings (name) {
+        name -> Varchar,
+        value -> Varchar,
+    }
+}
+
 table! {
     use diesel::sql_types::*;
     use models::mappings::TaskEventTypeMapping;
@@ -20,6 +53,7 @@ table! {
     use diesel::sql_types::*;
     use models::mappings::TaskDurationMapping;
     use models::mappings::TaskPriorityMapping;
+    use models::mappings::TaskRecurrenceTypeMapping;
     use models::mappings::TaskStatusMapping;
 
     tasks (id) {
@@ -31,9 +65,17 @@ table! {
         priority -> TaskPriorityMapping,
         duration -> TaskDurationMapping,
         destroyed -> Bool,
+        recurrence_type -> Nullable<TaskRecurrenceTypeMapping>,
+        recurrence_value -> Int4,
     }
 }
 
 joinable!(task_events -> tasks (task_id));
 
-allow_tables_to_appear_in_same_query!(task_events, tasks,);
+allow_tables_to_appear_in_same_query!(
+    action_log,
+    filter_presets,
+    settings,
+    task_events,
+    tasks,
+);
@@ -0,0 +1,418 @@
+use chrono::Utc;
+use error::{Error, Result};
+use models::{
+  ActionLogEntry, FilterPreset, NewActionLogEntry, Task, TaskDuration,
+  TaskEvent, TaskEventType, TaskPriority, TaskRecurrence, TaskStatus,
+};
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap};
+use storage::Storage;
+
+type DateTime = ::chrono::DateTime<Utc>;
+
+macro_rules! define_update_attribute_fn {
+  ($update_fn:ident, $fn_name:ident, $value_type:ty, $field_name:ident) => {
+    fn $fn_name(&self, id: i32, new_value: $value_type) -> Result<()> {
+      self.$update_fn(id, |record| record.$field_name = new_value)
+    }
+  };
+}
+
+// Keeps everything in vectors and forgets it all when dropped. Lets
+// tests run actions and components without a database.
+#[derive(Default)]
+pub struct MemoryStorage {
+  tasks: RefCell<Vec<Task>>,
+  task_events: RefCell<Vec<TaskEvent>>,
+  settings: RefCell<HashMap<String, String>>,
+  // Query by name, so they come out in name order.
+  filter_presets: RefCell<BTreeMap<String, String>>,
+  action_log: RefCell<Vec<ActionLogEntry>>,
+  // Counted separately, like a database sequence, so ids aren't reused
+  // after a rolled back transaction.
+  num_task_ids: Cell<i32>,
+  num_task_event_ids: Cell<i32>,
+  num_action_log_ids: Cell<i32>,
+}
+
+// Same order as the databases: newest first, ties by id.
+fn sort_newest_first(task_events: &mut [TaskEvent]) {
+  task_events.sort_by(|te1, te2| {
+    te2
+      .created_at
+      .cmp(&te1.created_at)
+      .then(te1.id.cmp(&te2.id))
+  });
+}
+
+impl MemoryStorage {
+  pub fn new() -> MemoryStorage {
+    MemoryStorage::default()
+  }
+
+  // Like `create_task`, but as if it had been created at `created_at`.
+  // Lets tests set up tasks of different ages.
+  pub fn create_task_at(&self, title: &str, created_at: DateTime) -> Task {
+    self.insert_task(Task {
+      id: 0,
+      title: title.to_string(),
+      status: TaskStatus::AvailableToPerform,
+      created_at,
+      requires_internet: false,
+      priority: TaskPriority::Low,
+      duration: TaskDuration::Short,
+      destroyed: false,
+      recurrence_type: None,
+      recurrence_value: 0,
+    })
+  }
+
+  // Ids are handed out like a database would: from 1, never reused.
+  fn insert_task(&self, mut task: Task) -> Task {
+    task.id = self.num_task_ids.get() + 1;
+    self.num_task_ids.set(task.id);
+    self.tasks.borrow_mut().push(task.clone());
+    task
+  }
+
+  // Like the databases' foreign key, refuses events for unknown tasks.
+  fn insert_task_event(
+    &self,
+    task_id: i32,
+    event_type: TaskEventType,
+  ) -> Result<TaskEvent> {
+    self.find_task(task_id)?;
+
+    let id = self.num_task_event_ids.get() + 1;
+    self.num_task_event_ids.set(id);
+    let task_event = TaskEvent {
+      id,
+      task_id,
+      created_at: Utc::now(),
+      destroyed: false,
+      event_type,
+    };
+    self.task_events.borrow_mut().push(task_event.clone());
+    Ok(task_event)
+  }
+
+  fn update_task<F: FnOnce(&mut Task)>(&self, id: i32, f: F) -> Result<()> {
+    let mut tasks = self.tasks.borrow_mut();
+    match tasks.iter_mut().find(|task| task.id == id) {
+      None => Err(Error::NoSuchRow { table: "tasks", id }),
+      Some(task) => {
+        f(task);
+        Ok(())
+      }
+    }
+  }
+
+  fn update_task_event<F: FnOnce(&mut TaskEvent)>(
+    &self,
+    id: i32,
+    f: F,
+  ) -> Result<()> {
+    let mut task_events = self.task_events.borrow_mut();
+    match task_events.iter_mut().find(|task_event| task_event.id == id) {
+      None => Err(Error::NoSuchRow {
+        table: "task_events",
+        id,
+      }),
+      Some(task_event) => {
+        f(task_event);
+        Ok(())
+      }
+    }
+  }
+}
+
+impl Storage for MemoryStorage {
+  fn all_available_to_perform(&self) -> Result<Vec<Task>> {
+    let current_time = Utc::now();
+
+    Ok(
+      self
+        .tasks
+        .borrow()
+        .iter()
+        .filter(|task| {
+          task.status == TaskStatus::AvailableToPerform
+            && !task.destroyed
+            // Upcoming instances of recurring tasks are not yet due.
+            && task.created_at <= current_time
+        }).cloned()
+        .collect(),
+    )
+  }
+
+  fn create_task(&self, title: &str) -> Result<Task> {
+    Ok(self.create_task_at(title, Utc::now()))
+  }
+
+  fn create_next_recurrence(
+    &self,
+    task: &Task,
+    created_at: DateTime,
+  ) -> Result<Task> {
+    Ok(self.insert_task(Task {
+      id: 0,
+      title: task.title.clone(),
+      status: TaskStatus::AvailableToPerform,
+      created_at,
+      requires_internet: task.requires_internet,
+      priority: task.priority,
+      duration: task.duration,
+      destroyed: false,
+      recurrence_type: task.recurrence_type,
+      recurrence_value: task.recurrence_value,
+    }))
+  }
+
+  fn find_task(&self, id: i32) -> Result<Task> {
+    self
+      .tasks
+      .borrow()
+      .iter()
+      .find(|task| task.id == id)
+      .cloned()
+      .ok_or(Error::NoSuchRow { table: "tasks", id })
+  }
+
+  fn update_recurrence(
+    &self,
+    id: i32,
+    new_value: Option<TaskRecurrence>,
+  ) -> Result<()> {
+    let (new_type, new_value) = TaskRecurrence::to_columns(new_value);
+    self.update_task(id, |task| {
+      task.recurrence_type = new_type;
+      task.recurrence_value = new_value;
+    })
+  }
+
+  fn update_title(&self, id: i32, new_value: &str) -> Result<()> {
+    self.update_task(id, |task| task.title = new_value.to_string())
+  }
+
+  define_update_attribute_fn!(
+    update_task,
+    update_duration,
+    TaskDuration,
+    duration
+  );
+  define_update_attribute_fn!(
+    update_task,
+    update_priority,
+    TaskPriority,
+    priority
+  );
+  define_update_attribute_fn!(
+    update_task,
+    update_requires_internet,
+    bool,
+    requires_internet
+  );
+  define_update_attribute_fn!(update_task, update_status, TaskStatus, status);
+  define_update_attribute_fn!(
+    update_task,
+    update_task_destroyed,
+    bool,
+    destroyed
+  );
+
+  fn find_task_event(&self, id: i32) -> Result<TaskEvent> {
+    self
+      .task_events
+      .borrow()
+      .iter()
+      .find(|task_event| task_event.id == id)
+      .cloned()
+      .ok_or(Error::NoSuchRow {
+        table: "task_events",
+        id,
+      })
+  }
+
+  fn task_events(&self, task: &Task) -> Result<Vec<TaskEvent>> {
+    let mut task_events: Vec<_> = self
+      .task_events
+      .borrow()
+      .iter()
+      .filter(|te| te.task_id == task.id && !te.destroyed)
+      .cloned()
+      .collect();
+    sort_newest_first(&mut task_events);
+    Ok(task_events)
+  }
+
+  fn task_events_by_task(
+    &self,
+    tasks: &[Task],
+  ) -> Result<Vec<Vec<TaskEvent>>> {
+    tasks.iter().map(|task| self.task_events(task)).collect()
+  }
+
+  fn done_since(&self, since: DateTime) -> Result<Vec<(TaskEvent, Task)>> {
+    let mut task_events: Vec<_> = self
+      .task_events
+      .borrow()
+      .iter()
+      .filter(|te| {
+        !te.destroyed
+          && te.created_at >= since
+          && (te.event_type == TaskEventType::TaskCompleted
+            || te.event_type == TaskEventType::TaskEffortRecorded)
+      }).cloned()
+      .collect();
+    task_events.sort_by_key(|te| te.created_at);
+
+    let pairs = task_events
+      .into_iter()
+      .map(|te| {
+        let task = self.find_task(te.task_id)?;
+        Ok((te, task))
+      }).collect::<Result<Vec<_>>>()?;
+    Ok(pairs.into_iter().filter(|(_, task)| !task.destroyed).collect())
+  }
+
+  fn record_task_effort(&self, task_id: i32) -> Result<TaskEvent> {
+    self.insert_task_event(task_id, TaskEventType::TaskEffortRecorded)
+  }
+
+  fn request_delay(&self, task_id: i32) -> Result<TaskEvent> {
+    self.insert_task_event(task_id, TaskEventType::DelayRequested)
+  }
+
+  fn request_task_age_reset(&self, task_id: i32) -> Result<TaskEvent> {
+    self.insert_task_event(task_id, TaskEventType::AgeResetRequested)
+  }
+
+  fn record_status_change(
+    &self,
+    task_id: i32,
+    new_status: TaskStatus,
+  ) -> Result<TaskEvent> {
+    self.insert_task_event(task_id, TaskEventType::from_status(new_status))
+  }
+
+  define_update_attribute_fn!(
+    update_task_event,
+    update_task_event_destroyed,
+    bool,
+    destroyed
+  );
+
+  fn get_setting(&self, name: &str) -> Result<Option<String>> {
+    Ok(self.settings.borrow().get(name).cloned())
+  }
+
+  fn set_setting(&self, name: &str, value: &str) -> Result<()> {
+    self
+      .settings
+      .borrow_mut()
+      .insert(name.to_string(), value.to_string());
+    Ok(())
+  }
+
+  fn filter_presets(&self) -> Result<Vec<FilterPreset>> {
+    Ok(
+      self
+        .filter_presets
+        .borrow()
+        .iter()
+        .map(|(name, query)| FilterPreset {
+          name: name.clone(),
+          query: query.clone(),
+        }).collect(),
+    )
+  }
+
+  fn save_filter_preset(&self, name: &str, query: &str) -> Result<()> {
+    self
+      .filter_presets
+      .borrow_mut()
+      .insert(name.to_string(), query.to_string());
+    Ok(())
+  }
+
+  fn delete_filter_preset(&self, name: &str) -> Result<()> {
+    self.filter_presets.borrow_mut().remove(name);
+    Ok(())
+  }
+
+  fn recent_action_log(&self, limit: i64) -> Result<Vec<ActionLogEntry>> {
+    let action_log = self.action_log.borrow();
+    let first_ids: Vec<i32> = action_log
+      .iter()
+      .rev()
+      .filter(|entry| entry.batch_id.is_none())
+      .take(limit as usize)
+      .map(|entry| entry.id)
+      .collect();
+
+    let in_loaded_batch = |entry: &&ActionLogEntry| {
+      first_ids.contains(&entry.batch_id.unwrap_or(entry.id))
+    };
+    Ok(action_log.iter().filter(in_loaded_batch).cloned().collect())
+  }
+
+  fn log_action(&self, entry: &NewActionLogEntry) -> Result<ActionLogEntry> {
+    self.find_task(entry.task_id)?;
+
+    let id = self.num_action_log_ids.get() + 1;
+    self.num_action_log_ids.set(id);
+    let entry = ActionLogEntry {
+      id,
+      kind: entry.kind,
+      task_id: entry.task_id,
+      old_value: entry.old_value.clone(),
+      new_value: entry.new_value.clone(),
+      task_event_id: entry.task_event_id,
+      spawned_task_id: entry.spawned_task_id,
+      created_at: Utc::now(),
+      undone: false,
+      parent_id: entry.parent_id,
+      batch_id: entry.batch_id,
+    };
+    self.action_log.borrow_mut().push(entry.clone());
+    Ok(entry)
+  }
+
+  fn update_action_log_undone(&self, id: i32, new_value: bool) -> Result<()> {
+    let mut action_log = self.action_log.borrow_mut();
+    match action_log.iter_mut().find(|entry| entry.id == id) {
+      None => Err(Error::NoSuchRow {
+        table: "action_log",
+        id,
+      }),
+      Some(entry) => {
+        entry.undone = new_value;
+        Ok(())
+      }
+    }
+  }
+
+  // Puts back copies of everything if `f` fails.
+  fn transaction(&self, f: &mut dyn FnMut() -> Result<()>) -> Result<()> {
+    let tasks = self.tasks.borrow().clone();
+    let task_events = self.task_events.borrow().clone();
+    let settings = self.settings.borrow().clone();
+    let filter_presets = self.filter_presets.borrow().clone();
+    let action_log = self.action_log.borrow().clone();
+
+    let result = f();
+    if result.is_err() {
+      *self.tasks.borrow_mut() = tasks;
+      *self.task_events.borrow_mut() = task_events;
+      *self.settings.borrow_mut() = settings;
+      *self.filter_presets.borrow_mut() = filter_presets;
+      *self.action_log.borrow_mut() = action_log;
+    }
+    result
+  }
+
+  // Never kept anything anyway.
+  fn make_disposable(&self) -> Result<()> {
+    Ok(())
+  }
+}
@@ -0,0 +1,107 @@
+mod memory_storage;
+mod pg_storage;
+mod sqlite_storage;
+
+pub use self::memory_storage::MemoryStorage;
+pub use self::pg_storage::PgStorage;
+pub use self::sqlite_storage::SqliteStorage;
+
+use error::Result;
+use models::{
+  ActionLogEntry, FilterPreset, NewActionLogEntry, Task, TaskDuration,
+  TaskEvent, TaskPriority, TaskRecurrence, TaskStatus,
+};
+use std::rc::Rc;
+
+type DateTime = ::chrono::DateTime<::chrono::Utc>;
+
+// Everything the rest of the app reads or writes. Implemented once per
+// database, so nothing above this layer knows which one it is using.
+pub trait Storage {
+  // Tasks.
+  fn all_available_to_perform(&self) -> Result<Vec<Task>>;
+  fn create_task(&self, title: &str) -> Result<Task>;
+  // Creates the instance of a recurring task that becomes due at
+  // `created_at`.
+  fn create_next_recurrence(
+    &self,
+    task: &Task,
+    created_at: DateTime,
+  ) -> Result<Task>;
+  fn find_task(&self, id: i32) -> Result<Task>;
+  fn update_duration(&self, id: i32, new_value: TaskDuration) -> Result<()>;
+  fn update_priority(&self, id: i32, new_value: TaskPriority) -> Result<()>;
+  fn update_recurrence(
+    &self,
+    id: i32,
+    new_value: Option<TaskRecurrence>,
+  ) -> Result<()>;
+  fn update_requires_internet(&self, id: i32, new_value: bool) -> Result<()>;
+  fn update_status(&self, id: i32, new_value: TaskStatus) -> Result<()>;
+  fn update_title(&self, id: i32, new_value: &str) -> Result<()>;
+  fn update_task_destroyed(&self, id: i32, new_value: bool) -> Result<()>;
+
+  // Task events. Lists of events are newest first.
+  // Finds destroyed events too.
+  fn find_task_event(&self, id: i32) -> Result<TaskEvent>;
+  fn task_events(&self, task: &Task) -> Result<Vec<TaskEvent>>;
+  // Grouped to line up with `tasks`.
+  fn task_events_by_task(
+    &self,
+    tasks: &[Task],
+  ) -> Result<Vec<Vec<TaskEvent>>>;
+  // Completions and efforts recorded since the given time, oldest first.
+  fn done_since(&self, since: DateTime) -> Result<Vec<(TaskEvent, Task)>>;
+  fn record_task_effort(&self, task_id: i32) -> Result<TaskEvent>;
+  fn request_delay(&self, task_id: i32) -> Result<TaskEvent>;
+  fn request_task_age_reset(&self, task_id: i32) -> Result<TaskEvent>;
+  fn record_status_change(
+    &self,
+    task_id: i32,
+    new_status: TaskStatus,
+  ) -> Result<TaskEvent>;
+  fn update_task_event_destroyed(
+    &self,
+    id: i32,
+    new_value: bool,
+  ) -> Result<()>;
+
+  // Settings.
+  fn get_setting(&self, name: &str) -> Result<Option<String>>;
+  // Inserts the setting, or overwrites it if it already exists.
+  fn set_setting(&self, name: &str, value: &str) -> Result<()>;
+
+  // Filter presets, by name.
+  fn filter_presets(&self) -> Result<Vec<FilterPreset>>;
+  // Inserts the preset, or overwrites it if it already exists.
+  fn save_filter_preset(&self, name: &str, query: &str) -> Result<()>;
+  // Deleting a preset that isn't there does nothing.
+  fn delete_filter_preset(&self, name: &str) -> Result<()>;
+
+  // The action log, which lets task actions be undone after a restart.
+  // The entries of the newest `limit` actions, oldest first. A batch
+  // of entries logged together counts as one action, and is never
+  // split.
+  fn recent_action_log(&self, limit: i64) -> Result<Vec<ActionLogEntry>>;
+  fn log_action(&self, entry: &NewActionLogEntry) -> Result<ActionLogEntry>;
+  fn update_action_log_undone(&self, id: i32, new_value: bool) -> Result<()>;
+
+  // Runs `f` so that either everything it writes is kept or, if it
+  // fails, none of it is.
+  fn transaction(&self, f: &mut dyn FnMut() -> Result<()>) -> Result<()>;
+
+  // Nothing written from here on outlives this storage. For replaying
+  // recorded sessions without touching real data.
+  fn make_disposable(&self) -> Result<()>;
+}
+
+// `sqlite://<path>` opens an SQLite file. Anything else goes to
+// Postgres.
+pub fn open_storage(database_url: &str) -> Result<Rc<dyn Storage>> {
+  const SQLITE_PREFIX: &str = "sqlite://";
+
+  Ok(match database_url.strip_prefix(SQLITE_PREFIX) {
+    Some(path) => Rc::new(SqliteStorage::establish(path)?),
+    None => Rc::new(PgStorage::establish(database_url)?),
+  })
+}
@@ -0,0 +1,180 @@
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use error::{Error, Result};
+use models::{
+  ActionLogEntry, FilterPreset, NewActionLogEntry, Task, TaskDuration,
+  TaskEvent, TaskPriority, TaskRecurrence, TaskStatus,
+};
+use queries::{
+  action_log as action_log_queries, filter_preset as preset_queries,
+  setting as setting_queries, task as task_queries,
+  task_event as te_queries,
+};
+use storage::Storage;
+
+type DateTime = ::chrono::DateTime<::chrono::Utc>;
+
+// The queries in `queries` were written for Postgres; this just hands
+// them the connection.
+pub struct PgStorage {
+  connection: PgConnection,
+}
+
+impl PgStorage {
+  pub fn establish(database_url: &str) -> Result<PgStorage> {
+    let connection = PgConnection::establish(database_url).map_err(
+      |source| Error::Connect {
+        database_url: database_url.to_string(),
+        source,
+      },
+    )?;
+
+    Ok(PgStorage { connection })
+  }
+
+  pub fn connection(&self) -> &PgConnection {
+    &self.connection
+  }
+}
+
+impl Storage for PgStorage {
+  fn all_available_to_perform(&self) -> Result<Vec<Task>> {
+    task_queries::all_available_to_perform(&self.connection)
+  }
+
+  fn create_task(&self, title: &str) -> Result<Task> {
+    task_queries::create(title, &self.connection)
+  }
+
+  fn create_next_recurrence(
+    &self,
+    task: &Task,
+    created_at: DateTime,
+  ) -> Result<Task> {
+    task_queries::create_next_recurrence(task, created_at, &self.connection)
+  }
+
+  fn find_task(&self, id: i32) -> Result<Task> {
+    task_queries::find(id, &self.connection)
+  }
+
+  fn update_duration(&self, id: i32, new_value: TaskDuration) -> Result<()> {
+    task_queries::update_duration(id, new_value, &self.connection)
+  }
+
+  fn update_priority(&self, id: i32, new_value: TaskPriority) -> Result<()> {
+    task_queries::update_priority(id, new_value, &self.connection)
+  }
+
+  fn update_recurrence(
+    &self,
+    id: i32,
+    new_value: Option<TaskRecurrence>,
+  ) -> Result<()> {
+    task_queries::update_recurrence(id, new_value, &self.connection)
+  }
+
+  fn update_requires_internet(&self, id: i32, new_value: bool) -> Result<()> {
+    task_queries::update_requires_internet(id, new_value, &self.connection)
+  }
+
+  fn update_status(&self, id: i32, new_value: TaskStatus) -> Result<()> {
+    task_queries::update_status(id, new_value, &self.connection)
+  }
+
+  fn update_title(&self, id: i32, new_value: &str) -> Result<()> {
+    task_queries::update_title(id, new_value, &self.connection)
+  }
+
+  fn update_task_destroyed(&self, id: i32, new_value: bool) -> Result<()> {
+    task_queries::update_destroyed(id, new_value, &self.connection)
+  }
+
+  fn task_events(&self, task: &Task) -> Result<Vec<TaskEvent>> {
+    te_queries::task_events(task, &self.connection)
+  }
+
+  fn find_task_event(&self, id: i32) -> Result<TaskEvent> {
+    te_queries::find(id, &self.connection)
+  }
+
+  fn task_events_by_task(
+    &self,
+    tasks: &[Task],
+  ) -> Result<Vec<Vec<TaskEvent>>> {
+    te_queries::task_events_by_task(tasks, &self.connection)
+  }
+
+  fn done_since(&self, since: DateTime) -> Result<Vec<(TaskEvent, Task)>> {
+    te_queries::done_since(since, &self.connection)
+  }
+
+  fn record_task_effort(&self, task_id: i32) -> Result<TaskEvent> {
+    te_queries::record_task_effort(task_id, &self.connection)
+  }
+
+  fn request_delay(&self, task_id: i32) -> Result<TaskEvent> {
+    te_queries::request_delay(task_id, &self.connection)
+  }
+
+  fn request_task_age_reset(&self, task_id: i32) -> Result<TaskEvent> {
+    te_queries::request_task_age_reset(task_id, &self.connection)
+  }
+
+  fn record_status_change(
+    &self,
+    task_id: i32,
+    new_status: TaskStatus,
+  ) -> Result<TaskEvent> {
+    te_queries::record_status_change(task_id, new_status, &self.connection)
+  }
+
+  fn update_task_event_destroyed(
+    &self,
+    id: i32,
+    new_value: bool,
+  ) -> Result<()> {
+    te_queries::update_destroyed(id, new_value, &self.connection)
+  }
+
+  fn get_setting(&self, name: &str) -> Result<Option<String>> {
+    setting_queries::get(name, &self.connection)
+  }
+
+  fn set_setting(&self, name: &str, value: &str) -> Result<()> {
+    setting_queries::set(name, value, &self.connection)
+  }
+
+  fn filter_presets(&self) -> Result<Vec<FilterPreset>> {
+    preset_queries::all(&self.connection)
+  }
+
+  fn save_filter_preset(&self, name: &str, query: &str) -> Result<()> {
+    preset_queries::save(name, query, &self.connection)
+  }
+
+  fn delete_filter_preset(&self, name: &str) -> Result<()> {
+    preset_queries::delete(name, &self.connection)
+  }
+
+  fn recent_action_log(&self, limit: i64) -> Result<Vec<ActionLogEntry>> {
+    action_log_queries::recent(limit, &self.connection)
+  }
+
+  fn log_action(&self, entry: &NewActionLogEntry) -> Result<ActionLogEntry> {
+    action_log_queries::create(entry, &self.connection)
+  }
+
+  fn update_action_log_undone(&self, id: i32, new_value: bool) -> Result<()> {
+    action_log_queries::update_undone(id, new_value, &self.connection)
+  }
+
+  fn transaction(&self, f: &mut dyn FnMut() -> Result<()>) -> Result<()> {
+    self.connection.transaction(f)
+  }
+
+  // The transaction is never committed.
+  fn make_disposable(&self) -> Result<()> {
+    self.connection.begin_test_transaction().map_err(Error::from)
+  }
+}
@@ -0,0 +1,366 @@
+mod rows;
+mod schema;
+
+use self::rows::{
+  ActionLogRow, NewActionLogRow, NewFilterPreset, NewSetting, NewTask,
+  NewTaskEvent, TaskEventRow, TaskRow,
+};
+use self::schema::{action_log, filter_presets, settings, task_events, tasks};
+use chrono::{NaiveDateTime, Utc};
+use diesel;
+use diesel::prelude::*;
+use diesel::result::Error as QueryError;
+use diesel::sqlite::SqliteConnection;
+use error::{expect_one_row, Error, Result};
+use models::{
+  ActionLogEntry, FilterPreset, NewActionLogEntry, Task, TaskDuration,
+  TaskEvent, TaskEventType, TaskPriority, TaskRecurrence, TaskStatus,
+};
+use storage::Storage;
+
+type DateTime = ::chrono::DateTime<Utc>;
+
+// SQLite limits how many values may be bound in one statement.
+const MAX_IDS_PER_QUERY: usize = 500;
+
+macro_rules! define_update_attribute_fn {
+  ($table:ident, $fn_name:ident, $value_type:ty, $field_name:ident) => {
+    fn $fn_name(&self, id: i32, new_value: $value_type) -> Result<()> {
+      let num_updated = diesel::update($table::table.find(id))
+        .set($table::$field_name.eq(new_value))
+        .execute(&self.connection)?;
+
+      expect_one_row(stringify!($table), id, num_updated)
+    }
+  };
+}
+
+// A single file, so `tm` can run without a database server.
+pub struct SqliteStorage {
+  connection: SqliteConnection,
+}
+
+impl SqliteStorage {
+  pub fn establish(path: &str) -> Result<SqliteStorage> {
+    let connection =
+      SqliteConnection::establish(path).map_err(|source| Error::Connect {
+        database_url: path.to_string(),
+        source,
+      })?;
+
+    Ok(SqliteStorage { connection })
+  }
+
+  fn now() -> NaiveDateTime {
+    Utc::now().naive_utc()
+  }
+
+  // SQLite can't return inserted rows, so read back the newest one.
+  fn insert_task(&self, new_task: &NewTask) -> Result<Task> {
+    let row = self.connection.transaction::<_, QueryError, _>(|| {
+      diesel::insert_into(tasks::table)
+        .values(new_task)
+        .execute(&self.connection)?;
+      tasks::table
+        .order(tasks::id.desc())
+        .first::<TaskRow>(&self.connection)
+    })?;
+    Ok(Task::from(row))
+  }
+
+  fn insert_task_event(
+    &self,
+    task_id: i32,
+    event_type: TaskEventType,
+  ) -> Result<TaskEvent> {
+    let new_te = NewTaskEvent {
+      task_id,
+      created_at: SqliteStorage::now(),
+      event_type,
+    };
+
+    let row = self.connection.transaction::<_, QueryError, _>(|| {
+      diesel::insert_into(task_events::table)
+        .values(&new_te)
+        .execute(&self.connection)?;
+      task_events::table
+        .order(task_events::id.desc())
+        .first::<TaskEventRow>(&self.connection)
+    })?;
+    Ok(TaskEvent::from(row))
+  }
+}
+
+impl Storage for SqliteStorage {
+  fn all_available_to_perform(&self) -> Result<Vec<Task>> {
+    let rows = tasks::table
+      .filter(
+        tasks::status
+          .eq(TaskStatus::AvailableToPerform)
+          .and(tasks::destroyed.eq(false))
+          // Upcoming instances of recurring tasks are not yet due.
+          .and(tasks::created_at.le(SqliteStorage::now())),
+      ).order(tasks::id)
+      .load::<TaskRow>(&self.connection)?;
+    Ok(rows.into_iter().map(Task::from).collect())
+  }
+
+  fn create_task(&self, title: &str) -> Result<Task> {
+    self.insert_task(&NewTask {
+      title,
+      status: TaskStatus::AvailableToPerform,
+      created_at: SqliteStorage::now(),
+      requires_internet: false,
+      priority: TaskPriority::Low,
+      duration: TaskDuration::Short,
+      recurrence_type: None,
+      recurrence_value: 0,
+    })
+  }
+
+  fn create_next_recurrence(
+    &self,
+    task: &Task,
+    created_at: DateTime,
+  ) -> Result<Task> {
+    self.insert_task(&NewTask {
+      title: &task.title,
+      status: TaskStatus::AvailableToPerform,
+      created_at: created_at.naive_utc(),
+      requires_internet: task.requires_internet,
+      priority: task.priority,
+      duration: task.duration,
+      recurrence_type: task.recurrence_type,
+      recurrence_value: task.recurrence_value,
+    })
+  }
+
+  fn find_task(&self, id: i32) -> Result<Task> {
+    tasks::table
+      .find(id)
+      .get_result::<TaskRow>(&self.connection)
+      .optional()?
+      .map(Task::from)
+      .ok_or(Error::NoSuchRow { table: "tasks", id })
+  }
+
+  fn update_recurrence(
+    &self,
+    id: i32,
+    new_value: Option<TaskRecurrence>,
+  ) -> Result<()> {
+    let (new_type, new_value) = TaskRecurrence::to_columns(new_value);
+    let num_updated = diesel::update(tasks::table.find(id))
+      .set((
+        tasks::recurrence_type.eq(new_type),
+        tasks::recurrence_value.eq(new_value),
+      )).execute(&self.connection)?;
+
+    expect_one_row("tasks", id, num_updated)
+  }
+
+  define_update_attribute_fn!(tasks, update_duration, TaskDuration, duration);
+  define_update_attribute_fn!(tasks, update_priority, TaskPriority, priority);
+  define_update_attribute_fn!(
+    tasks,
+    update_requires_internet,
+    bool,
+    requires_internet
+  );
+  define_update_attribute_fn!(tasks, update_status, TaskStatus, status);
+  define_update_attribute_fn!(tasks, update_title, &str, title);
+  define_update_attribute_fn!(tasks, update_task_destroyed, bool, destroyed);
+
+  fn find_task_event(&self, id: i32) -> Result<TaskEvent> {
+    task_events::table
+      .find(id)
+      .get_result::<TaskEventRow>(&self.connection)
+      .optional()?
+      .map(TaskEvent::from)
+      .ok_or(Error::NoSuchRow {
+        table: "task_events",
+        id,
+      })
+  }
+
+  fn task_events(&self, task: &Task) -> Result<Vec<TaskEvent>> {
+    let rows = task_events::table
+      .filter(
+        task_events::task_id
+          .eq(task.id)
+          .and(task_events::destroyed.eq(false)),
+      ).order((task_events::created_at.desc(), task_events::id))
+      .load::<TaskEventRow>(&self.connection)?;
+    Ok(rows.into_iter().map(TaskEvent::from).collect())
+  }
+
+  fn task_events_by_task(
+    &self,
+    tasks: &[Task],
+  ) -> Result<Vec<Vec<TaskEvent>>> {
+    let task_ids: Vec<_> = tasks.iter().map(|task| task.id).collect();
+
+    let mut all_task_events = vec![];
+    for task_ids in task_ids.chunks(MAX_IDS_PER_QUERY) {
+      let rows = task_events::table
+        .filter(
+          task_events::task_id
+            .eq_any(task_ids)
+            .and(task_events::destroyed.eq(false)),
+        ).order((task_events::created_at.desc(), task_events::id))
+        .load::<TaskEventRow>(&self.connection)?;
+      all_task_events.extend(rows.into_iter().map(TaskEvent::from));
+    }
+
+    // Each chunk holds whole groups, so grouping keeps every group
+    // newest first.
+    Ok(all_task_events.grouped_by(tasks))
+  }
+
+  fn done_since(&self, since: DateTime) -> Result<Vec<(TaskEvent, Task)>> {
+    let rows = task_events::table
+      .inner_join(tasks::table)
+      .filter(
+        task_events::destroyed
+          .eq(false)
+          .and(tasks::destroyed.eq(false))
+          .and(task_events::created_at.ge(since.naive_utc()))
+          .and(
+            task_events::event_type
+              .eq(TaskEventType::TaskCompleted)
+              .or(task_events::event_type.eq(TaskEventType::TaskEffortRecorded)),
+          ),
+      ).order(task_events::created_at)
+      .load::<(TaskEventRow, TaskRow)>(&self.connection)?;
+    Ok(
+      rows
+        .into_iter()
+        .map(|(te_row, task_row)| (te_row.into(), task_row.into()))
+        .collect(),
+    )
+  }
+
+  fn record_task_effort(&self, task_id: i32) -> Result<TaskEvent> {
+    self.insert_task_event(task_id, TaskEventType::TaskEffortRecorded)
+  }
+
+  fn request_delay(&self, task_id: i32) -> Result<TaskEvent> {
+    self.insert_task_event(task_id, TaskEventType::DelayRequested)
+  }
+
+  fn request_task_age_reset(&self, task_id: i32) -> Result<TaskEvent> {
+    self.insert_task_event(task_id, TaskEventType::AgeResetRequested)
+  }
+
+  fn record_status_change(
+    &self,
+    task_id: i32,
+    new_status: TaskStatus,
+  ) -> Result<TaskEvent> {
+    self.insert_task_event(task_id, TaskEventType::from_status(new_status))
+  }
+
+  define_update_attribute_fn!(
+    task_events,
+    update_task_event_destroyed,
+    bool,
+    destroyed
+  );
+
+  fn get_setting(&self, name: &str) -> Result<Option<String>> {
+    settings::table
+      .find(name)
+      .select(settings::value)
+      .get_result(&self.connection)
+      .optional()
+      .map_err(Error::from)
+  }
+
+  fn set_setting(&self, name: &str, value: &str) -> Result<()> {
+    diesel::replace_into(settings::table)
+      .values(&NewSetting { name, value })
+      .execute(&self.connection)?;
+
+    Ok(())
+  }
+
+  fn filter_presets(&self) -> Result<Vec<FilterPreset>> {
+    filter_presets::table
+      .order(filter_presets::name)
+      .load(&self.connection)
+      .map_err(Error::from)
+  }
+
+  fn save_filter_preset(&self, name: &str, query: &str) -> Result<()> {
+    diesel::replace_into(filter_presets::table)
+      .values(&NewFilterPreset { name, query })
+      .execute(&self.connection)?;
+
+    Ok(())
+  }
+
+  fn delete_filter_preset(&self, name: &str) -> Result<()> {
+    diesel::delete(filter_presets::table.find(name))
+      .execute(&self.connection)?;
+
+    Ok(())
+  }
+
+  fn recent_action_log(&self, limit: i64) -> Result<Vec<ActionLogEntry>> {
+    let first_ids = action_log::table
+      .filter(action_log::batch_id.is_null())
+      .order(action_log::id.desc())
+      .limit(limit)
+      .select(action_log::id)
+      .load::<i32>(&self.connection)?;
+
+    let rows = action_log::table
+      .filter(
+        action_log::id
+          .eq_any(&first_ids)
+          .or(action_log::batch_id.eq_any(&first_ids)),
+      ).order(action_log::id.asc())
+      .load::<ActionLogRow>(&self.connection)?;
+    Ok(rows.into_iter().map(ActionLogEntry::from).collect())
+  }
+
+  fn log_action(&self, entry: &NewActionLogEntry) -> Result<ActionLogEntry> {
+    let new_row = NewActionLogRow {
+      kind: entry.kind,
+      task_id: entry.task_id,
+      old_value: entry.old_value.as_deref(),
+      new_value: entry.new_value.as_deref(),
+      task_event_id: entry.task_event_id,
+      spawned_task_id: entry.spawned_task_id,
+      created_at: SqliteStorage::now(),
+      parent_id: entry.parent_id,
+      batch_id: entry.batch_id,
+    };
+
+    let row = self.connection.transaction::<_, QueryError, _>(|| {
+      diesel::insert_into(action_log::table)
+        .values(&new_row)
+        .execute(&self.connection)?;
+      action_log::table
+        .order(action_log::id.desc())
+        .first::<ActionLogRow>(&self.connection)
+    })?;
+    Ok(ActionLogEntry::from(row))
+  }
+
+  define_update_attribute_fn!(
+    action_log,
+    update_action_log_undone,
+    bool,
+    undone
+  );
+
+  fn transaction(&self, f: &mut dyn FnMut() -> Result<()>) -> Result<()> {
+    self.connection.transaction(f)
+  }
+
+  // The transaction is never committed.
+  fn make_disposable(&self) -> Result<()> {
+    self.connection.begin_test_transaction().map_err(Error::from)
+  }
+}
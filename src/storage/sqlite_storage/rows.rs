@@ -0,0 +1,151 @@
+// Because Queryable triggers a Rust lang warning. They'll fix this in a
+// future release.
+#![allow(proc_macro_derive_resolution_fallback)]
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use models::{
+  ActionLogEntry, ActionLogKind, Task, TaskDuration, TaskEvent,
+  TaskEventType, TaskPriority, TaskRecurrenceType, TaskStatus,
+};
+use super::schema::{
+  action_log, filter_presets, settings, task_events, tasks,
+};
+
+// Diesel can't read a `DateTime<Utc>` out of SQLite, so rows are read
+// into these first and then converted to the models.
+
+#[derive(Queryable)]
+pub struct TaskRow {
+  id: i32,
+  title: String,
+  status: TaskStatus,
+  created_at: NaiveDateTime,
+  requires_internet: bool,
+  priority: TaskPriority,
+  duration: TaskDuration,
+  destroyed: bool,
+  recurrence_type: Option<TaskRecurrenceType>,
+  recurrence_value: i32,
+}
+
+impl From<TaskRow> for Task {
+  fn from(row: TaskRow) -> Task {
+    Task {
+      id: row.id,
+      title: row.title,
+      status: row.status,
+      created_at: DateTime::from_utc(row.created_at, Utc),
+      requires_internet: row.requires_internet,
+      priority: row.priority,
+      duration: row.duration,
+      destroyed: row.destroyed,
+      recurrence_type: row.recurrence_type,
+      recurrence_value: row.recurrence_value,
+    }
+  }
+}
+
+#[derive(Queryable)]
+pub struct TaskEventRow {
+  id: i32,
+  task_id: i32,
+  created_at: NaiveDateTime,
+  destroyed: bool,
+  event_type: TaskEventType,
+}
+
+impl From<TaskEventRow> for TaskEvent {
+  fn from(row: TaskEventRow) -> TaskEvent {
+    TaskEvent {
+      id: row.id,
+      task_id: row.task_id,
+      created_at: DateTime::from_utc(row.created_at, Utc),
+      destroyed: row.destroyed,
+      event_type: row.event_type,
+    }
+  }
+}
+
+#[derive(Queryable)]
+pub struct ActionLogRow {
+  id: i32,
+  kind: ActionLogKind,
+  task_id: i32,
+  old_value: Option<String>,
+  new_value: Option<String>,
+  task_event_id: Option<i32>,
+  spawned_task_id: Option<i32>,
+  created_at: NaiveDateTime,
+  undone: bool,
+  parent_id: Option<i32>,
+  batch_id: Option<i32>,
+}
+
+impl From<ActionLogRow> for ActionLogEntry {
+  fn from(row: ActionLogRow) -> ActionLogEntry {
+    ActionLogEntry {
+      id: row.id,
+      kind: row.kind,
+      task_id: row.task_id,
+      old_value: row.old_value,
+      new_value: row.new_value,
+      task_event_id: row.task_event_id,
+      spawned_task_id: row.spawned_task_id,
+      created_at: DateTime::from_utc(row.created_at, Utc),
+      undone: row.undone,
+      parent_id: row.parent_id,
+      batch_id: row.batch_id,
+    }
+  }
+}
+
+// Times are always given, rather than left to the column default, so
+// they have the same precision as in Postgres.
+#[derive(Insertable)]
+#[table_name = "tasks"]
+pub struct NewTask<'a> {
+  pub title: &'a str,
+  pub status: TaskStatus,
+  pub created_at: NaiveDateTime,
+  pub requires_internet: bool,
+  pub priority: TaskPriority,
+  pub duration: TaskDuration,
+  pub recurrence_type: Option<TaskRecurrenceType>,
+  pub recurrence_value: i32,
+}
+
+#[derive(Insertable)]
+#[table_name = "task_events"]
+pub struct NewTaskEvent {
+  pub task_id: i32,
+  pub created_at: NaiveDateTime,
+  pub event_type: TaskEventType,
+}
+
+#[derive(Insertable)]
+#[table_name = "settings"]
+pub struct NewSetting<'a> {
+  pub name: &'a str,
+  pub value: &'a str,
+}
+
+#[derive(Insertable)]
+#[table_name = "filter_presets"]
+pub struct NewFilterPreset<'a> {
+  pub name: &'a str,
+  pub query: &'a str,
+}
+
+#[derive(Insertable)]
+#[table_name = "action_log"]
+pub struct NewActionLogRow<'a> {
+  pub kind: ActionLogKind,
+  pub task_id: i32,
+  pub old_value: Option<&'a str>,
+  pub new_value: Option<&'a str>,
+  pub task_event_id: Option<i32>,
+  pub spawned_task_id: Option<i32>,
+  pub created_at: NaiveDateTime,
+  pub parent_id: Option<i32>,
+  pub batch_id: Option<i32>,
+}
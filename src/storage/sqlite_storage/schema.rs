@@ -0,0 +1,80 @@
+// Same tables as `schema`, but SQLite has no time zones and no enum
+// types: times are UTC and enums are text.
+#![allow(proc_macro_derive_resolution_fallback)]
+
+table! {
+    use diesel::sql_types::*;
+    use models::mappings::ActionLogKindMapping;
+
+    action_log (id) {
+        id -> Integer,
+        kind -> ActionLogKindMapping,
+        task_id -> Integer,
+        old_value -> Nullable<Text>,
+        new_value -> Nullable<Text>,
+        task_event_id -> Nullable<Integer>,
+        spawned_task_id -> Nullable<Integer>,
+        created_at -> Timestamp,
+        undone -> Bool,
+        parent_id -> Nullable<Integer>,
+        batch_id -> Nullable<Integer>,
+    }
+}
+
+table! {
+    filter_presets (name) {
+        name -> Text,
+        query -> Text,
+    }
+}
+
+table! {
+    settings (name) {
+        name -> Text,
+        value -> Text,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use models::mappings::TaskEventTypeMapping;
+
+    task_events (id) {
+        id -> Integer,
+        task_id -> Integer,
+        created_at -> Timestamp,
+        destroyed -> Bool,
+        event_type -> TaskEventTypeMapping,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use models::mappings::TaskDurationMapping;
+    use models::mappings::TaskPriorityMapping;
+    use models::mappings::TaskRecurrenceTypeMapping;
+    use models::mappings::TaskStatusMapping;
+
+    tasks (id) {
+        id -> Integer,
+        title -> Text,
+        status -> TaskStatusMapping,
+        created_at -> Timestamp,
+        requires_internet -> Bool,
+        priority -> TaskPriorityMapping,
+        duration -> TaskDurationMapping,
+        destroyed -> Bool,
+        recurrence_type -> Nullable<TaskRecurrenceTypeMapping>,
+        recurrence_value -> Integer,
+    }
+}
+
+joinable!(task_events -> tasks (task_id));
+
+allow_tables_to_appear_in_same_query!(
+    action_log,
+    filter_presets,
+    settings,
+    task_events,
+    tasks,
+);
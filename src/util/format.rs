@@ -0,0 +1,30 @@
+use chrono::Duration;
+
+pub fn format_task_age(age: Duration) -> String {
+  let weeks = age.num_weeks();
+  let days = age.num_days() - 7 * age.num_weeks();
+  let hours = age.num_hours() - 24 * age.num_days();
+  let mins = age.num_minutes() - 60 * age.num_hours();
+
+  if weeks > 0 {
+    format!("{}w {}d", weeks, days)
+  } else if days > 0 {
+    format!("{}d {}h", days, hours)
+  } else if hours > 0 {
+    format!("{}h {}m", hours, mins)
+  } else if mins > 0 {
+    format!("{}m", mins)
+  } else {
+    String::from("now")
+  }
+}
+
+// Upper-cases the first letter, e.g. to start a message with an action's
+// description.
+pub fn capitalize(text: &str) -> String {
+  let mut chars = text.chars();
+  match chars.next() {
+    None => String::new(),
+    Some(first) => first.to_uppercase().chain(chars).collect(),
+  }
+}
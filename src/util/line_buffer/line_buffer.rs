@@ -1,7 +1,7 @@
-use super::{LineBufferState, TerminalLine};
+use super::{LineBufferState, Region, TerminalLine};
 use std::cell::RefCell;
 use std::rc::Rc;
-use util::ui::UserInterface;
+use util::ui::{ColorPair, UserInterface};
 
 #[derive(Clone, Copy)]
 struct Margins {
@@ -11,13 +11,15 @@ struct Margins {
 
 pub struct LineBuffer {
   state: RefCell<LineBufferState>,
+  region: Region,
   ui: Rc<UserInterface>,
 }
 
 impl LineBuffer {
-  pub fn new(ui: &Rc<UserInterface>) -> LineBuffer {
+  pub fn new(ui: &Rc<UserInterface>, region: Region) -> LineBuffer {
     LineBuffer {
       state: RefCell::new(LineBufferState::default()),
+      region,
       ui: Rc::clone(ui),
     }
   }
@@ -31,16 +33,27 @@ impl LineBuffer {
     let num_lines = state.num_lines();
     let max_line_len = state.max_line_len();
 
-    let window = &self.ui.window;
-    let window_max_x = window.get_max_x() as usize;
-    let window_max_y = window.get_max_y() as usize;
+    let (window_max_y, window_max_x) = self.ui.terminal.size();
+    let region_height = self.region.height(window_max_y);
 
     Margins {
-      left: (window_max_x - max_line_len) / 2,
-      top: (window_max_y - num_lines) / 2,
+      left: window_max_x.saturating_sub(max_line_len) / 2,
+      top: self.region.top(window_max_y)
+        + region_height.saturating_sub(num_lines) / 2,
     }
   }
 
+  // How many rows the region has on the screen right now.
+  pub fn height(&self) -> usize {
+    let (window_max_y, _) = self.ui.terminal.size();
+    self.region.height(window_max_y)
+  }
+
+  // Forces every line to be redrawn, e.g. after the screen was cleared.
+  pub fn mark_all_dirty(&self) {
+    self.state.borrow_mut().mark_all_dirty();
+  }
+
   fn num_lines(&self) -> usize {
     self.state.borrow().num_lines()
   }
@@ -64,8 +77,8 @@ impl LineBuffer {
 
     self
       .ui
-      .window
-      .mv((margins.top + num_lines) as i32, margins.left as i32);
+      .terminal
+      .move_cursor(margins.top + num_lines, margins.left);
   }
 
   fn redraw_line(
@@ -74,15 +87,12 @@ impl LineBuffer {
     margins: Margins,
     line: &TerminalLine,
   ) {
-    let window = &self.ui.window;
-
-    window.mv((margins.top + idx) as i32, 0);
-    window.clrtoeol();
-
-    window.mv((margins.top + idx) as i32, margins.left as i32);
-    window.attron(line.color.to_attr());
-    window.printw(&line.text);
-    window.attroff(line.color.to_attr());
+    self.ui.terminal.draw_line(
+      margins.top + idx,
+      margins.left,
+      &line.text,
+      line.color,
+    );
   }
 
   pub fn replace_line(&self, idx: usize, new_line: TerminalLine) {
@@ -92,4 +102,24 @@ impl LineBuffer {
   pub fn truncate(&self, new_len: usize) {
     self.state.borrow_mut().truncate(new_len);
   }
+
+  // Puts `message` below the first `num_lines` lines, leaving a blank
+  // row in between, and blanks whatever came after.
+  pub fn display_message(&self, num_lines: usize, message: Option<&str>) {
+    match message {
+      None => self.truncate(num_lines),
+      Some(message) => {
+        let text = format!(" {} \n", message);
+        self.clear_line(num_lines);
+        self.replace_line(
+          num_lines + 1,
+          TerminalLine {
+            text,
+            color: ColorPair::Bold,
+          },
+        );
+        self.truncate(num_lines + 2);
+      }
+    }
+  }
 }
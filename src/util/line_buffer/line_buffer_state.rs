@@ -77,7 +77,7 @@ impl LineBufferState {
   }
 
   // Marks all lines as dirty, thus triggering a full redraw.
-  fn mark_all_dirty(&mut self) {
+  pub fn mark_all_dirty(&mut self) {
     for line_state in &mut self.line_states {
       line_state.mark_dirty();
     }
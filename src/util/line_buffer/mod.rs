@@ -1,10 +1,12 @@
 mod line_buffer;
 mod line_buffer_state;
+mod region;
 mod terminal_line;
 mod terminal_line_state;
 
 pub use self::line_buffer::LineBuffer;
 pub(self) use self::line_buffer_state::LineBufferState;
+pub use self::region::Region;
 pub use self::terminal_line::TerminalLine;
 pub(self) use self::terminal_line_state::{
   LineChange, TerminalLineState,
@@ -0,0 +1,64 @@
+// A screen row, counted from either edge so that regions follow the
+// terminal when it is resized.
+#[derive(Clone, Copy)]
+enum Row {
+  FromTop(usize),
+  FromBottom(usize),
+}
+
+impl Row {
+  fn index(self, window_max_y: usize) -> usize {
+    match self {
+      Row::FromTop(row) => ::std::cmp::min(row, window_max_y),
+      Row::FromBottom(row) => window_max_y.saturating_sub(row),
+    }
+  }
+}
+
+// The rows of the screen that a `LineBuffer` draws into. Lines are
+// centered within the region.
+#[derive(Clone, Copy)]
+pub struct Region {
+  top: Row,
+  // Exclusive.
+  bottom: Row,
+}
+
+impl Region {
+  pub fn rows(top: usize, height: usize) -> Region {
+    Region {
+      top: Row::FromTop(top),
+      bottom: Row::FromTop(top + height),
+    }
+  }
+
+  pub fn rest_of_screen(top: usize) -> Region {
+    Region::above_bottom_rows(top, 0)
+  }
+
+  // Like `rest_of_screen`, but stops short of the last `num_rows` rows.
+  pub fn above_bottom_rows(top: usize, num_rows: usize) -> Region {
+    Region {
+      top: Row::FromTop(top),
+      bottom: Row::FromBottom(num_rows),
+    }
+  }
+
+  pub fn bottom_rows(num_rows: usize) -> Region {
+    Region {
+      top: Row::FromBottom(num_rows),
+      bottom: Row::FromBottom(0),
+    }
+  }
+
+  pub fn top(self, window_max_y: usize) -> usize {
+    self.top.index(window_max_y)
+  }
+
+  pub fn height(self, window_max_y: usize) -> usize {
+    self
+      .bottom
+      .index(window_max_y)
+      .saturating_sub(self.top(window_max_y))
+  }
+}
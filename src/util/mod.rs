@@ -1,6 +1,7 @@
-mod db_connection;
+mod format;
 pub mod line_buffer;
+pub mod terminal;
 pub mod ui;
 
-pub use self::db_connection::get_db_connection;
+pub use self::format::{capitalize, format_task_age};
 pub use self::ui::UserInterface;
@@ -0,0 +1,161 @@
+use std::cell::RefCell;
+use util::terminal::{InputScript, ScriptStep, ScriptedInput, Terminal};
+use util::ui::ColorPair;
+
+#[derive(Clone, Copy)]
+struct ScreenCell {
+  ch: char,
+  color: ColorPair,
+}
+
+const BLANK: ScreenCell = ScreenCell {
+  ch: ' ',
+  color: ColorPair::Default,
+};
+
+// A pretend terminal for tests. Keys and prompt answers are queued up
+// front; whatever is drawn lands in a grid that can be read back. A
+// script that doesn't match what the app asks for panics.
+pub struct HeadlessTerminal {
+  script: InputScript,
+  screen: RefCell<Vec<Vec<ScreenCell>>>,
+  prompts: RefCell<Vec<String>>,
+  last_history: RefCell<Vec<String>>,
+}
+
+impl HeadlessTerminal {
+  pub fn new(rows: usize, columns: usize) -> HeadlessTerminal {
+    HeadlessTerminal {
+      script: InputScript::default(),
+      screen: RefCell::new(vec![vec![BLANK; columns]; rows]),
+      prompts: RefCell::new(vec![]),
+      last_history: RefCell::new(vec![]),
+    }
+  }
+
+  pub fn push_input(&self, input: ScriptedInput) {
+    self.script.push(input);
+  }
+
+  // Each character is one key press.
+  pub fn type_keys(&self, keys: &str) {
+    for key in keys.chars() {
+      self.push_input(ScriptedInput::Key(key));
+    }
+  }
+
+  // The answer to the next prompt.
+  pub fn type_line(&self, line: &str) {
+    self.push_input(ScriptedInput::Line(line.to_string()));
+  }
+
+  pub fn has_pending_input(&self) -> bool {
+    !self.script.is_empty()
+  }
+
+  // Every prompt shown so far, oldest first.
+  pub fn prompts(&self) -> Vec<String> {
+    self.prompts.borrow().clone()
+  }
+
+  // What up and down would have gone through at the latest prompt.
+  pub fn last_history(&self) -> Vec<String> {
+    self.last_history.borrow().clone()
+  }
+
+  // The screen as text, one string per row, without trailing blanks.
+  pub fn lines(&self) -> Vec<String> {
+    self
+      .screen
+      .borrow()
+      .iter()
+      .map(|row| {
+        let text: String = row.iter().map(|cell| cell.ch).collect();
+        text.trim_end().to_string()
+      }).collect()
+  }
+
+  // The first row whose text contains `needle`.
+  pub fn find_row(&self, needle: &str) -> Option<usize> {
+    self.lines().iter().position(|line| line.contains(needle))
+  }
+
+  // The color the text of row `y` was drawn in.
+  pub fn row_color(&self, y: usize) -> ColorPair {
+    let screen = self.screen.borrow();
+    screen[y]
+      .iter()
+      .find(|cell| cell.ch != ' ')
+      .map_or(ColorPair::Default, |cell| cell.color)
+  }
+}
+
+impl Terminal for HeadlessTerminal {
+  // `None` for a scripted failed read, and once the script runs out.
+  fn getch(&self) -> Option<char> {
+    match self.script.next_key() {
+      ScriptStep::Next(key) => key,
+      ScriptStep::RunOut => None,
+      ScriptStep::Diverged(input) => {
+        panic!("Script has {:?} where a key was expected", input)
+      }
+    }
+  }
+
+  fn read_line(&self, prompt: &str) -> Option<String> {
+    self.read_line_with_history(prompt, &[])
+  }
+
+  fn read_line_with_history(
+    &self,
+    prompt: &str,
+    history: &[String],
+  ) -> Option<String> {
+    self.prompts.borrow_mut().push(prompt.to_string());
+    *self.last_history.borrow_mut() = history.to_vec();
+
+    match self.script.next_line() {
+      ScriptStep::Next(line) => line,
+      ScriptStep::RunOut => {
+        panic!("Script ran out when {:?} was asked", prompt)
+      }
+      ScriptStep::Diverged(input) => {
+        panic!("Script has {:?} where {:?} was asked", input, prompt)
+      }
+    }
+  }
+
+  fn size(&self) -> (usize, usize) {
+    let screen = self.screen.borrow();
+    (screen.len(), screen.first().map_or(0, |row| row.len()))
+  }
+
+  fn clear(&self) {
+    for row in self.screen.borrow_mut().iter_mut() {
+      for cell in row.iter_mut() {
+        *cell = BLANK;
+      }
+    }
+  }
+
+  fn draw_line(&self, y: usize, x: usize, text: &str, color: ColorPair) {
+    let mut screen = self.screen.borrow_mut();
+    let row = match screen.get_mut(y) {
+      // Off the bottom of the screen, which curses ignores too.
+      None => return,
+      Some(row) => row,
+    };
+
+    for cell in row.iter_mut() {
+      *cell = BLANK;
+    }
+    // Lines end in a newline; anything past the right edge is cut off.
+    let chars = text.chars().take_while(|ch| *ch != '\n');
+    for (cell, ch) in row.iter_mut().skip(x).zip(chars) {
+      *cell = ScreenCell { ch, color };
+    }
+  }
+
+  // There is no cursor to show.
+  fn move_cursor(&self, _y: usize, _x: usize) {}
+}
@@ -0,0 +1,125 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+use util::terminal::ScriptedInput;
+
+// One input per line: milliseconds since recording started, the kind
+// of input, and for keys and lines the escaped text.
+//
+//     1520	key	n
+//     3981	line	Buy distilled water
+//     5002	interrupt
+//     6310	nokey
+
+#[derive(Debug)]
+pub enum InputLogError {
+  Io(io::Error),
+  BadEntry { line_number: usize, line: String },
+}
+
+impl fmt::Display for InputLogError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      InputLogError::Io(err) => write!(f, "{}", err),
+      InputLogError::BadEntry { line_number, line } => {
+        write!(f, "Can't read line {}: {:?}", line_number, line)
+      }
+    }
+  }
+}
+
+impl From<io::Error> for InputLogError {
+  fn from(err: io::Error) -> InputLogError {
+    InputLogError::Io(err)
+  }
+}
+
+pub fn format_input_log_entry(
+  elapsed: Duration,
+  input: &ScriptedInput,
+) -> String {
+  let ms = elapsed.as_secs() * 1000 + u64::from(elapsed.subsec_millis());
+
+  match input {
+    ScriptedInput::Key(key) => format!("{}\tkey\t{}\n", ms, key.escape_default()),
+    ScriptedInput::Line(line) => {
+      format!("{}\tline\t{}\n", ms, line.escape_default())
+    }
+    ScriptedInput::Interrupt => format!("{}\tinterrupt\n", ms),
+    ScriptedInput::NoKey => format!("{}\tnokey\n", ms),
+  }
+}
+
+// Undoes `escape_default`.
+fn unescape(text: &str) -> Option<String> {
+  let mut result = String::new();
+  let mut chars = text.chars();
+
+  while let Some(ch) = chars.next() {
+    if ch != '\\' {
+      result.push(ch);
+      continue;
+    }
+
+    let escaped = match chars.next()? {
+      't' => '\t',
+      'r' => '\r',
+      'n' => '\n',
+      'u' => {
+        // \u{XXXX}
+        if chars.next()? != '{' {
+          return None;
+        }
+        let hex: String = chars.by_ref().take_while(|ch| *ch != '}').collect();
+        ::std::char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?
+      }
+      ch => ch,
+    };
+    result.push(escaped);
+  }
+
+  Some(result)
+}
+
+fn parse_entry(line: &str) -> Option<ScriptedInput> {
+  let mut fields = line.splitn(3, '\t');
+  fields.next()?.parse::<u64>().ok()?;
+
+  match (fields.next()?, fields.next()) {
+    ("key", Some(text)) => {
+      let text = unescape(text)?;
+      let mut chars = text.chars();
+      match (chars.next(), chars.next()) {
+        (Some(key), None) => Some(ScriptedInput::Key(key)),
+        _ => None,
+      }
+    }
+    ("line", Some(text)) => unescape(text).map(ScriptedInput::Line),
+    ("interrupt", None) => Some(ScriptedInput::Interrupt),
+    ("nokey", None) => Some(ScriptedInput::NoKey),
+    _ => None,
+  }
+}
+
+pub fn parse_input_log(
+  text: &str,
+) -> Result<Vec<ScriptedInput>, InputLogError> {
+  text
+    .lines()
+    .enumerate()
+    .filter(|(_, line)| !line.is_empty())
+    .map(|(idx, line)| {
+      parse_entry(line).ok_or_else(|| InputLogError::BadEntry {
+        line_number: idx + 1,
+        line: line.to_string(),
+      })
+    }).collect()
+}
+
+pub fn read_input_log(
+  path: &Path,
+) -> Result<Vec<ScriptedInput>, InputLogError> {
+  parse_input_log(&fs::read_to_string(path)?)
+}
@@ -0,0 +1,67 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ScriptedInput {
+  Key(char),
+  Line(String),
+  // Ctrl-C at a prompt.
+  Interrupt,
+  // A key read that failed, which `getch` gives as `None`.
+  NoKey,
+}
+
+// What the script gives when a key or a prompt answer is asked for.
+pub enum ScriptStep<T> {
+  Next(T),
+  RunOut,
+  // A key was asked for when the script has a line next, or the other
+  // way round. The script no longer matches what the app is doing.
+  Diverged(ScriptedInput),
+}
+
+// Keys and prompt answers, handed out in order.
+#[derive(Default)]
+pub struct InputScript {
+  inputs: RefCell<VecDeque<ScriptedInput>>,
+}
+
+impl InputScript {
+  pub fn new(inputs: Vec<ScriptedInput>) -> InputScript {
+    InputScript {
+      inputs: RefCell::new(inputs.into_iter().collect()),
+    }
+  }
+
+  pub fn push(&self, input: ScriptedInput) {
+    self.inputs.borrow_mut().push_back(input);
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.inputs.borrow().is_empty()
+  }
+
+  pub fn clear(&self) {
+    self.inputs.borrow_mut().clear();
+  }
+
+  // `None` is a failed read.
+  pub fn next_key(&self) -> ScriptStep<Option<char>> {
+    match self.inputs.borrow_mut().pop_front() {
+      None => ScriptStep::RunOut,
+      Some(ScriptedInput::Key(key)) => ScriptStep::Next(Some(key)),
+      Some(ScriptedInput::NoKey) => ScriptStep::Next(None),
+      Some(input) => ScriptStep::Diverged(input),
+    }
+  }
+
+  // `None` is a Ctrl-C.
+  pub fn next_line(&self) -> ScriptStep<Option<String>> {
+    match self.inputs.borrow_mut().pop_front() {
+      None => ScriptStep::RunOut,
+      Some(ScriptedInput::Line(line)) => ScriptStep::Next(Some(line)),
+      Some(ScriptedInput::Interrupt) => ScriptStep::Next(None),
+      Some(input) => ScriptStep::Diverged(input),
+    }
+  }
+}
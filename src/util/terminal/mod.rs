@@ -0,0 +1,39 @@
+mod headless_terminal;
+mod input_log;
+mod input_script;
+mod pancurses_terminal;
+mod recording_terminal;
+mod replay_terminal;
+
+pub use self::headless_terminal::HeadlessTerminal;
+pub use self::input_log::{
+  format_input_log_entry, parse_input_log, read_input_log, InputLogError,
+};
+pub use self::input_script::{InputScript, ScriptStep, ScriptedInput};
+pub use self::pancurses_terminal::PancursesTerminal;
+pub use self::recording_terminal::RecordingTerminal;
+pub use self::replay_terminal::ReplayTerminal;
+
+use util::ui::ColorPair;
+
+// Everything the app does to the screen and keyboard. The pancurses
+// version is the real thing; the headless one lets tests type keys and
+// read back the screen. Recording and replaying wrap another terminal.
+pub trait Terminal {
+  fn getch(&self) -> Option<char>;
+  // `None` means the user hit Ctrl-C.
+  fn read_line(&self, prompt: &str) -> Option<String>;
+  // Like `read_line`, but up and down go through `history`, oldest
+  // first.
+  fn read_line_with_history(
+    &self,
+    prompt: &str,
+    history: &[String],
+  ) -> Option<String>;
+  // Rows, then columns.
+  fn size(&self) -> (usize, usize);
+  fn clear(&self);
+  // Blanks row `y`, then writes `text` from column `x` onwards.
+  fn draw_line(&self, y: usize, x: usize, text: &str, color: ColorPair);
+  fn move_cursor(&self, y: usize, x: usize);
+}
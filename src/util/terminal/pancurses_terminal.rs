@@ -0,0 +1,145 @@
+use config::ColorsConfig;
+use pancurses;
+use rustyline::{error::ReadlineError, Editor};
+use std::io::{stdout, Write};
+use util::terminal::Terminal;
+use util::ui::ColorPair;
+
+pub struct PancursesTerminal {
+  window: pancurses::Window,
+}
+
+impl PancursesTerminal {
+  pub fn initscr(colors: &ColorsConfig) -> PancursesTerminal {
+    // Important! You must initscr before you can do any of the start
+    // color stuff. Otherwise you get a wonderful segfault...
+    let window = pancurses::initscr();
+
+    pancurses::start_color();
+    pancurses::use_default_colors();
+    pancurses::init_pair(
+      ColorPair::Default as i16,
+      colors.default.foreground,
+      colors.default.background,
+    );
+    pancurses::init_pair(
+      ColorPair::Highlight as i16,
+      colors.highlight.foreground,
+      colors.highlight.background,
+    );
+    pancurses::noecho();
+    // Keypad mode handles escape sequences. I think how it works is
+    // waits a small amount of time to get all characters that occur
+    // after the escape character.
+    window.keypad(true);
+
+    PancursesTerminal { window }
+  }
+}
+
+impl Terminal for PancursesTerminal {
+  fn getch(&self) -> Option<char> {
+    let result = self.window.getch();
+
+    match result {
+      None => {
+        // wgetch had some problem. I believe one such problem happens
+        // when the window size changes.
+        //
+        // Could try to handle SIGWINCH. Should redraw the display. But
+        // right now my program has fixed with output regardless of
+        // display size.
+
+        None
+      }
+      // A character
+      Some(pancurses::Input::Character(ch)) => Some(ch),
+      // As the DEL most terminals send for it, so there is only one
+      // backspace to look for.
+      Some(pancurses::Input::KeyBackspace) => Some('\u{7f}'),
+      // Likewise as Ctrl-F and Ctrl-B, which page too.
+      Some(pancurses::Input::KeyNPage) => Some('\u{6}'),
+      Some(pancurses::Input::KeyPPage) => Some('\u{2}'),
+      // Not a character
+      Some(_) => None,
+    }
+  }
+
+  fn read_line(&self, prompt: &str) -> Option<String> {
+    self.read_line_with_history(prompt, &[])
+  }
+
+  fn read_line_with_history(
+    &self,
+    prompt: &str,
+    history: &[String],
+  ) -> Option<String> {
+    pancurses::echo();
+    let result = loop {
+      let mut editor = Editor::<()>::new();
+      for line in history {
+        editor.add_history_entry(line.as_str());
+      }
+      match editor.readline(prompt) {
+        Ok(line) => {
+          break Some(line);
+        }
+
+        // Corresponds to Ctrl-C
+        Err(ReadlineError::Interrupted) => {
+          break None;
+        }
+        Err(_) => continue,
+      }
+    };
+    pancurses::noecho();
+
+    // Super hacky. readline moves cursor without pancurses knowing.
+    {
+      // y, x is the position to restore. Since pancurses doesn't know,
+      // the "cur_yx" is actually where we were before readline.
+      let (y, x) = self.window.get_cur_yx();
+      let mut out = stdout();
+      // Scroll back, then clear the line.
+      out.write_all(b"\x1b[F\x1b[2K").unwrap();
+      // Then reposition. Is this freaking one based???
+      out.write_all(format!("\x1b[{};{}H", y + 1, x + 1).as_bytes()).unwrap();
+      out.flush().unwrap();
+    }
+
+    result
+  }
+
+  fn size(&self) -> (usize, usize) {
+    (
+      self.window.get_max_y() as usize,
+      self.window.get_max_x() as usize,
+    )
+  }
+
+  fn clear(&self) {
+    self.window.clear();
+  }
+
+  fn draw_line(&self, y: usize, x: usize, text: &str, color: ColorPair) {
+    let window = &self.window;
+
+    window.mv(y as i32, 0);
+    window.clrtoeol();
+
+    window.mv(y as i32, x as i32);
+    window.attron(color.to_attr());
+    window.printw(text);
+    window.attroff(color.to_attr());
+  }
+
+  fn move_cursor(&self, y: usize, x: usize) {
+    self.window.mv(y as i32, x as i32);
+  }
+}
+
+impl Drop for PancursesTerminal {
+  fn drop(&mut self) {
+    pancurses::endwin();
+  }
+}
@@ -0,0 +1,83 @@
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::Write;
+use std::rc::Rc;
+use std::time::Instant;
+use util::terminal::{format_input_log_entry, ScriptedInput, Terminal};
+use util::ui::ColorPair;
+
+// Passes everything through to another terminal, and writes each key
+// and prompt answer to a log that `ReplayTerminal` can play back.
+// Failed key reads are logged too, since some views act on them.
+pub struct RecordingTerminal {
+  terminal: Rc<dyn Terminal>,
+  log: RefCell<File>,
+  started_at: Instant,
+}
+
+impl RecordingTerminal {
+  pub fn new(terminal: Rc<dyn Terminal>, log: File) -> RecordingTerminal {
+    RecordingTerminal {
+      terminal,
+      log: RefCell::new(log),
+      started_at: Instant::now(),
+    }
+  }
+
+  // Written straight away, so the log is complete even if we panic on
+  // the very next key.
+  fn record(&self, input: &ScriptedInput) {
+    let entry = format_input_log_entry(self.started_at.elapsed(), input);
+    let mut log = self.log.borrow_mut();
+    log
+      .write_all(entry.as_bytes())
+      .and_then(|_| log.flush())
+      .expect("Error writing input log");
+  }
+}
+
+impl Terminal for RecordingTerminal {
+  fn getch(&self) -> Option<char> {
+    let key = self.terminal.getch();
+    self.record(&match key {
+      None => ScriptedInput::NoKey,
+      Some(key) => ScriptedInput::Key(key),
+    });
+    key
+  }
+
+  fn read_line(&self, prompt: &str) -> Option<String> {
+    self.read_line_with_history(prompt, &[])
+  }
+
+  // Only the answer is recorded. Replaying gives the same answer
+  // without needing the history.
+  fn read_line_with_history(
+    &self,
+    prompt: &str,
+    history: &[String],
+  ) -> Option<String> {
+    let line = self.terminal.read_line_with_history(prompt, history);
+    self.record(&match &line {
+      None => ScriptedInput::Interrupt,
+      Some(line) => ScriptedInput::Line(line.clone()),
+    });
+    line
+  }
+
+  fn size(&self) -> (usize, usize) {
+    self.terminal.size()
+  }
+
+  fn clear(&self) {
+    self.terminal.clear();
+  }
+
+  fn draw_line(&self, y: usize, x: usize, text: &str, color: ColorPair) {
+    self.terminal.draw_line(y, x, text, color);
+  }
+
+  fn move_cursor(&self, y: usize, x: usize) {
+    self.terminal.move_cursor(y, x);
+  }
+}
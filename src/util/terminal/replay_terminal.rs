@@ -0,0 +1,75 @@
+use std::rc::Rc;
+use util::terminal::{InputScript, ScriptStep, ScriptedInput, Terminal};
+use util::ui::ColorPair;
+
+// Draws to another terminal, but takes its keys and prompt answers from
+// a recorded log. Once the log runs out the keyboard takes over, so the
+// user can look around wherever the replay left off. The same happens
+// if the log stops matching what the app asks for, e.g. because the
+// database holds different tasks than when it was recorded.
+pub struct ReplayTerminal {
+  terminal: Rc<dyn Terminal>,
+  script: InputScript,
+}
+
+impl ReplayTerminal {
+  pub fn new(
+    terminal: Rc<dyn Terminal>,
+    inputs: Vec<ScriptedInput>,
+  ) -> ReplayTerminal {
+    ReplayTerminal {
+      terminal,
+      script: InputScript::new(inputs),
+    }
+  }
+}
+
+impl Terminal for ReplayTerminal {
+  fn getch(&self) -> Option<char> {
+    match self.script.next_key() {
+      ScriptStep::Next(key) => key,
+      ScriptStep::RunOut => self.terminal.getch(),
+      ScriptStep::Diverged(_) => {
+        self.script.clear();
+        self.terminal.getch()
+      }
+    }
+  }
+
+  fn read_line(&self, prompt: &str) -> Option<String> {
+    self.read_line_with_history(prompt, &[])
+  }
+
+  fn read_line_with_history(
+    &self,
+    prompt: &str,
+    history: &[String],
+  ) -> Option<String> {
+    match self.script.next_line() {
+      ScriptStep::Next(line) => line,
+      ScriptStep::RunOut => {
+        self.terminal.read_line_with_history(prompt, history)
+      }
+      ScriptStep::Diverged(_) => {
+        self.script.clear();
+        self.terminal.read_line_with_history(prompt, history)
+      }
+    }
+  }
+
+  fn size(&self) -> (usize, usize) {
+    self.terminal.size()
+  }
+
+  fn clear(&self) {
+    self.terminal.clear();
+  }
+
+  fn draw_line(&self, y: usize, x: usize, text: &str, color: ColorPair) {
+    self.terminal.draw_line(y, x, text, color);
+  }
+
+  fn move_cursor(&self, y: usize, x: usize) {
+    self.terminal.move_cursor(y, x);
+  }
+}
@@ -1,32 +1,70 @@
-use actions::ForwardAction;
-use commands::ActiveTasksViewCommand;
+use actions::{
+  read_filter_preset, ActiveTasksViewAction, ForwardAction,
+  ReversableAction, TaskAction,
+};
+use commands::{
+  ActiveTasksViewCommand, Keymap, KeymapMatch, OpenViewCommand,
+};
 use components::{
-  DataSource, Filterer, TaskResultsWindow, TasksScroller, UndoBuffer,
+  DataSource, Filterer, ScoringStrategy, Scroller, TaskResultsWindow,
+  TasksScroller, UndoBuffer,
 };
-use diesel::pg::PgConnection;
+use config::Config;
+use error::Result;
+use models::{ActionLogEntry, Direction};
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
-use util::{get_db_connection, UserInterface};
+use storage::Storage;
+use util::UserInterface;
+use views::{
+  content_region, status_region, HelpView, TaskDetailView,
+  UndoHistoryView, View, ViewResult,
+};
+
+const SCORING_STRATEGY_SETTING: &str = "scoring_strategy";
+// How many logged actions are loaded back into the undo buffer.
+const ACTION_LOG_LIMIT: i64 = 100;
+// Keys the title search reacts to.
+const ENTER: char = '\n';
+const ESCAPE: char = '\u{1b}';
+const BACKSPACE: char = '\u{7f}';
 
 pub struct ActiveTasksView {
-  pub connection: Rc<PgConnection>,
+  pub storage: Rc<dyn Storage>,
   pub ui: Rc<UserInterface>,
   pub task_results_window: Rc<TaskResultsWindow>,
   pub scroller: Rc<TasksScroller>,
   pub filterer: Rc<Filterer>,
   pub data_source: Rc<DataSource>,
   pub undo_buffer: Rc<UndoBuffer>,
+  pub keymap: Keymap<ActiveTasksViewCommand>,
+  // Keys typed so far of a multi-key sequence.
+  pending_keys: RefCell<Vec<char>>,
+  // Whether the next command was asked to go to every task listed.
+  applying_to_all: Cell<bool>,
+  // Lines typed at the `:` prompt this session, oldest first.
+  command_history: RefCell<Vec<String>>,
+  // The latest title search, for `n` and `N`.
+  search: RefCell<Option<String>>,
 }
 
 impl ActiveTasksView {
-  pub fn new(ui: &Rc<UserInterface>) -> Rc<ActiveTasksView> {
-    // We need our own copy of the root window.
+  pub fn new(
+    ui: &Rc<UserInterface>,
+    storage: &Rc<dyn Storage>,
+    config: &Config,
+    keymap: Keymap<ActiveTasksViewCommand>,
+  ) -> Rc<ActiveTasksView> {
+    // We need our own copy of the root window and storage.
     let ui = Rc::clone(ui);
-
-    // Setup connection
-    let connection = Rc::new(get_db_connection());
+    let storage = Rc::clone(storage);
 
     // Setup TaskResultsWindow
-    let task_results_window = Rc::new(TaskResultsWindow::new(&ui));
+    let task_results_window = Rc::new(TaskResultsWindow::new(
+      &ui,
+      content_region(),
+      status_region(),
+    ));
 
     // Setup Scroller.
     let mut scroller = TasksScroller::new();
@@ -53,7 +91,7 @@ impl ActiveTasksView {
     let filterer = Rc::new(filterer);
 
     // Setup DataSource
-    let mut data_source = DataSource::new();
+    let mut data_source = DataSource::new(config.scoring);
     // Filterer listens to DataSource.
     {
       let filterer = Rc::clone(&filterer);
@@ -61,38 +99,467 @@ impl ActiveTasksView {
         filterer.refresh(results);
       }));
     }
+    // Pick up the strategy chosen in a previous session.
+    // If it can't be read, the first pull will report why.
+    let scoring_strategy = storage
+      .get_setting(SCORING_STRATEGY_SETTING)
+      .unwrap_or(None)
+      .and_then(|name| ScoringStrategy::from_name(&name))
+      .unwrap_or_default();
+    data_source.set_scoring_strategy(scoring_strategy);
     let data_source = Rc::new(data_source);
 
     // Setup UndoBuffer
     let undo_buffer = Rc::new(UndoBuffer::new());
 
     let view = ActiveTasksView {
-      connection,
+      storage,
       ui,
       task_results_window,
       scroller,
       filterer,
       data_source,
       undo_buffer,
+      keymap,
+      pending_keys: RefCell::new(vec![]),
+      applying_to_all: Cell::new(false),
+      command_history: RefCell::new(vec![]),
+      search: RefCell::new(None),
     };
     let view = Rc::new(view);
 
-    view.data_source.pull(&view.connection);
+    if let Err(err) = view.restore_undo_buffer() {
+      view.task_results_window.show_error(&err);
+    }
 
     view
   }
 
-  pub fn handle_key(view: &Rc<Self>, ch: char) {
-    let did_execute_action = ActiveTasksViewCommand::from_key(ch)
-      .and_then(|cmd| cmd.to_action(view))
-      .map(|mut action| {
-        action.execute();
-        action.maybe_add_to_undo_buffer(&view.undo_buffer);
+  // Picks up the task actions logged by previous sessions. Entries
+  // logged together are one action. The applied actions form a single
+  // branch, so the newest of them is where the undo tree is at. Actions
+  // whose parent is too old to be loaded hang off the start.
+  fn restore_undo_buffer(self: &Rc<Self>) -> Result<()> {
+    let entries = self.storage.recent_action_log(ACTION_LOG_LIMIT)?;
+    let mut batches: Vec<Vec<&ActionLogEntry>> = vec![];
+    for entry in &entries {
+      let batch_id = entry.batch_id.unwrap_or(entry.id);
+      match batches.last_mut() {
+        Some(batch) if batch[0].id == batch_id => batch.push(entry),
+        _ => batches.push(vec![entry]),
+      }
+    }
+
+    // A batch with an unreadable entry is skipped, so its children hang
+    // off its parent as if it were too old. The first error is
+    // reported once the rest is restored.
+    let mut first_err = None;
+    let mut restored = vec![];
+    for batch in batches {
+      let tas: Result<Vec<_>> = batch
+        .iter()
+        .map(|entry| TaskAction::from_log_entry(entry, &self.storage))
+        .collect();
+      match tas {
+        Ok(tas) => restored.push((batch, tas)),
+        Err(err) => {
+          first_err.get_or_insert(err);
+        }
+      }
+    }
+
+    // Batch `idx` becomes action number `idx + 1`.
+    let num_of = |log_id: Option<i32>| {
+      restored
+        .iter()
+        .position(|(batch, _)| {
+          batch.iter().any(|entry| Some(entry.id) == log_id)
+        }).map_or(0, |idx| idx + 1)
+    };
+    let current = restored
+      .iter()
+      .rposition(|(batch, _)| !batch[0].undone)
+      .map_or(0, |idx| idx + 1);
+
+    let parents: Vec<usize> = restored
+      .iter()
+      .map(|(batch, _)| num_of(batch[0].parent_id))
+      .collect();
+
+    let mut actions: Vec<(Box<dyn ReversableAction>, usize)> = vec![];
+    for ((batch, tas), parent) in restored.into_iter().zip(parents) {
+      let log_ids = batch.iter().map(|entry| entry.id).collect();
+      let action = ActiveTasksViewAction::restore_from_log(tas, self, log_ids);
+      actions.push((Box::new(action), parent));
+    }
+
+    self.undo_buffer.restore(actions, current);
+    first_err.map_or(Ok(()), Err)
+  }
+
+  fn open_view(&self, cmd: OpenViewCommand) -> Option<Rc<dyn View>> {
+    match cmd {
+      OpenViewCommand::Help => Some(HelpView::new(&self.ui, &self.keymap)),
+      OpenViewCommand::TaskDetail => {
+        let task_id = self.scroller.current_task_id()?;
+        let view = TaskDetailView::new(
+          task_id,
+          self.data_source.scorer(),
+          &self.ui,
+          &self.storage,
+          &self.undo_buffer,
+        );
+
+        // Stay here if the task can't be loaded.
+        match view.pull() {
+          Ok(()) => Some(view as Rc<dyn View>),
+          Err(err) => {
+            self.task_results_window.show_error(&err);
+            None
+          }
+        }
+      }
+      OpenViewCommand::UndoHistory => {
+        Some(UndoHistoryView::new(&self.ui, &self.undo_buffer))
+      }
+    }
+  }
+
+  // Shows storage failures on the status line instead of crashing.
+  pub fn report(&self, result: Result<()>) {
+    if let Err(err) = result {
+      self.task_results_window.show_error(&err);
+    }
+  }
+
+  // `None` if the user gave up or typed nothing. Up and down go through
+  // the earlier lines.
+  pub fn read_command_line(&self) -> Option<String> {
+    let line = self
+      .ui
+      .read_line_with_history(":", &self.command_history.borrow())?;
+    let line = line.trim();
+    if line.is_empty() {
+      return None;
+    }
+
+    let mut command_history = self.command_history.borrow_mut();
+    if command_history.last().map(String::as_str) != Some(line) {
+      command_history.push(line.to_string());
+    }
+    Some(line.to_string())
+  }
+
+  // Pulls fresh results, e.g. after an action.
+  pub fn refresh(&self) {
+    self.report(self.data_source.pull(&*self.storage));
+  }
+
+  // Returns a command once the keys typed so far spell one out.
+  fn resolve_key(&self, ch: char) -> Option<ActiveTasksViewCommand> {
+    let mut pending_keys = self.pending_keys.borrow_mut();
+    pending_keys.push(ch);
+
+    match self.keymap.lookup(&pending_keys) {
+      KeymapMatch::Command(cmd) => {
+        pending_keys.clear();
+        Some(cmd)
+      }
+      KeymapMatch::Prefix => None,
+      KeymapMatch::NoMatch => {
+        let was_sequence = pending_keys.len() > 1;
+        pending_keys.clear();
+        drop(pending_keys);
+
+        // The key that broke a sequence may start a new one.
+        if was_sequence {
+          self.resolve_key(ch)
+        } else {
+          None
+        }
+      }
+    }
+  }
+
+  // E.g. "-net | 12 tasks | undo 3/5", or "showing 21\u{2013}40 of 130
+  // tasks" when they don't all fit, plus the filter and its preset in
+  // the header.
+  fn update_status(&self) {
+    let num_tasks = self.scroller.num_results();
+    let (undo_position, undo_len) = self.undo_buffer.position();
+    self.task_results_window.set_filter(self.filterer.criteria().map(
+      |criteria| match self.filterer.preset() {
+        None => criteria.to_string(),
+        Some(preset) => format!("{}   [{}]", criteria, preset),
+      },
+    ));
+
+    let visible_range = self.task_results_window.visible_range();
+    let showing = if visible_range.len() < num_tasks as usize {
+      format!(
+        "showing {}\u{2013}{} of ",
+        visible_range.start + 1,
+        visible_range.end
+      )
+    } else {
+      String::new()
+    };
+
+    self.task_results_window.set_status(format!(
+      "{} | {}{} task{} | undo {}/{}",
+      self.filterer.description(),
+      showing,
+      num_tasks,
+      if num_tasks == 1 { "" } else { "s" },
+      undo_position,
+      undo_len,
+    ));
+  }
+
+  // Everything `handle_key` does besides refreshing the status line.
+  fn handle_command(self: Rc<Self>, ch: char) -> ViewResult {
+    let cmd = self.resolve_key(ch);
+    // Only lasts for one command, whatever it is.
+    let applying_to_all =
+      cmd.is_some() && self.applying_to_all.replace(false);
+
+    let cmd = match cmd {
+      Some(ActiveTasksViewCommand::OpenView(cmd)) => {
+        return match self.open_view(cmd) {
+          None => ViewResult::KeyHandled,
+          Some(view) => ViewResult::PushView(view),
+        };
+      }
+      Some(ActiveTasksViewCommand::CycleScoringStrategy) => {
+        self.cycle_scoring_strategy();
+        return ViewResult::KeyHandled;
+      }
+      Some(ActiveTasksViewCommand::SaveFilterPreset) => {
+        self.save_filter_preset();
+        return ViewResult::KeyHandled;
+      }
+      Some(ActiveTasksViewCommand::DeleteFilterPreset) => {
+        self.delete_filter_preset();
+        return ViewResult::KeyHandled;
+      }
+      Some(ActiveTasksViewCommand::SearchTitles) => {
+        self.search_titles();
+        return ViewResult::KeyHandled;
+      }
+      Some(ActiveTasksViewCommand::RepeatSearch(direction)) => {
+        self.repeat_search(direction);
+        return ViewResult::KeyHandled;
+      }
+      Some(ActiveTasksViewCommand::ToggleScoreBreakdown) => {
+        self.task_results_window.toggle_score_breakdown();
+        return ViewResult::KeyHandled;
+      }
+      Some(ActiveTasksViewCommand::ApplyToAll) => {
+        self.applying_to_all.set(true);
+        self.task_results_window.show_message(&format!(
+          "Next task command applies to all {} listed",
+          self.scroller.num_results()
+        ));
+        return ViewResult::KeyHandled;
+      }
+      cmd => cmd,
+    };
+
+    let is_undo_buffer_cmd =
+      matches!(cmd, Some(ActiveTasksViewCommand::UndoBuffer(..)));
+    let did_execute_action = cmd
+      .and_then(|cmd| match cmd {
+        ActiveTasksViewCommand::Task(tc) if applying_to_all => {
+          ActiveTasksViewAction::prepare_batch(tc, &self)
+        }
+        cmd => cmd.to_action(&self),
+      }).map(|mut action| match action.execute() {
+        // A failed action never happened, so there is nothing to undo.
+        Ok(()) => action.maybe_add_to_undo_buffer(&self.undo_buffer),
+        Err(err) => self.task_results_window.show_error(&err),
       }).is_some();
 
-    if !did_execute_action {
+    if is_undo_buffer_cmd {
+      // Actions done in other views, like destroying a task event,
+      // don't refresh this one when undone.
+      self.refresh();
+    } else if !did_execute_action {
       // Redraw screen regardless.
-      view.task_results_window.full_redraw();
+      self.task_results_window.full_redraw();
+    }
+
+    ViewResult::KeyHandled
+  }
+
+  fn cycle_scoring_strategy(&self) {
+    let scoring_strategy = self.data_source.scoring_strategy().next();
+    self.report(
+      self
+        .storage
+        .set_setting(SCORING_STRATEGY_SETTING, scoring_strategy.name()),
+    );
+    self.data_source.set_scoring_strategy(scoring_strategy);
+    self.refresh();
+  }
+
+  // Reads the search a key at a time. Each change moves to the first
+  // match at or below where the search started, or back there if
+  // nothing matches. Enter stays put and Escape goes back. Like a
+  // scroll, none of this is undone.
+  fn search_titles(&self) {
+    let start_idx = self.scroller.current_result_idx();
+    let old_search = self.search.borrow().clone();
+    let mut query = String::new();
+
+    loop {
+      self.task_results_window.show_message(&format!("/{}", query));
+      match self.ui.getch() {
+        // As does any key that isn't a character, like an arrow.
+        None | Some(ENTER) => break,
+        Some(ESCAPE) => {
+          self.set_search(old_search);
+          self.go_back_to(start_idx);
+          self.task_results_window.clear_message();
+          return;
+        }
+        Some(BACKSPACE) => {
+          query.pop();
+        }
+        Some(ch) => query.push(ch),
+      }
+
+      if query.is_empty() {
+        self.set_search(None);
+        self.go_back_to(start_idx);
+      } else {
+        self.set_search(Some(query.clone()));
+        if !self.scroller.jump_to_title_match(
+          &query,
+          start_idx,
+          Direction::Increase,
+        ) {
+          self.go_back_to(start_idx);
+        }
+      }
+    }
+
+    // An empty search just clears the old one.
+    match query.as_str() {
+      "" => self.task_results_window.clear_message(),
+      query => self.show_search_position(query),
     }
   }
+
+  fn repeat_search(&self, direction: Direction) {
+    let query = match self.search.borrow().clone() {
+      None => {
+        self.task_results_window.show_message("No search to repeat");
+        return;
+      }
+      Some(query) => query,
+    };
+
+    let current_idx = self.scroller.current_result_idx();
+    let from_idx = match direction {
+      Direction::Decrease => current_idx - 1,
+      Direction::Increase => current_idx + 1,
+    };
+    self.scroller.jump_to_title_match(&query, from_idx, direction);
+    self.show_search_position(&query);
+  }
+
+  fn set_search(&self, search: Option<String>) {
+    *self.search.borrow_mut() = search.clone();
+    self.task_results_window.set_search(search);
+  }
+
+  // Moving in an empty list would fall off it.
+  fn go_back_to(&self, result_idx: i32) {
+    if self.scroller.current_result_idx() != result_idx {
+      self.scroller.set_current_result_idx(result_idx);
+    }
+  }
+
+  // E.g. "/rvbdg 2 of 5".
+  fn show_search_position(&self, query: &str) {
+    let matches = self.scroller.title_matches(query);
+    let current_idx = self.scroller.current_result_idx();
+    let message = match matches.iter().position(|&idx| idx == current_idx) {
+      None => format!("No title matches /{}", query),
+      Some(pos) => format!("/{} {} of {}", query, pos + 1, matches.len()),
+    };
+    self.task_results_window.show_message(&message);
+  }
+
+  // Not an action, so there is nothing to undo. The filter stays as it
+  // is until the preset is applied.
+  fn save_filter_preset(&self) {
+    let criteria = match self.filterer.criteria() {
+      None => {
+        self.task_results_window.show_message("No filter to save");
+        return;
+      }
+      Some(criteria) => criteria,
+    };
+    let name = match self.ui.read_line("Save filter as: ") {
+      None => return,
+      Some(name) => name.trim().to_string(),
+    };
+    if name.is_empty() {
+      return;
+    }
+
+    if let Err(err) =
+      self.storage.save_filter_preset(&name, &criteria.to_string())
+    {
+      self.task_results_window.show_error(&err);
+      return;
+    }
+    self
+      .task_results_window
+      .show_message(&format!("Saved filter as {}", name));
+  }
+
+  // Leaves the filter as it is if the preset was the active one.
+  fn delete_filter_preset(&self) {
+    let preset =
+      match read_filter_preset(&self.ui, &self.storage, "Delete preset") {
+        None => return,
+        Some(Err(message)) => {
+          self.task_results_window.show_message(&message);
+          return;
+        }
+        Some(Ok(preset)) => preset,
+      };
+
+    if let Err(err) = self.storage.delete_filter_preset(&preset.name) {
+      self.task_results_window.show_error(&err);
+      return;
+    }
+    self
+      .task_results_window
+      .show_message(&format!("Deleted preset {}", preset.name));
+  }
+}
+
+impl View for ActiveTasksView {
+  fn handle_key(self: Rc<Self>, ch: char) -> ViewResult {
+    self.task_results_window.clear_message();
+    let view_result = Rc::clone(&self).handle_command(ch);
+    self.update_status();
+    view_result
+  }
+
+  fn redraw(&self) {
+    self.task_results_window.invalidate();
+    self.refresh();
+    self.update_status();
+  }
+
+  fn title(&self) -> String {
+    format!(
+      "Active tasks ({})",
+      self.data_source.scoring_strategy().name()
+    )
+  }
 }
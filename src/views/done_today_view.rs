@@ -0,0 +1,96 @@
+use actions::ForwardAction;
+use chrono::{Local, Utc};
+use commands::DoneTodayViewCommand;
+use components::{BaseScroller, TaskEventResult, TaskEventResultsWindow};
+use std::rc::Rc;
+use storage::Storage;
+use util::UserInterface;
+use views::{content_region, View, ViewResult};
+
+// Lists the tasks completed or worked on since local midnight.
+pub struct DoneTodayView {
+  pub storage: Rc<dyn Storage>,
+  pub ui: Rc<UserInterface>,
+  pub task_event_results_window: Rc<TaskEventResultsWindow>,
+  pub scroller: Rc<BaseScroller<TaskEventResult>>,
+}
+
+impl DoneTodayView {
+  pub fn new(
+    ui: &Rc<UserInterface>,
+    storage: &Rc<dyn Storage>,
+  ) -> Rc<DoneTodayView> {
+    let ui = Rc::clone(ui);
+    let storage = Rc::clone(storage);
+
+    // Setup TaskEventResultsWindow
+    let task_event_results_window =
+      Rc::new(TaskEventResultsWindow::new(&ui, content_region()));
+
+    // Setup Scroller.
+    let mut scroller = BaseScroller::new();
+    // TaskEventResultsWindow listens to Scroller.
+    {
+      let task_event_results_window =
+        Rc::clone(&task_event_results_window);
+      scroller.add_callback(Box::new(move |event| {
+        task_event_results_window.redraw(event);
+      }));
+    }
+    let scroller = Rc::new(scroller);
+
+    Rc::new(DoneTodayView {
+      storage,
+      ui,
+      task_event_results_window,
+      scroller,
+    })
+  }
+}
+
+impl View for DoneTodayView {
+  fn handle_key(self: Rc<Self>, ch: char) -> ViewResult {
+    self.task_event_results_window.clear_message();
+
+    let did_execute_action = DoneTodayViewCommand::from_key(ch)
+      .and_then(|cmd| cmd.to_action(&self))
+      .map(|mut action| {
+        if let Err(err) = action.execute() {
+          self.task_event_results_window.show_error(&err);
+        }
+      }).is_some();
+
+    if !did_execute_action {
+      // Redraw screen regardless.
+      self.task_event_results_window.full_redraw();
+    }
+
+    ViewResult::KeyHandled
+  }
+
+  // Events change while other views are in use, so they are pulled
+  // afresh every time the view is shown.
+  fn redraw(&self) {
+    let midnight = Local::today().and_hms(0, 0, 0).with_timezone(&Utc);
+    self.task_event_results_window.invalidate();
+
+    match self.storage.done_since(midnight) {
+      Ok(done) => {
+        let results: Vec<_> = done
+          .into_iter()
+          .map(|(task_event, task)| TaskEventResult { task, task_event })
+          .collect();
+        self.scroller.refresh(&Rc::new(results));
+      }
+      Err(err) => {
+        // Keep showing whatever was there before, if anything.
+        self.scroller.refresh(&self.scroller.results());
+        self.task_event_results_window.show_error(&err);
+      }
+    }
+  }
+
+  fn title(&self) -> String {
+    String::from("Done today")
+  }
+}
@@ -0,0 +1,136 @@
+use commands::{format_key_sequence, ActiveTasksViewCommand, Keymap};
+use std::rc::Rc;
+use util::{
+  line_buffer::{LineBuffer, TerminalLine},
+  ui::{ColorPair, UserInterface},
+};
+use views::{content_region, View, ViewResult};
+
+// Command name in the keymap, description.
+const ACTIVE_TASKS_VIEW_COMMANDS: &[(&str, &str)] = &[
+  ("scroll_down", "scroll down"),
+  ("scroll_up", "scroll up"),
+  ("jump_to_top", "jump to top"),
+  ("jump_to_bottom", "jump to bottom"),
+  ("page_down", "page down"),
+  ("page_up", "page up"),
+  ("half_page_down", "half a page down"),
+  ("half_page_up", "half a page up"),
+  ("jump_to_task", "jump to task id"),
+  ("search_titles", "search titles as you type"),
+  ("search_next", "next search match"),
+  ("search_previous", "previous search match"),
+  ("task_detail", "show task details"),
+  ("toggle_score_breakdown", "toggle score breakdown"),
+  ("cycle_scoring_strategy", "cycle scoring strategy"),
+  ("create_task", "create task"),
+  ("edit_task_title", "edit task title"),
+  ("record_task_effort", "record task effort"),
+  ("request_task_delay", "request delay"),
+  ("request_task_age_reset", "request age reset"),
+  ("toggle_requires_internet", "toggle requires internet"),
+  ("decrease_duration", "decrease duration"),
+  ("increase_duration", "increase duration"),
+  ("decrease_priority", "decrease priority"),
+  ("increase_priority", "increase priority"),
+  ("edit_recurrence", "edit recurrence"),
+  ("complete_task", "complete task"),
+  ("abandon_task", "abandon task"),
+  ("apply_to_all", "apply next task command to all listed"),
+  ("filter_by_requires_internet", "filter by requires internet"),
+  ("filter_by_min_priority", "and priority at least"),
+  ("filter_by_max_priority", "and priority at most"),
+  ("filter_by_duration", "and duration one of"),
+  ("filter_older_than", "and older than"),
+  ("filter_younger_than", "and younger than"),
+  ("filter_by_title", "and title contains"),
+  ("filter_by_recent_effort", "and effort recorded within"),
+  ("filter_by_delays", "and delayed at least"),
+  ("negate_last_filter", "negate last filter"),
+  ("or_last_filters", "or last filter with the rest"),
+  ("clear_filter", "clear filter"),
+  ("apply_filter_preset", "apply saved filter preset"),
+  ("save_filter_preset", "save filter as preset"),
+  ("delete_filter_preset", "delete filter preset"),
+  ("command_line", "command line, e.g. :filter prio>=med -net"),
+  ("undo", "undo"),
+  ("redo", "redo"),
+  ("undo_older", "go to older undo state"),
+  ("undo_newer", "go to newer undo state"),
+  ("undo_history", "undo history"),
+  ("help", "this help"),
+];
+
+// Keys the Application handles itself.
+const APPLICATION_KEYS: &[(&str, &str)] = &[
+  ("Tab", "switch to done today"),
+  ("q", "close view / quit"),
+];
+
+pub struct HelpView {
+  line_buffer: LineBuffer,
+  // Keys, description.
+  lines: Vec<(String, &'static str)>,
+}
+
+impl HelpView {
+  // Shows whatever keys the commands are bound to right now.
+  pub fn new(
+    ui: &Rc<UserInterface>,
+    keymap: &Keymap<ActiveTasksViewCommand>,
+  ) -> Rc<HelpView> {
+    let command_lines =
+      ACTIVE_TASKS_VIEW_COMMANDS.iter().map(|(name, description)| {
+        let keys = keymap
+          .keys_for(name)
+          .expect("Every command in the help should be in the keymap");
+        (format_key_sequence(keys), *description)
+      });
+    let application_lines = APPLICATION_KEYS
+      .iter()
+      .map(|(keys, description)| (keys.to_string(), *description));
+
+    Rc::new(HelpView {
+      line_buffer: LineBuffer::new(ui, content_region()),
+      lines: command_lines.chain(application_lines).collect(),
+    })
+  }
+}
+
+impl View for HelpView {
+  fn handle_key(self: Rc<Self>, _ch: char) -> ViewResult {
+    ViewResult::KeyHandled
+  }
+
+  fn redraw(&self) {
+    let key_width = self
+      .lines
+      .iter()
+      .map(|(keys, _)| keys.len())
+      .max()
+      .unwrap_or(0);
+
+    for (idx, (keys, description)) in self.lines.iter().enumerate() {
+      let text = format!(
+        " {keys:key_width$} | {description} \n",
+        keys = keys,
+        key_width = key_width,
+        description = description,
+      );
+      self.line_buffer.replace_line(
+        idx,
+        TerminalLine {
+          text,
+          color: ColorPair::Default,
+        },
+      );
+    }
+
+    self.line_buffer.mark_all_dirty();
+    self.line_buffer.redraw();
+  }
+
+  fn title(&self) -> String {
+    String::from("Help")
+  }
+}
@@ -1,3 +1,13 @@
 mod active_tasks_view;
+mod done_today_view;
+mod help_view;
+mod task_detail_view;
+mod undo_history_view;
+mod view;
 
 pub use self::active_tasks_view::ActiveTasksView;
+pub use self::done_today_view::DoneTodayView;
+pub use self::help_view::HelpView;
+pub use self::task_detail_view::TaskDetailView;
+pub use self::undo_history_view::UndoHistoryView;
+pub use self::view::{content_region, status_region, View, ViewResult};
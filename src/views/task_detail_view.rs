@@ -0,0 +1,129 @@
+use actions::ForwardAction;
+use commands::TaskDetailViewCommand;
+use components::{
+  BaseScroller, Scorer, TaskDetailWindow, TaskResult, UndoBuffer,
+};
+use error::Result;
+use models::TaskEvent;
+use std::rc::Rc;
+use storage::Storage;
+use util::UserInterface;
+use views::{content_region, View, ViewResult};
+
+// Everything about a single task, including its full event history.
+pub struct TaskDetailView {
+  pub task_id: i32,
+  pub scorer: Box<dyn Scorer>,
+  pub storage: Rc<dyn Storage>,
+  pub ui: Rc<UserInterface>,
+  pub task_detail_window: Rc<TaskDetailWindow>,
+  pub scroller: Rc<BaseScroller<TaskEvent>>,
+  pub undo_buffer: Rc<UndoBuffer>,
+}
+
+impl TaskDetailView {
+  pub fn new(
+    task_id: i32,
+    scorer: Box<dyn Scorer>,
+    ui: &Rc<UserInterface>,
+    storage: &Rc<dyn Storage>,
+    undo_buffer: &Rc<UndoBuffer>,
+  ) -> Rc<TaskDetailView> {
+    let ui = Rc::clone(ui);
+    let storage = Rc::clone(storage);
+    // Shared with the view that opened this one, so what is done here
+    // can be undone after it is closed.
+    let undo_buffer = Rc::clone(undo_buffer);
+
+    // Setup TaskDetailWindow
+    let task_detail_window =
+      Rc::new(TaskDetailWindow::new(&ui, content_region()));
+
+    // Setup Scroller.
+    let mut scroller = BaseScroller::new();
+    // TaskDetailWindow listens to Scroller.
+    {
+      let task_detail_window = Rc::clone(&task_detail_window);
+      scroller.add_callback(Box::new(move |event| {
+        task_detail_window.redraw(event);
+      }));
+    }
+    let scroller = Rc::new(scroller);
+
+    Rc::new(TaskDetailView {
+      task_id,
+      scorer,
+      storage,
+      ui,
+      task_detail_window,
+      scroller,
+      undo_buffer,
+    })
+  }
+
+  pub fn current_task_event(&self) -> Option<TaskEvent> {
+    let idx = self.scroller.current_result_idx();
+    self.scroller.results().get(idx as usize).cloned()
+  }
+
+  // Pulls the task and its events afresh.
+  pub fn pull(&self) -> Result<()> {
+    let task = self.storage.find_task(self.task_id)?;
+    let task_events = self.storage.task_events(&task)?;
+    let task_result = TaskResult::from_task(
+      task,
+      task_events,
+      ::chrono::Utc::now(),
+      self.scorer.as_ref(),
+    );
+
+    // Events are displayed oldest first.
+    let mut task_events = task_result.task_events.clone();
+    task_events.reverse();
+
+    self.task_detail_window.set_task_result(task_result);
+    self.scroller.refresh(&Rc::new(task_events));
+    Ok(())
+  }
+
+  // Like `pull`, but keeps showing the old data if it fails.
+  pub fn refresh(&self) {
+    if let Err(err) = self.pull() {
+      self.task_detail_window.show_error(&err);
+    }
+  }
+}
+
+impl View for TaskDetailView {
+  fn handle_key(self: Rc<Self>, ch: char) -> ViewResult {
+    self.task_detail_window.clear_message();
+
+    let cmd = TaskDetailViewCommand::from_key(ch);
+    let did_execute_action = cmd
+      .and_then(|cmd| cmd.to_action(&self))
+      .map(|mut action| match action.execute() {
+        Ok(()) => action.maybe_add_to_undo_buffer(&self.undo_buffer),
+        Err(err) => self.task_detail_window.show_error(&err),
+      }).is_some();
+
+    if let Some(TaskDetailViewCommand::UndoBuffer(..)) = cmd {
+      // What was undone may have been done in another view, which
+      // won't have refreshed this one.
+      self.refresh();
+    } else if !did_execute_action {
+      // Redraw screen regardless.
+      self.task_detail_window.full_redraw();
+    }
+
+    ViewResult::KeyHandled
+  }
+
+  fn redraw(&self) {
+    self.task_detail_window.invalidate();
+    self.refresh();
+  }
+
+  fn title(&self) -> String {
+    format!("Task #{}", self.task_id)
+  }
+}
@@ -0,0 +1,150 @@
+use actions::ForwardAction;
+use commands::UndoHistoryViewCommand;
+use components::{
+  BaseScroller, Scroller, UndoBuffer, UndoHistoryResult, UndoHistoryWindow,
+};
+use std::rc::Rc;
+use util::UserInterface;
+use views::{content_region, View, ViewResult};
+
+// Shows the whole tree of an `UndoBuffer` and goes to the selected
+// point, whichever branch it is on.
+pub struct UndoHistoryView {
+  pub undo_buffer: Rc<UndoBuffer>,
+  pub undo_history_window: Rc<UndoHistoryWindow>,
+  pub scroller: Rc<BaseScroller<UndoHistoryResult>>,
+}
+
+impl UndoHistoryView {
+  pub fn new(
+    ui: &Rc<UserInterface>,
+    undo_buffer: &Rc<UndoBuffer>,
+  ) -> Rc<UndoHistoryView> {
+    // Setup UndoHistoryWindow
+    let undo_history_window =
+      Rc::new(UndoHistoryWindow::new(ui, content_region()));
+
+    // Setup Scroller.
+    let mut scroller = BaseScroller::new();
+    // UndoHistoryWindow listens to Scroller.
+    {
+      let undo_history_window = Rc::clone(&undo_history_window);
+      scroller.add_callback(Box::new(move |event| {
+        undo_history_window.redraw(event);
+      }));
+    }
+    let scroller = Rc::new(scroller);
+
+    let view = Rc::new(UndoHistoryView {
+      undo_buffer: Rc::clone(undo_buffer),
+      undo_history_window,
+      scroller,
+    });
+
+    // Start out on the current point.
+    let current_idx = view
+      .pull()
+      .iter()
+      .position(|result| result.is_current)
+      .unwrap_or(0);
+    view.scroller.set_current_result_idx(current_idx as i32);
+
+    view
+  }
+
+  // Lists the tree depth first. The oldest child of an action carries
+  // on its branch and the others start new ones below it.
+  fn pull(&self) -> Rc<Vec<UndoHistoryResult>> {
+    let tree = self.undo_buffer.tree();
+    let current = self.undo_buffer.current();
+
+    let mut children = vec![vec![]; tree.len() + 1];
+    for node in &tree {
+      children[node.parent].push(node.num);
+    }
+    let mut is_applied = vec![false; tree.len() + 1];
+    let mut num = current;
+    while num != 0 {
+      is_applied[num] = true;
+      num = tree[num - 1].parent;
+    }
+
+    let mut results = vec![];
+    // Number, branch depth, whether it starts a branch.
+    let mut stack = vec![(0, 0, false)];
+    while let Some((num, branch_depth, starts_branch)) = stack.pop() {
+      results.push(UndoHistoryResult {
+        num,
+        description: num
+          .checked_sub(1)
+          .map(|idx| tree[idx].description.clone()),
+        branch_depth,
+        starts_branch,
+        is_applied: is_applied[num],
+        is_current: num == current,
+      });
+
+      for (idx, &child) in children[num].iter().enumerate().rev() {
+        let starts_branch = idx > 0;
+        let depth = branch_depth + if starts_branch { 1 } else { 0 };
+        stack.push((child, depth, starts_branch));
+      }
+    }
+
+    let results = Rc::new(results);
+    self.scroller.refresh(&results);
+    results
+  }
+
+  // The actions redraw the views they belong to, which draws over this
+  // one, so the whole screen has to be repainted afterwards.
+  fn jump_to_selected(&self) -> ViewResult {
+    let selected_idx = self.scroller.current_result_idx() as usize;
+    let result = match self.scroller.results().get(selected_idx) {
+      None => Ok(()),
+      Some(selected) => self.undo_buffer.go_to(selected.num),
+    };
+
+    self.pull();
+    if let Err(err) = result {
+      self.undo_history_window.show_error(&err);
+    }
+
+    ViewResult::RedrawScreen
+  }
+}
+
+impl View for UndoHistoryView {
+  fn handle_key(self: Rc<Self>, ch: char) -> ViewResult {
+    self.undo_history_window.clear_message();
+
+    match UndoHistoryViewCommand::from_key(ch) {
+      Some(UndoHistoryViewCommand::JumpToSelected) => {
+        self.jump_to_selected()
+      }
+      Some(UndoHistoryViewCommand::Scroll(sc)) => {
+        let scroller = Rc::clone(&self.scroller) as Rc<dyn Scroller>;
+        if let Some(Err(err)) =
+          sc.to_action(&scroller).map(|mut sa| sa.execute())
+        {
+          self.undo_history_window.show_error(&err);
+        }
+        ViewResult::KeyHandled
+      }
+      None => {
+        // Redraw screen regardless.
+        self.undo_history_window.full_redraw();
+        ViewResult::KeyHandled
+      }
+    }
+  }
+
+  fn redraw(&self) {
+    self.undo_history_window.invalidate();
+    self.pull();
+  }
+
+  fn title(&self) -> String {
+    String::from("Undo history")
+  }
+}
@@ -0,0 +1,33 @@
+use std::rc::Rc;
+use util::line_buffer::Region;
+
+// The `Application` draws a title bar on the first row. Views get
+// everything below it except for the last row, which is kept for a
+// status line.
+pub fn content_region() -> Region {
+  Region::above_bottom_rows(1, 1)
+}
+
+pub fn status_region() -> Region {
+  Region::bottom_rows(1)
+}
+
+// Tells the `Application` what should happen after a key was handled.
+pub enum ViewResult {
+  KeyHandled,
+  PushView(Rc<dyn View>),
+  // Something drew over this view, so everything must be repainted.
+  RedrawScreen,
+}
+
+pub trait View {
+  // Takes an `Rc` because actions hold weak references back to their
+  // view.
+  fn handle_key(self: Rc<Self>, ch: char) -> ViewResult;
+
+  // Called whenever the view (re)appears on screen. The screen will
+  // have been cleared, so everything must be repainted.
+  fn redraw(&self);
+
+  fn title(&self) -> String;
+}
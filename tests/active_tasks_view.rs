@@ -0,0 +1,636 @@
+extern crate chrono;
+extern crate task_manager;
+
+mod common;
+
+use chrono::Duration;
+use common::{available_task_ids, create_aged_task, event_types};
+use std::collections::HashMap;
+use std::rc::Rc;
+use task_manager::commands::ActiveTasksViewCommand;
+use task_manager::config::Config;
+use task_manager::error::Error;
+use task_manager::models::{ActionLogKind, NewActionLogEntry, TaskEventType};
+use task_manager::storage::Storage;
+use task_manager::util::terminal::{HeadlessTerminal, ScriptedInput, Terminal};
+use task_manager::util::ui::ColorPair;
+use task_manager::util::UserInterface;
+use task_manager::views::{ActiveTasksView, View, ViewResult};
+
+struct Harness {
+  terminal: Rc<HeadlessTerminal>,
+  view: Rc<ActiveTasksView>,
+}
+
+impl Harness {
+  fn new(storage: &Rc<dyn Storage>) -> Harness {
+    let terminal = Rc::new(HeadlessTerminal::new(30, 160));
+    let ui = Rc::new(UserInterface::new(
+      Rc::clone(&terminal) as Rc<dyn Terminal>
+    ));
+    let keymap = ActiveTasksViewCommand::keymap(&HashMap::new()).unwrap();
+    let view =
+      ActiveTasksView::new(&ui, storage, &Config::default(), keymap);
+    view.redraw();
+
+    Harness { terminal, view }
+  }
+
+  // Feeds every scripted key to the view, the way Application does.
+  fn run(&self) {
+    while let Some(ch) = self.terminal.getch() {
+      Rc::clone(&self.view).handle_key(ch);
+    }
+  }
+
+  fn type_keys(&self, keys: &str) {
+    self.terminal.type_keys(keys);
+    self.run();
+  }
+
+  fn row(&self, title: &str) -> usize {
+    self
+      .terminal
+      .find_row(title)
+      .unwrap_or_else(|| panic!("{} is not on screen", title))
+  }
+
+  // Whether any row above the status line mentions `title`.
+  fn shows_task(&self, title: &str) -> bool {
+    let lines = self.terminal.lines();
+    lines[..lines.len() - 1]
+      .iter()
+      .any(|line| line.contains(title))
+  }
+
+  // The column names.
+  fn header(&self) -> String {
+    self.terminal.lines()[self.row(" title ")].trim().to_string()
+  }
+
+  fn status_line(&self) -> String {
+    self.terminal.lines().last().unwrap().trim().to_string()
+  }
+
+  fn is_highlighted(&self, title: &str) -> bool {
+    self.terminal.row_color(self.row(title)) == ColorPair::Highlight
+  }
+}
+
+fn storage_with_two_tasks() -> Rc<dyn Storage> {
+  let storage = common::memory_storage();
+  create_aged_task(&storage, "Review budget", Duration::hours(1));
+  create_aged_task(&storage, "Schedule flu shot", Duration::hours(5));
+  storage
+}
+
+#[test]
+fn shows_oldest_task_first_and_highlighted() {
+  let harness = Harness::new(&storage_with_two_tasks());
+
+  assert!(harness.row("Schedule flu shot") < harness.row("Review budget"));
+  assert!(harness.is_highlighted("Schedule flu shot"));
+  assert!(!harness.is_highlighted("Review budget"));
+}
+
+#[test]
+fn scrolling_moves_the_highlight() {
+  let harness = Harness::new(&storage_with_two_tasks());
+
+  harness.type_keys("j");
+  assert!(harness.is_highlighted("Review budget"));
+  assert!(!harness.is_highlighted("Schedule flu shot"));
+
+  harness.type_keys("k");
+  assert!(harness.is_highlighted("Schedule flu shot"));
+}
+
+#[test]
+fn create_task_then_undo_and_redo() {
+  let harness = Harness::new(&storage_with_two_tasks());
+
+  harness.terminal.type_keys("o");
+  harness.terminal.type_line("Buy distilled water");
+  harness.run();
+  assert_eq!(harness.terminal.prompts(), vec!["Edit task title: "]);
+  harness.row("Buy distilled water");
+
+  harness.type_keys("u");
+  assert!(!harness.shows_task("Buy distilled water"));
+
+  harness.type_keys("U");
+  harness.row("Buy distilled water");
+}
+
+#[test]
+fn destroyed_task_events_can_be_undone_after_closing_the_detail_view() {
+  let storage = common::storage();
+  let task = storage.create_task("Water the ferns").unwrap();
+  storage.record_task_effort(task.id).unwrap();
+  let harness = Harness::new(&storage);
+
+  let detail_view = match Rc::clone(&harness.view).handle_key('\n') {
+    ViewResult::PushView(view) => view,
+    _ => panic!("Enter should open the task detail view"),
+  };
+  // Takes the last reference, so the view is gone afterwards.
+  detail_view.handle_key('x');
+  assert!(event_types(&storage, task.id).is_empty());
+
+  harness.type_keys("u");
+  assert_eq!(
+    event_types(&storage, task.id),
+    vec![TaskEventType::TaskEffortRecorded]
+  );
+}
+
+#[test]
+fn interrupting_the_prompt_creates_nothing() {
+  let harness = Harness::new(&storage_with_two_tasks());
+  let lines_before = harness.terminal.lines();
+
+  harness.terminal.type_keys("o");
+  harness.terminal.push_input(ScriptedInput::Interrupt);
+  harness.run();
+  assert_eq!(harness.terminal.lines(), lines_before);
+}
+
+#[test]
+fn completing_a_task_removes_it() {
+  let harness = Harness::new(&storage_with_two_tasks());
+
+  harness.type_keys("c");
+  assert!(!harness.shows_task("Schedule flu shot"));
+  assert!(harness.is_highlighted("Review budget"));
+}
+
+#[test]
+fn raising_priority_shows_in_the_row() {
+  let harness = Harness::new(&storage_with_two_tasks());
+
+  harness.type_keys("jP");
+  let row = harness.row("Review budget");
+  assert!(harness.terminal.lines()[row].contains("| Med "));
+}
+
+#[test]
+fn score_breakdown_column_toggles() {
+  let harness = Harness::new(&storage_with_two_tasks());
+  let header = harness.row("title");
+  assert!(!harness.terminal.lines()[header].contains("score"));
+
+  harness.type_keys("s");
+  assert!(harness.terminal.lines()[header].contains("score"));
+
+  harness.type_keys("s");
+  assert!(!harness.terminal.lines()[header].contains("score"));
+}
+
+#[test]
+fn errors_show_on_the_status_line_until_next_key() {
+  let harness = Harness::new(&storage_with_two_tasks());
+
+  let err = Error::NoSuchRow { table: "tasks", id: 99 };
+  harness.view.task_results_window.show_error(&err);
+  assert_eq!(
+    harness.status_line(),
+    "Error: No row with id 99 in tasks | no filter | 2 tasks | undo 0/0"
+  );
+
+  harness.type_keys("j");
+  assert_eq!(harness.status_line(), "no filter | 2 tasks | undo 0/0");
+  assert!(harness.is_highlighted("Review budget"));
+}
+
+#[test]
+fn status_line_says_what_happened_and_tracks_undo() {
+  let storage = common::storage();
+  let task = storage.create_task("Review budget").unwrap();
+  let harness = Harness::new(&storage);
+
+  harness.type_keys("r");
+  assert_eq!(
+    harness.status_line(),
+    format!("Recorded effort on #{} | no filter | 1 task | undo 1/1", task.id)
+  );
+
+  harness.type_keys("P");
+  assert_eq!(
+    harness.status_line(),
+    format!(
+      "Priority Low\u{2192}Medium on #{} | no filter | 1 task | undo 2/2",
+      task.id
+    )
+  );
+
+  harness.type_keys("u");
+  assert_eq!(
+    harness.status_line(),
+    format!(
+      "Undid: priority Low\u{2192}Medium on #{} | no filter | 1 task | undo 1/2",
+      task.id
+    )
+  );
+}
+
+#[test]
+fn jumping_to_a_missing_task_says_so() {
+  let harness = Harness::new(&storage_with_two_tasks());
+
+  harness.terminal.type_keys("#");
+  harness.terminal.type_line("99");
+  harness.run();
+  assert!(harness.status_line().starts_with("No task with id 99 | "));
+  assert!(harness.is_highlighted("Schedule flu shot"));
+
+  harness.terminal.type_keys("#");
+  harness.terminal.type_line("abc");
+  harness.run();
+  assert!(harness.status_line().starts_with("Not a task id: abc | "));
+}
+
+#[test]
+fn task_actions_can_be_undone_after_a_restart() {
+  let storage = storage_with_two_tasks();
+  {
+    let harness = Harness::new(&storage);
+    harness.type_keys("c");
+    harness.terminal.type_keys("F");
+    harness.terminal.type_line("no");
+    harness.run();
+    harness.type_keys("Pu");
+    assert!(harness.status_line().ends_with("undo 2/3"));
+  }
+
+  // The filter is gone, but the completion and the undone priority
+  // change are still there.
+  let harness = Harness::new(&storage);
+  assert_eq!(harness.status_line(), "no filter | 1 task | undo 1/2");
+
+  harness.type_keys("U");
+  assert!(harness.status_line().starts_with("Redid: priority"));
+  let row = harness.row("Review budget");
+  assert!(harness.terminal.lines()[row].contains("| Med "));
+
+  harness.type_keys("uu");
+  assert!(harness.shows_task("Schedule flu shot"));
+  assert!(harness.status_line().ends_with("undo 0/2"));
+}
+
+#[test]
+fn created_tasks_can_be_undone_after_a_restart() {
+  let storage = storage_with_two_tasks();
+  {
+    let harness = Harness::new(&storage);
+    harness.terminal.type_keys("o");
+    harness.terminal.type_line("Buy distilled water");
+    harness.run();
+    harness.row("Buy distilled water");
+  }
+
+  let harness = Harness::new(&storage);
+  assert_eq!(harness.status_line(), "no filter | 3 tasks | undo 1/1");
+
+  harness.type_keys("u");
+  assert!(harness.status_line().starts_with("Undid: created"));
+  assert!(!harness.shows_task("Buy distilled water"));
+}
+
+#[test]
+fn unreadable_logged_actions_are_skipped_after_a_restart() {
+  let storage = storage_with_two_tasks();
+  Harness::new(&storage).type_keys("c");
+  let task_id = available_task_ids(&storage)[0];
+  storage
+    .log_action(&NewActionLogEntry {
+      kind: ActionLogKind::UpdatePriority,
+      task_id,
+      old_value: Some(String::from("Low")),
+      new_value: Some(String::from("Urgent")),
+      task_event_id: None,
+      spawned_task_id: None,
+      parent_id: Some(1),
+      batch_id: None,
+    }).unwrap();
+
+  // The completion can still be undone.
+  let harness = Harness::new(&storage);
+  assert_eq!(
+    harness.status_line(),
+    "Error: Can't read action log entry 2 | no filter | 1 task | undo 1/1"
+  );
+  harness.type_keys("u");
+  assert!(harness.shows_task("Schedule flu shot"));
+}
+
+#[test]
+fn undone_branches_survive_a_restart() {
+  let storage = common::storage();
+  storage.create_task("Review budget").unwrap();
+  Harness::new(&storage).type_keys("rPul");
+
+  let harness = Harness::new(&storage);
+  assert!(harness.status_line().ends_with("undo 2/2"));
+
+  // Back in time is the priority change, on the branch the delay
+  // replaced.
+  harness.type_keys("-");
+  let row = harness.row("Review budget");
+  assert!(harness.terminal.lines()[row].contains("| Med "));
+  assert!(harness.status_line().ends_with("undo 2/2"));
+
+  harness.type_keys("+");
+  let row = harness.row("Review budget");
+  assert!(harness.terminal.lines()[row].contains("| Low "));
+}
+
+#[test]
+fn applying_to_all_listed_tasks_is_one_undo_step() {
+  let storage = storage_with_two_tasks();
+  let harness = Harness::new(&storage);
+
+  harness.type_keys("*");
+  assert!(
+    harness
+      .status_line()
+      .starts_with("Next task command applies to all 2 listed | ")
+  );
+  harness.type_keys("c");
+  assert!(!harness.shows_task("Schedule flu shot"));
+  assert!(!harness.shows_task("Review budget"));
+  assert!(harness.status_line().ends_with("| 0 tasks | undo 1/1"));
+
+  harness.type_keys("u");
+  assert!(harness.shows_task("Schedule flu shot"));
+  assert!(harness.shows_task("Review budget"));
+
+  // Still one step after a restart.
+  harness.type_keys("U");
+  let harness = Harness::new(&storage);
+  harness.type_keys("u");
+  assert!(harness.shows_task("Schedule flu shot"));
+  assert!(harness.shows_task("Review budget"));
+  assert!(harness.status_line().ends_with("| 2 tasks | undo 0/1"));
+
+  harness.type_keys("*e");
+  assert!(harness
+    .status_line()
+    .starts_with("Can't apply that to all tasks | "));
+}
+
+#[test]
+fn batches_longer_than_the_loaded_log_stay_one_undo_step() {
+  // More entries than the 100 actions reloaded after a restart.
+  let storage = common::storage();
+  for num in 1..=120 {
+    storage.create_task(&format!("Task {:03}", num)).unwrap();
+  }
+  Harness::new(&storage).type_keys("*c");
+  assert!(available_task_ids(&storage).is_empty());
+
+  let harness = Harness::new(&storage);
+  assert!(harness.status_line().ends_with("| 0 tasks | undo 1/1"));
+  harness.type_keys("u");
+  assert_eq!(available_task_ids(&storage).len(), 120);
+  assert!(harness.status_line().ends_with(" of 120 tasks | undo 0/1"));
+}
+
+#[test]
+fn filters_combine_and_undo() {
+  let harness = Harness::new(&storage_with_two_tasks());
+
+  harness.terminal.type_keys("ft");
+  harness.terminal.type_line("flu");
+  harness.run();
+  assert_eq!(
+    harness.status_line(),
+    "Filter none\u{2192}\"flu\" | \"flu\" | 1 task | undo 1/1"
+  );
+  assert!(!harness.shows_task("Review budget"));
+
+  harness.terminal.type_keys("fa");
+  harness.terminal.type_line("3h");
+  harness.run();
+  harness.type_keys("f!");
+  assert!(harness.status_line().ends_with(
+    "| \"flu\" -age>3h | 0 tasks | undo 3/3"
+  ));
+
+  harness.type_keys("f|");
+  assert!(harness.status_line().ends_with(
+    "| \"flu\" or -age>3h | 2 tasks | undo 4/4"
+  ));
+
+  harness.terminal.type_keys("fp");
+  harness.terminal.type_line("urgent");
+  harness.run();
+  assert!(harness.status_line().starts_with("Not a priority: urgent | "));
+
+  harness.type_keys("uuu");
+  assert!(
+    harness
+      .status_line()
+      .ends_with("| \"flu\" | 1 task | undo 1/4")
+  );
+  harness.type_keys("fc");
+  assert_eq!(harness.status_line().split(" | ").nth(1), Some("no filter"));
+  assert!(harness.shows_task("Review budget"));
+}
+
+#[test]
+fn filter_queries_are_typed_at_the_command_line() {
+  let harness = Harness::new(&storage_with_two_tasks());
+
+  harness.terminal.type_keys(":");
+  harness.terminal.type_line("filter age>3h -net");
+  harness.run();
+  assert!(!harness.shows_task("Review budget"));
+  assert!(harness.header().ends_with(":filter age>3h -net"));
+
+  harness.terminal.type_keys(":");
+  harness.terminal.type_line("filter age>3h or");
+  harness.run();
+  assert!(harness.status_line().starts_with("Filter ends too soon | "));
+  assert_eq!(harness.terminal.last_history(), vec!["filter age>3h -net"]);
+
+  harness.terminal.type_keys(":");
+  harness.terminal.type_line("filter");
+  harness.run();
+  assert!(harness.shows_task("Review budget"));
+  assert!(!harness.header().contains(":filter"));
+  assert_eq!(
+    harness.terminal.last_history(),
+    vec!["filter age>3h -net", "filter age>3h or"]
+  );
+
+  harness.terminal.type_keys(":");
+  harness.terminal.type_line("sort");
+  harness.run();
+  assert!(harness.status_line().starts_with("Unknown command: sort | "));
+}
+
+#[test]
+fn filter_presets_are_saved_and_swapped_atomically() {
+  let storage = storage_with_two_tasks();
+  let harness = Harness::new(&storage);
+
+  harness.type_keys("fS");
+  assert!(harness.status_line().starts_with("No filter to save | "));
+
+  for (query, name) in &[("\"flu\"", "train"), ("age<3h", "desk")] {
+    harness.terminal.type_keys(":");
+    harness.terminal.type_line(&format!("filter {}", query));
+    harness.terminal.type_keys("fS");
+    harness.terminal.type_line(name);
+    harness.run();
+    assert!(
+      harness
+        .status_line()
+        .starts_with(&format!("Saved filter as {} | {} | ", name, query))
+    );
+  }
+  let names: Vec<_> = storage
+    .filter_presets()
+    .unwrap()
+    .into_iter()
+    .map(|preset| preset.name)
+    .collect();
+  assert_eq!(names, vec!["desk", "train"]);
+
+  // By number, in name order.
+  harness.terminal.type_keys("fs");
+  harness.terminal.type_line("2");
+  harness.run();
+  assert_eq!(
+    harness.status_line(),
+    "Filter age<3h\u{2192}[train] | [train] \"flu\" | 1 task | undo 3/3"
+  );
+  assert!(harness.header().ends_with(":filter \"flu\"   [train]"));
+
+  harness.terminal.type_keys("fs");
+  harness.terminal.type_line("desk");
+  harness.run();
+  assert!(
+    harness
+      .status_line()
+      .ends_with("| [desk] age<3h | 1 task | undo 4/4")
+  );
+  assert!(harness.shows_task("Review budget"));
+
+  harness.type_keys("u");
+  assert_eq!(
+    harness.status_line(),
+    "Undid: filter [train]\u{2192}[desk] | [train] \"flu\" | 1 task | \
+     undo 3/4"
+  );
+
+  // Changing the filter leaves the preset behind.
+  harness.type_keys("f!");
+  assert!(
+    harness
+      .status_line()
+      .ends_with("| -\"flu\" | 1 task | undo 4/4")
+  );
+
+  harness.terminal.type_keys("fs");
+  harness.terminal.type_line("home");
+  harness.run();
+  assert!(harness.status_line().starts_with("No preset: home | "));
+
+  harness.terminal.type_keys("fx");
+  harness.terminal.type_line("train");
+  harness.run();
+  assert!(harness.status_line().starts_with("Deleted preset train | "));
+  assert_eq!(storage.filter_presets().unwrap().len(), 1);
+}
+
+#[test]
+fn title_search_moves_as_you_type_and_repeats() {
+  let storage = common::memory_storage();
+  create_aged_task(&storage, "Review budget", Duration::hours(1));
+  create_aged_task(&storage, "Buy shoes", Duration::hours(3));
+  create_aged_task(&storage, "Schedule flu shot", Duration::hours(5));
+  let storage: Rc<dyn Storage> = storage;
+  let harness = Harness::new(&storage);
+
+  harness.type_keys("n");
+  assert!(harness.status_line().starts_with("No search to repeat | "));
+
+  harness.type_keys("/bu\n");
+  assert!(harness.is_highlighted("Buy shoes"));
+  assert!(harness.status_line().starts_with("/bu 1 of 2 | "));
+  // The other match stands out too.
+  let budget_row = harness.row("Review budget");
+  assert_eq!(harness.terminal.row_color(budget_row), ColorPair::Bold);
+  let flu_row = harness.row("Schedule flu shot");
+  assert_eq!(harness.terminal.row_color(flu_row), ColorPair::Default);
+
+  harness.type_keys("n");
+  assert!(harness.is_highlighted("Review budget"));
+  assert!(harness.status_line().starts_with("/bu 2 of 2 | "));
+  harness.type_keys("n");
+  assert!(harness.is_highlighted("Buy shoes"));
+  harness.type_keys("N");
+  assert!(harness.is_highlighted("Review budget"));
+
+  // Escape goes back to where the search started and keeps the old
+  // search for `n`.
+  harness.type_keys("g");
+  harness.type_keys("/bx\u{7f}d\u{1b}");
+  assert!(harness.is_highlighted("Schedule flu shot"));
+  harness.type_keys("n");
+  assert!(harness.is_highlighted("Buy shoes"));
+
+  harness.type_keys("/bx\u{7f}d\n");
+  assert!(harness.is_highlighted("Review budget"));
+  assert!(harness.status_line().starts_with("/bd 1 of 1 | "));
+
+  harness.type_keys("/zzz\n");
+  assert!(harness.status_line().starts_with("No title matches /zzz | "));
+  assert!(harness.is_highlighted("Review budget"));
+}
+
+#[test]
+fn long_lists_page_and_keep_the_current_task_on_screen() {
+  let storage = common::memory_storage();
+  for num in 1..=40 {
+    let title = format!("Task {:02}", num);
+    create_aged_task(&storage, &title, Duration::hours(100 - num));
+  }
+  let storage: Rc<dyn Storage> = storage;
+  // Room for the column names and 27 tasks.
+  let harness = Harness::new(&storage);
+  let showing = |range: &str| {
+    harness
+      .status_line()
+      .ends_with(&format!("| showing {} of 40 tasks | undo 0/0", range))
+  };
+
+  assert!(showing("1\u{2013}27"));
+  assert!(harness.shows_task("Task 27"));
+  assert!(!harness.shows_task("Task 28"));
+
+  harness.type_keys("$");
+  assert!(harness.is_highlighted("Task 40"));
+  assert!(showing("14\u{2013}40"));
+  assert!(!harness.shows_task("Task 13"));
+
+  // Half a page moves the view along with the current task.
+  harness.type_keys("\u{15}");
+  assert!(harness.is_highlighted("Task 27"));
+  assert!(showing("1\u{2013}27"));
+  harness.type_keys("\u{4}");
+  assert!(harness.is_highlighted("Task 40"));
+
+  harness.type_keys("g\u{6}");
+  assert!(harness.is_highlighted("Task 28"));
+  assert!(showing("14\u{2013}40"));
+  harness.type_keys("\u{2}");
+  assert!(harness.is_highlighted("Task 01"));
+  assert!(showing("1\u{2013}27"));
+
+  // Stepping past the bottom row brings one more task into view.
+  harness.type_keys(&"j".repeat(27));
+  assert!(harness.is_highlighted("Task 28"));
+  assert!(showing("2\u{2013}28"));
+}
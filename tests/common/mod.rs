@@ -0,0 +1,45 @@
+// Shared by the test files. Not every file uses every helper.
+#![allow(dead_code)]
+
+use chrono::{Duration, Utc};
+use std::rc::Rc;
+use task_manager::models::{Task, TaskEventType};
+use task_manager::storage::{MemoryStorage, Storage};
+
+pub fn storage() -> Rc<dyn Storage> {
+  Rc::new(MemoryStorage::new())
+}
+
+// For tests that backdate tasks before handing the storage on.
+pub fn memory_storage() -> Rc<MemoryStorage> {
+  Rc::new(MemoryStorage::new())
+}
+
+// A task that has been aging for `age`, so scores differ noticeably.
+pub fn create_aged_task(
+  storage: &MemoryStorage,
+  title: &str,
+  age: Duration,
+) -> Task {
+  storage.create_task_at(title, Utc::now() - age)
+}
+
+pub fn event_types(
+  storage: &Rc<dyn Storage>,
+  task_id: i32,
+) -> Vec<TaskEventType> {
+  let task = storage.find_task(task_id).unwrap();
+  storage
+    .task_events(&task).unwrap()
+    .into_iter()
+    .map(|task_event| task_event.event_type)
+    .collect()
+}
+
+pub fn available_task_ids(storage: &Rc<dyn Storage>) -> Vec<i32> {
+  storage
+    .all_available_to_perform().unwrap()
+    .into_iter()
+    .map(|task| task.id)
+    .collect()
+}
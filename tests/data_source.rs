@@ -0,0 +1,208 @@
+extern crate chrono;
+extern crate task_manager;
+
+mod common;
+
+use chrono::Duration;
+use common::create_aged_task;
+use std::cell::RefCell;
+use std::rc::Rc;
+use task_manager::actions::{FiltererAction, ForwardAction, ReversableAction};
+use task_manager::components::{
+  DataSource, FilterPredicate, Filterer, TaskResult,
+};
+use task_manager::config::ScoringConfig;
+use task_manager::models::TaskPriority;
+use task_manager::storage::Storage;
+
+type ResultsVec = Rc<Vec<TaskResult>>;
+
+// A DataSource feeding a Filterer, wired up like ActiveTasksView does.
+// The returned cell holds whatever the Filterer last pushed.
+fn pipeline() -> (DataSource, Rc<Filterer>, Rc<RefCell<ResultsVec>>) {
+  let filtered_results = Rc::new(RefCell::new(Rc::new(vec![])));
+
+  let mut filterer = Filterer::new();
+  {
+    let filtered_results = Rc::clone(&filtered_results);
+    filterer.add_callback(Box::new(move |results, _event| {
+      *filtered_results.borrow_mut() = Rc::clone(results);
+    }));
+  }
+  let filterer = Rc::new(filterer);
+
+  let mut data_source = DataSource::new(ScoringConfig::default());
+  {
+    let filterer = Rc::clone(&filterer);
+    data_source.add_callback(Box::new(move |results| {
+      filterer.refresh(results);
+    }));
+  }
+
+  (data_source, filterer, filtered_results)
+}
+
+fn titles(results: &Rc<RefCell<ResultsVec>>) -> Vec<String> {
+  results
+    .borrow()
+    .iter()
+    .map(|result| result.task.title.clone())
+    .collect()
+}
+
+#[test]
+fn pull_orders_tasks_by_age() {
+  let storage = common::memory_storage();
+  create_aged_task(&storage, "newer", Duration::hours(1));
+  create_aged_task(&storage, "older", Duration::hours(5));
+  let (data_source, _filterer, results) = pipeline();
+
+  data_source.pull(&*storage).unwrap();
+  assert_eq!(titles(&results), vec!["older", "newer"]);
+}
+
+#[test]
+fn recording_effort_resets_age() {
+  let storage = common::memory_storage();
+  create_aged_task(&storage, "newer", Duration::hours(1));
+  let older = create_aged_task(&storage, "older", Duration::hours(5));
+  let (data_source, _filterer, results) = pipeline();
+
+  storage.record_task_effort(older.id).unwrap();
+  data_source.pull(&*storage).unwrap();
+  assert_eq!(titles(&results), vec!["newer", "older"]);
+
+  // An age reset counts the same as effort.
+  let newer_id = results.borrow()[0].task.id;
+  storage.request_task_age_reset(newer_id).unwrap();
+  data_source.pull(&*storage).unwrap();
+  assert!(results
+    .borrow()
+    .iter()
+    .all(|result| result.task_effort_age < Duration::minutes(1)));
+}
+
+#[test]
+fn delays_lower_the_score() {
+  let storage = common::memory_storage();
+  let task = create_aged_task(&storage, "task", Duration::hours(5));
+  let (data_source, _filterer, results) = pipeline();
+
+  data_source.pull(&*storage).unwrap();
+  let score_before = results.borrow()[0].score;
+  assert_eq!(score_before.delay_deduction, 0);
+
+  storage.request_delay(task.id).unwrap();
+  data_source.pull(&*storage).unwrap();
+  let score_after = results.borrow()[0].score;
+  assert!(score_after.delay_deduction > 0);
+  assert!(score_after.total < score_before.total);
+}
+
+#[test]
+fn each_delay_pushes_a_task_back_a_day() {
+  let storage = common::memory_storage();
+  create_aged_task(&storage, "newer", Duration::hours(2));
+  let older = create_aged_task(&storage, "older", Duration::hours(25));
+  let (data_source, _filterer, results) = pipeline();
+
+  storage.request_delay(older.id).unwrap();
+  data_source.pull(&*storage).unwrap();
+  assert_eq!(titles(&results), vec!["newer", "older"]);
+  assert_eq!(
+    results.borrow()[1].score.delay_deduction,
+    Duration::days(1).num_milliseconds()
+  );
+}
+
+#[test]
+fn pull_skips_destroyed_tasks_and_events() {
+  let storage = common::memory_storage();
+  let task = create_aged_task(&storage, "kept", Duration::hours(5));
+  let destroyed = storage.create_task("destroyed").unwrap();
+  storage.update_task_destroyed(destroyed.id, true).unwrap();
+  let task_event = storage.record_task_effort(task.id).unwrap();
+  storage.update_task_event_destroyed(task_event.id, true).unwrap();
+  let (data_source, _filterer, results) = pipeline();
+
+  data_source.pull(&*storage).unwrap();
+  assert_eq!(titles(&results), vec!["kept"]);
+  assert!(results.borrow()[0].task_events.is_empty());
+}
+
+#[test]
+fn filterer_action_filters_and_unfilters() {
+  let storage = common::memory_storage();
+  let online = create_aged_task(&storage, "online", Duration::hours(5));
+  storage.update_requires_internet(online.id, true).unwrap();
+  create_aged_task(&storage, "offline", Duration::hours(1));
+  let (data_source, filterer, results) = pipeline();
+  data_source.pull(&*storage).unwrap();
+
+  let mut action = FiltererAction::UpdateCriteria {
+    new_value: Some(FilterPredicate::RequiresInternet(false)),
+    new_preset: None,
+    old_value: None,
+    old_preset: None,
+    filterer: Rc::clone(&filterer),
+  };
+  // Criteria take effect on the next pull.
+  action.execute().unwrap();
+  data_source.pull(&*storage).unwrap();
+  assert_eq!(titles(&results), vec!["offline"]);
+
+  action.unexecute().unwrap();
+  data_source.pull(&*storage).unwrap();
+  assert_eq!(titles(&results), vec!["online", "offline"]);
+
+  filterer
+    .set_criteria(Some(FilterPredicate::RequiresInternet(true)), None);
+  data_source.pull(&*storage).unwrap();
+  assert_eq!(titles(&results), vec!["online"]);
+}
+
+#[test]
+fn filter_predicates_combine() {
+  use task_manager::components::FilterPredicate::*;
+
+  let storage = common::memory_storage();
+  let urgent = create_aged_task(&storage, "urgent call", Duration::days(3));
+  storage.update_priority(urgent.id, TaskPriority::High).unwrap();
+  let delayed = create_aged_task(&storage, "taxes", Duration::days(2));
+  storage.request_delay(delayed.id).unwrap();
+  storage.request_delay(delayed.id).unwrap();
+  let worked_on = create_aged_task(&storage, "garden", Duration::hours(1));
+  storage.record_task_effort(worked_on.id).unwrap();
+  let (data_source, filterer, results) = pipeline();
+
+  let filter = |criteria: FilterPredicate| {
+    filterer.set_criteria(Some(criteria), None);
+    data_source.pull(&*storage).unwrap();
+    titles(&results)
+  };
+
+  // "garden" is low priority like "taxes", but was just worked on.
+  let low_and_old =
+    PriorityAtMost(TaskPriority::Medium).and(OlderThan(Duration::days(1)));
+  assert_eq!(filter(low_and_old.clone()), vec!["taxes"]);
+  assert_eq!(
+    filter(low_and_old.clone().negate()),
+    vec!["urgent call", "garden"]
+  );
+  assert_eq!(filter(low_and_old.clone().negate_last()), vec!["garden"]);
+
+  let delayed_or_urgent = DelayedAtLeast(2).and(TitleContains("URGENT".into()));
+  assert_eq!(filter(delayed_or_urgent.clone()), Vec::<String>::new());
+  let delayed_or_urgent = delayed_or_urgent.or_last().unwrap();
+  assert_eq!(filter(delayed_or_urgent.clone()), vec!["urgent call", "taxes"]);
+  assert_eq!(
+    delayed_or_urgent.to_string(),
+    "delays>=2 or \"URGENT\""
+  );
+
+  assert_eq!(filter(HasRecentEffort(Duration::minutes(5))), vec!["garden"]);
+  assert_eq!(
+    low_and_old.negate().and(RequiresInternet(false)).to_string(),
+    "-(prio<=med age>1d) -net"
+  );
+}
@@ -0,0 +1,57 @@
+extern crate chrono;
+extern crate task_manager;
+
+use chrono::Duration;
+use task_manager::components::parse_filter_query;
+use task_manager::components::FilterPredicate::*;
+use task_manager::models::{TaskDuration, TaskPriority};
+
+#[test]
+fn terms_next_to_each_other_all_hold() {
+  let criteria =
+    parse_filter_query("prio>=med dur:short -net age>3d \"taxes\"").unwrap();
+
+  let expected = PriorityAtLeast(TaskPriority::Medium)
+    .and(DurationIn(vec![TaskDuration::Short]))
+    .and(RequiresInternet(false))
+    .and(OlderThan(Duration::days(3)))
+    .and(TitleContains(String::from("taxes")));
+  assert_eq!(criteria, Some(expected));
+}
+
+#[test]
+fn queries_print_the_way_they_parse() {
+  for query in &[
+    "prio>=med dur:short,long -net age>3d \"taxes\"",
+    "delays>=2 or effort<1w prio<=low",
+    "(net or age<12h) -(\"say \\\"hi\\\"\" or dur:med)",
+    "-net -(prio>=high age>30m)",
+  ] {
+    let criteria = parse_filter_query(query).unwrap().unwrap();
+    assert_eq!(&criteria.to_string(), query);
+  }
+
+  // Spelled out the long way.
+  let criteria =
+    parse_filter_query("not net and (prio:medium)").unwrap().unwrap();
+  assert_eq!(criteria.to_string(), "-net (prio>=med prio<=med)");
+}
+
+#[test]
+fn an_empty_query_filters_nothing() {
+  assert_eq!(parse_filter_query("  "), Ok(None));
+}
+
+#[test]
+fn bad_queries_say_what_is_wrong() {
+  let error = |query| parse_filter_query(query).unwrap_err();
+
+  assert_eq!(error("prio>=urgent"), "Not a priority: urgent");
+  assert_eq!(error("age>3 days"), "Not a span of time: 3");
+  assert_eq!(error("taxes"), "Unknown filter: taxes");
+  assert_eq!(error("prio<med"), "Unknown filter: prio<med");
+  assert_eq!(error("(net"), "Missing )");
+  assert_eq!(error("net)"), "Unexpected )");
+  assert_eq!(error("net or"), "Filter ends too soon");
+  assert_eq!(error("\"taxes"), "Unclosed quote");
+}
@@ -0,0 +1,131 @@
+extern crate chrono;
+extern crate task_manager;
+
+mod common;
+
+use std::collections::HashMap;
+use std::env;
+use std::fs::{self, File};
+use std::process;
+use std::rc::Rc;
+use std::time::Duration;
+use task_manager::application::Application;
+use task_manager::commands::ActiveTasksViewCommand;
+use task_manager::config::Config;
+use task_manager::storage::Storage;
+use task_manager::util::terminal::{
+  format_input_log_entry, parse_input_log, read_input_log, HeadlessTerminal,
+  InputLogError, RecordingTerminal, ReplayTerminal, ScriptedInput, Terminal,
+};
+use task_manager::util::UserInterface;
+
+fn run_application(terminal: Rc<dyn Terminal>, storage: &Rc<dyn Storage>) {
+  let config = Config::default();
+  let keymap = ActiveTasksViewCommand::keymap(&HashMap::new()).unwrap();
+  let ui = Rc::new(UserInterface::new(terminal));
+
+  Application::new(&config, keymap, &ui, storage).run();
+}
+
+fn task_titles(storage: &Rc<dyn Storage>) -> Vec<String> {
+  storage
+    .all_available_to_perform().unwrap()
+    .into_iter()
+    .map(|task| task.title)
+    .collect()
+}
+
+#[test]
+fn log_entries_round_trip() {
+  let inputs = vec![
+    ScriptedInput::Key('n'),
+    ScriptedInput::Key('\n'),
+    ScriptedInput::Key('\t'),
+    ScriptedInput::Key('\\'),
+    ScriptedInput::Line(String::from("Tabs\tand \\slashes\\ and émigrés")),
+    ScriptedInput::Line(String::new()),
+    ScriptedInput::Interrupt,
+    ScriptedInput::NoKey,
+  ];
+
+  let log: String = inputs
+    .iter()
+    .enumerate()
+    .map(|(idx, input)| {
+      format_input_log_entry(Duration::from_millis(idx as u64 * 250), input)
+    }).collect();
+
+  assert_eq!(log.lines().count(), inputs.len());
+  assert_eq!(parse_input_log(&log).unwrap(), inputs);
+}
+
+#[test]
+fn bad_log_entries_are_reported_by_line() {
+  let log = "10\tkey\tn\n20\tkey\tnn\n";
+
+  match parse_input_log(log) {
+    Err(InputLogError::BadEntry { line_number, .. }) => {
+      assert_eq!(line_number, 2)
+    }
+    _ => panic!("Expected a bad entry"),
+  }
+}
+
+#[test]
+fn recorded_session_replays() {
+  let path = env::temp_dir().join(format!("tm-replay-{}.log", process::id()));
+
+  // Record a session that creates two tasks, gives up on creating a
+  // third, searches for one with a failed read ending the search,
+  // completes it, and quits.
+  let storage = common::storage();
+  {
+    let headless = Rc::new(HeadlessTerminal::new(30, 160));
+    headless.type_keys("o");
+    headless.type_line("Buy distilled water");
+    headless.type_keys("o");
+    headless.type_line("Call the vet");
+    headless.type_keys("o");
+    headless.push_input(ScriptedInput::Interrupt);
+    headless.type_keys("/Buy");
+    headless.push_input(ScriptedInput::NoKey);
+    headless.type_keys("cq");
+    let log = File::create(&path).unwrap();
+    let terminal = RecordingTerminal::new(headless, log);
+
+    run_application(Rc::new(terminal), &storage);
+  }
+  assert_eq!(task_titles(&storage), vec!["Call the vet"]);
+
+  // Replaying it against a fresh storage does the same again.
+  let inputs = read_input_log(&path).unwrap();
+  fs::remove_file(&path).unwrap();
+  assert_eq!(inputs.len(), 13);
+
+  let replayed_storage = common::storage();
+  let headless = Rc::new(HeadlessTerminal::new(30, 160));
+  let terminal = ReplayTerminal::new(Rc::clone(&headless) as Rc<_>, inputs);
+  run_application(Rc::new(terminal), &replayed_storage);
+
+  assert_eq!(task_titles(&replayed_storage), vec!["Call the vet"]);
+  assert!(headless.find_row("Call the vet").is_some());
+}
+
+#[test]
+fn replay_hands_over_to_the_keyboard_once_the_log_diverges() {
+  // A prompt answer where the app wants a key, then a key it would
+  // otherwise replay.
+  let inputs = vec![
+    ScriptedInput::Line(String::from("Buy distilled water")),
+    ScriptedInput::Key('o'),
+  ];
+
+  let storage = common::storage();
+  let headless = Rc::new(HeadlessTerminal::new(30, 160));
+  headless.type_keys("q");
+  let terminal = ReplayTerminal::new(Rc::clone(&headless) as Rc<_>, inputs);
+  run_application(Rc::new(terminal), &storage);
+
+  assert!(task_titles(&storage).is_empty());
+  assert!(!headless.has_pending_input());
+}
@@ -0,0 +1,355 @@
+extern crate chrono;
+extern crate task_manager;
+
+mod common;
+
+use common::{available_task_ids, event_types};
+use std::rc::Rc;
+use task_manager::actions::{
+  CompositeAction, ForwardAction, ReversableAction, TaskAction,
+  TaskUpdateAction,
+};
+use task_manager::components::UndoBuffer;
+use task_manager::error::Error;
+use task_manager::models::{TaskEventType, TaskPriority};
+use task_manager::storage::Storage;
+
+fn create_task_action(storage: &Rc<dyn Storage>, title: &str) -> TaskAction {
+  TaskAction::CreateTask {
+    task_title: title.to_string(),
+    task: None,
+    storage: Rc::clone(storage),
+  }
+}
+
+fn created_task_id(action: &TaskAction) -> i32 {
+  match action {
+    TaskAction::CreateTask { task: Some(task), .. } => task.id,
+    _ => panic!("Expected an executed create action"),
+  }
+}
+
+fn event_id(action: &TaskAction) -> i32 {
+  match action {
+    TaskAction::RecordTaskEffort {
+      task_event: Some(task_event),
+      ..
+    }
+    | TaskAction::RequestTaskAgeReset {
+      task_event: Some(task_event),
+      ..
+    }
+    | TaskAction::RequestTaskDelay {
+      task_event: Some(task_event),
+      ..
+    } => task_event.id,
+    _ => panic!("Expected an executed task event action"),
+  }
+}
+
+// Executes, undoes and redoes an action that records one event.
+fn check_event_round_trip(
+  storage: &Rc<dyn Storage>,
+  mut action: TaskAction,
+  task_id: i32,
+  event_type: TaskEventType,
+) {
+  action.execute().unwrap();
+  assert_eq!(event_types(storage, task_id), vec![event_type]);
+  let first_event_id = event_id(&action);
+
+  action.unexecute().unwrap();
+  assert!(event_types(storage, task_id).is_empty());
+
+  // Redo brings back the same event rather than recording another.
+  action.redo().unwrap();
+  assert_eq!(event_types(storage, task_id), vec![event_type]);
+  assert_eq!(event_id(&action), first_event_id);
+}
+
+#[test]
+fn create_task_round_trip() {
+  let storage = common::storage();
+  let mut action = create_task_action(&storage, "Buy distilled water");
+
+  action.execute().unwrap();
+  let task_id = created_task_id(&action);
+  assert_eq!(available_task_ids(&storage), vec![task_id]);
+  assert_eq!(storage.find_task(task_id).unwrap().title, "Buy distilled water");
+
+  action.unexecute().unwrap();
+  assert!(available_task_ids(&storage).is_empty());
+
+  action.redo().unwrap();
+  assert_eq!(available_task_ids(&storage), vec![task_id]);
+  assert_eq!(created_task_id(&action), task_id);
+}
+
+#[test]
+fn record_task_effort_round_trip() {
+  let storage = common::storage();
+  let task = storage.create_task("Review budget").unwrap();
+
+  let action = TaskAction::RecordTaskEffort {
+    task_id: task.id,
+    task_event: None,
+    storage: Rc::clone(&storage),
+  };
+  check_event_round_trip(
+    &storage,
+    action,
+    task.id,
+    TaskEventType::TaskEffortRecorded,
+  );
+}
+
+#[test]
+fn request_task_delay_round_trip() {
+  let storage = common::storage();
+  let task = storage.create_task("Schedule flu shot").unwrap();
+
+  let action = TaskAction::RequestTaskDelay {
+    task_id: task.id,
+    task_event: None,
+    storage: Rc::clone(&storage),
+  };
+  check_event_round_trip(
+    &storage,
+    action,
+    task.id,
+    TaskEventType::DelayRequested,
+  );
+}
+
+#[test]
+fn request_task_age_reset_round_trip() {
+  let storage = common::storage();
+  let task = storage.create_task("Look into glasses").unwrap();
+
+  let action = TaskAction::RequestTaskAgeReset {
+    task_id: task.id,
+    task_event: None,
+    storage: Rc::clone(&storage),
+  };
+  check_event_round_trip(
+    &storage,
+    action,
+    task.id,
+    TaskEventType::AgeResetRequested,
+  );
+}
+
+#[test]
+fn undo_buffer_undoes_and_redoes_in_order() {
+  let storage = common::storage();
+  let undo_buffer = UndoBuffer::new();
+
+  let mut task_ids = vec![];
+  for title in &["Find Zumba times", "Purchase singing bowls"] {
+    let mut action = create_task_action(&storage, title);
+    action.execute().unwrap();
+    task_ids.push(created_task_id(&action));
+    undo_buffer.append_action(Box::new(action));
+  }
+
+  undo_buffer.undo().unwrap();
+  assert_eq!(available_task_ids(&storage), vec![task_ids[0]]);
+  undo_buffer.undo().unwrap();
+  assert!(available_task_ids(&storage).is_empty());
+  // Nothing left to undo.
+  undo_buffer.undo().unwrap();
+  assert!(available_task_ids(&storage).is_empty());
+
+  undo_buffer.redo().unwrap();
+  assert_eq!(available_task_ids(&storage), vec![task_ids[0]]);
+  undo_buffer.redo().unwrap();
+  assert_eq!(available_task_ids(&storage), task_ids);
+  // Nothing left to redo.
+  undo_buffer.redo().unwrap();
+  assert_eq!(available_task_ids(&storage), task_ids);
+}
+
+#[test]
+fn undo_buffer_keeps_undone_branch_on_new_action() {
+  let storage = common::storage();
+  let undo_buffer = UndoBuffer::new();
+
+  let mut first = create_task_action(&storage, "Get drysack from Clark");
+  first.execute().unwrap();
+  let first_id = created_task_id(&first);
+  undo_buffer.append_action(Box::new(first));
+  undo_buffer.undo().unwrap();
+
+  let mut second = create_task_action(&storage, "Finish Zion planning");
+  second.execute().unwrap();
+  let second_id = created_task_id(&second);
+  undo_buffer.append_action(Box::new(second));
+
+  // Redo follows the newest branch.
+  undo_buffer.undo().unwrap();
+  assert!(available_task_ids(&storage).is_empty());
+  undo_buffer.redo().unwrap();
+  assert_eq!(available_task_ids(&storage), vec![second_id]);
+  assert_eq!(undo_buffer.position(), (1, 1));
+
+  // The undone create is still there to go back to by time.
+  undo_buffer.go_to_older().unwrap();
+  assert_eq!(available_task_ids(&storage), vec![first_id]);
+  assert_eq!(undo_buffer.current(), 1);
+  undo_buffer.go_to_older().unwrap();
+  assert!(available_task_ids(&storage).is_empty());
+  // Nothing older than the start.
+  undo_buffer.go_to_older().unwrap();
+  assert_eq!(undo_buffer.current(), 0);
+
+  undo_buffer.go_to_newer().unwrap();
+  undo_buffer.go_to_newer().unwrap();
+  assert_eq!(available_task_ids(&storage), vec![second_id]);
+  // Nothing newer either.
+  undo_buffer.go_to_newer().unwrap();
+  assert_eq!(undo_buffer.current(), 2);
+}
+
+#[test]
+fn actions_on_missing_tasks_fail() {
+  let storage = common::storage();
+  let mut action = TaskAction::RecordTaskEffort {
+    task_id: 99,
+    task_event: None,
+    storage: Rc::clone(&storage),
+  };
+
+  match action.execute() {
+    Err(Error::NoSuchRow { table: "tasks", id: 99 }) => (),
+    _ => panic!("Expected the missing task to be reported"),
+  }
+}
+
+#[test]
+fn failed_undo_keeps_undo_buffer_position() {
+  let storage = common::storage();
+  let undo_buffer = UndoBuffer::new();
+
+  let mut create = create_task_action(&storage, "Order new filters");
+  create.execute().unwrap();
+  let task_id = created_task_id(&create);
+  undo_buffer.append_action(Box::new(create));
+
+  // Refers to a task that does not exist, so can't be undone.
+  undo_buffer.append_action(Box::new(TaskAction::TaskUpdate(
+    TaskUpdateAction::UpdatePriority {
+      task_id: 99,
+      old_value: TaskPriority::Low,
+      new_value: TaskPriority::High,
+      storage: Rc::clone(&storage),
+    },
+  )));
+
+  // Undo keeps retrying the broken action rather than skipping past
+  // it to the create.
+  assert!(undo_buffer.undo().is_err());
+  assert!(undo_buffer.undo().is_err());
+  assert_eq!(available_task_ids(&storage), vec![task_id]);
+}
+
+#[test]
+fn undo_buffer_jumps_to_any_point_and_describes_actions() {
+  let storage = common::storage();
+  let undo_buffer = UndoBuffer::new();
+
+  let mut task_ids = vec![];
+  for title in &["Call the vet", "Renew passport", "Order ink"] {
+    let mut action = create_task_action(&storage, title);
+    action.execute().unwrap();
+    task_ids.push(created_task_id(&action));
+    undo_buffer.append_action(Box::new(action));
+  }
+  let descriptions: Vec<String> = undo_buffer
+    .tree()
+    .into_iter()
+    .map(|node| node.description)
+    .collect();
+  assert_eq!(
+    descriptions,
+    vec![
+      format!("created #{} \"Call the vet\"", task_ids[0]),
+      format!("created #{} \"Renew passport\"", task_ids[1]),
+      format!("created #{} \"Order ink\"", task_ids[2]),
+    ]
+  );
+
+  undo_buffer.go_to(1).unwrap();
+  assert_eq!(undo_buffer.position(), (1, 3));
+  assert_eq!(available_task_ids(&storage), vec![task_ids[0]]);
+
+  undo_buffer.go_to(3).unwrap();
+  assert_eq!(undo_buffer.position(), (3, 3));
+  assert_eq!(available_task_ids(&storage), task_ids);
+
+  undo_buffer.go_to(0).unwrap();
+  assert_eq!(undo_buffer.position(), (0, 3));
+  assert!(available_task_ids(&storage).is_empty());
+}
+
+fn record_effort_action(storage: &Rc<dyn Storage>, task_id: i32) -> TaskAction {
+  TaskAction::RecordTaskEffort {
+    task_id,
+    task_event: None,
+    storage: Rc::clone(storage),
+  }
+}
+
+#[test]
+fn composite_action_is_one_step() {
+  let storage = common::storage();
+  let first = storage.create_task("Refill soap").unwrap();
+  let second = storage.create_task("Oil the bike chain").unwrap();
+  let undo_buffer = UndoBuffer::new();
+
+  let mut action = CompositeAction::new(
+    vec![
+      record_effort_action(&storage, first.id),
+      record_effort_action(&storage, second.id),
+    ],
+    &storage,
+  );
+  action.execute().unwrap();
+  assert_eq!(
+    action.description(),
+    format!("recorded effort on #{} and 1 more", first.id)
+  );
+  undo_buffer.append_action(Box::new(action));
+  assert_eq!(undo_buffer.position(), (1, 1));
+
+  undo_buffer.undo().unwrap();
+  assert!(event_types(&storage, first.id).is_empty());
+  assert!(event_types(&storage, second.id).is_empty());
+
+  undo_buffer.redo().unwrap();
+  for task_id in &[first.id, second.id] {
+    assert_eq!(
+      event_types(&storage, *task_id),
+      vec![TaskEventType::TaskEffortRecorded]
+    );
+  }
+}
+
+#[test]
+fn failed_composite_action_changes_nothing() {
+  let storage = common::storage();
+  let task = storage.create_task("Refill soap").unwrap();
+
+  let mut action = CompositeAction::new(
+    vec![
+      record_effort_action(&storage, task.id),
+      record_effort_action(&storage, 99),
+    ],
+    &storage,
+  );
+
+  assert!(action.execute().is_err());
+  // The first effort was rolled back with the failed second one.
+  assert!(event_types(&storage, task.id).is_empty());
+  // Its id isn't handed out again, as with a database sequence.
+  assert_eq!(storage.record_task_effort(task.id).unwrap().id, 2);
+}
@@ -0,0 +1,281 @@
+extern crate chrono;
+extern crate task_manager;
+
+mod common;
+
+use common::{available_task_ids, event_types};
+use std::rc::Rc;
+use task_manager::actions::{
+  ForwardAction, ReversableAction, TaskAction, TaskUpdateAction,
+};
+use task_manager::error::Error;
+use task_manager::models::{
+  ActionLogKind, NewActionLogEntry, TaskDuration, TaskEventType,
+  TaskPriority, TaskRecurrence, TaskStatus,
+};
+
+// Wrapped the way the views run them.
+fn run(action: TaskUpdateAction) -> TaskAction {
+  let mut action = TaskAction::TaskUpdate(action);
+  action.execute().unwrap();
+  action
+}
+
+#[test]
+fn update_priority_round_trip() {
+  let storage = common::storage();
+  let task = storage.create_task("Review meditation retreats").unwrap();
+
+  let mut action = run(TaskUpdateAction::UpdatePriority {
+    task_id: task.id,
+    old_value: TaskPriority::Low,
+    new_value: TaskPriority::High,
+    storage: Rc::clone(&storage),
+  });
+  assert_eq!(storage.find_task(task.id).unwrap().priority, TaskPriority::High);
+
+  action.unexecute().unwrap();
+  assert_eq!(storage.find_task(task.id).unwrap().priority, TaskPriority::Low);
+
+  action.redo().unwrap();
+  assert_eq!(storage.find_task(task.id).unwrap().priority, TaskPriority::High);
+}
+
+#[test]
+fn update_duration_round_trip() {
+  let storage = common::storage();
+  let task = storage.create_task("Finish taxes payment").unwrap();
+
+  let mut action = run(TaskUpdateAction::UpdateDuration {
+    task_id: task.id,
+    old_value: TaskDuration::Short,
+    new_value: TaskDuration::Long,
+    storage: Rc::clone(&storage),
+  });
+  assert_eq!(storage.find_task(task.id).unwrap().duration, TaskDuration::Long);
+
+  action.unexecute().unwrap();
+  assert_eq!(storage.find_task(task.id).unwrap().duration, TaskDuration::Short);
+}
+
+#[test]
+fn update_requires_internet_round_trip() {
+  let storage = common::storage();
+  let task = storage.create_task("Fix lambda comments").unwrap();
+
+  let mut action = run(TaskUpdateAction::UpdateRequiresInternet {
+    task_id: task.id,
+    old_value: false,
+    new_value: true,
+    storage: Rc::clone(&storage),
+  });
+  assert!(storage.find_task(task.id).unwrap().requires_internet);
+
+  action.unexecute().unwrap();
+  assert!(!storage.find_task(task.id).unwrap().requires_internet);
+}
+
+#[test]
+fn update_title_round_trip() {
+  let storage = common::storage();
+  let task = storage.create_task("Charge Kate money").unwrap();
+
+  let mut action = run(TaskUpdateAction::UpdateTaskTitle {
+    task_id: task.id,
+    old_value: task.title.clone(),
+    new_value: String::from("Charge Kate money for tickets"),
+    storage: Rc::clone(&storage),
+  });
+  assert_eq!(
+    storage.find_task(task.id).unwrap().title,
+    "Charge Kate money for tickets"
+  );
+
+  action.unexecute().unwrap();
+  assert_eq!(storage.find_task(task.id).unwrap().title, "Charge Kate money");
+}
+
+#[test]
+fn update_recurrence_round_trip() {
+  let storage = common::storage();
+  let task = storage.create_task("Buy a new balloon").unwrap();
+
+  let mut action = run(TaskUpdateAction::UpdateRecurrence {
+    task_id: task.id,
+    old_value: None,
+    new_value: Some(TaskRecurrence::EveryNDays { num_days: 3 }),
+    storage: Rc::clone(&storage),
+  });
+  assert_eq!(
+    storage.find_task(task.id).unwrap().recurrence(),
+    Some(TaskRecurrence::EveryNDays { num_days: 3 })
+  );
+
+  action.unexecute().unwrap();
+  assert_eq!(storage.find_task(task.id).unwrap().recurrence(), None);
+}
+
+#[test]
+fn update_status_records_an_event() {
+  let storage = common::storage();
+  let task = storage.create_task("Get coffee with employees").unwrap();
+
+  let mut action = run(TaskUpdateAction::UpdateStatus {
+    task_id: task.id,
+    old_value: TaskStatus::AvailableToPerform,
+    new_value: TaskStatus::Completed,
+    task_event: None,
+    spawned_task: None,
+    storage: Rc::clone(&storage),
+  });
+  assert_eq!(storage.find_task(task.id).unwrap().status, TaskStatus::Completed);
+  assert_eq!(
+    event_types(&storage, task.id),
+    vec![TaskEventType::TaskCompleted]
+  );
+  assert!(available_task_ids(&storage).is_empty());
+
+  action.unexecute().unwrap();
+  assert_eq!(
+    storage.find_task(task.id).unwrap().status,
+    TaskStatus::AvailableToPerform
+  );
+  assert!(event_types(&storage, task.id).is_empty());
+  assert_eq!(available_task_ids(&storage), vec![task.id]);
+
+  action.redo().unwrap();
+  assert_eq!(
+    event_types(&storage, task.id),
+    vec![TaskEventType::TaskCompleted]
+  );
+}
+
+#[test]
+fn completing_a_recurring_task_spawns_the_next_one() {
+  let storage = common::storage();
+  let task = storage.create_task("Water the plants").unwrap();
+  storage.update_recurrence(task.id, Some(TaskRecurrence::Daily)).unwrap();
+
+  let mut action = run(TaskUpdateAction::UpdateStatus {
+    task_id: task.id,
+    old_value: TaskStatus::AvailableToPerform,
+    new_value: TaskStatus::Completed,
+    task_event: None,
+    spawned_task: None,
+    storage: Rc::clone(&storage),
+  });
+  let spawned_task = match &action {
+    TaskAction::TaskUpdate(TaskUpdateAction::UpdateStatus {
+      spawned_task: Some(spawned_task),
+      ..
+    }) => spawned_task.clone(),
+    _ => panic!("Expected the next instance to be spawned"),
+  };
+  assert_eq!(spawned_task.title, task.title);
+  assert_eq!(spawned_task.recurrence(), Some(TaskRecurrence::Daily));
+  // Not due until tomorrow.
+  assert!(spawned_task.created_at > task.created_at);
+  assert!(available_task_ids(&storage).is_empty());
+
+  action.unexecute().unwrap();
+  assert!(storage.find_task(spawned_task.id).unwrap().destroyed);
+
+  action.redo().unwrap();
+  assert!(!storage.find_task(spawned_task.id).unwrap().destroyed);
+}
+
+#[test]
+fn abandoning_a_task_records_an_event() {
+  let storage = common::storage();
+  let task = storage.create_task("Launch lecture on Godel").unwrap();
+
+  let mut action = run(TaskUpdateAction::UpdateStatus {
+    task_id: task.id,
+    old_value: TaskStatus::AvailableToPerform,
+    new_value: TaskStatus::Abandoned,
+    task_event: None,
+    spawned_task: None,
+    storage: Rc::clone(&storage),
+  });
+  assert_eq!(
+    event_types(&storage, task.id),
+    vec![TaskEventType::TaskAbandoned]
+  );
+
+  action.unexecute().unwrap();
+  assert!(event_types(&storage, task.id).is_empty());
+}
+
+#[test]
+fn logged_actions_read_back_the_same() {
+  let storage = common::storage();
+  let task = storage.create_task("Water the plants").unwrap();
+  storage.update_recurrence(task.id, Some(TaskRecurrence::Daily)).unwrap();
+
+  let mut create = TaskAction::CreateTask {
+    task_title: String::from("Repot the fern"),
+    task: None,
+    storage: Rc::clone(&storage),
+  };
+  create.execute().unwrap();
+
+  let actions = vec![
+    create,
+    run(TaskUpdateAction::UpdateRecurrence {
+      task_id: task.id,
+      old_value: Some(TaskRecurrence::Daily),
+      new_value: Some(TaskRecurrence::EveryNDays { num_days: 3 }),
+      storage: Rc::clone(&storage),
+    }),
+    run(TaskUpdateAction::UpdateTaskTitle {
+      task_id: task.id,
+      old_value: String::from("Water the plants"),
+      new_value: String::from("Water the plants | ferns"),
+      storage: Rc::clone(&storage),
+    }),
+    run(TaskUpdateAction::UpdateStatus {
+      task_id: task.id,
+      old_value: TaskStatus::AvailableToPerform,
+      new_value: TaskStatus::Completed,
+      task_event: None,
+      spawned_task: None,
+      storage: Rc::clone(&storage),
+    }),
+  ];
+
+  for action in &actions {
+    let entry = storage.log_action(&action.log_entry()).unwrap();
+    let mut read_back = TaskAction::from_log_entry(&entry, &storage).unwrap();
+    assert_eq!(read_back.log_entry(), action.log_entry());
+    assert_eq!(read_back.description(), action.description());
+
+    // The spawned task comes back too, so it goes away on undo.
+    if let Some(spawned_task_id) = entry.spawned_task_id {
+      read_back.unexecute().unwrap();
+      assert!(storage.find_task(spawned_task_id).unwrap().destroyed);
+    }
+  }
+}
+
+#[test]
+fn unreadable_log_entries_are_errors() {
+  let storage = common::storage();
+  let task = storage.create_task("Review meditation retreats").unwrap();
+
+  let entry = storage
+    .log_action(&NewActionLogEntry {
+      kind: ActionLogKind::UpdatePriority,
+      task_id: task.id,
+      old_value: Some(String::from("Low")),
+      new_value: Some(String::from("Urgent")),
+      task_event_id: None,
+      spawned_task_id: None,
+      parent_id: None,
+      batch_id: None,
+    }).unwrap();
+
+  match TaskAction::from_log_entry(&entry, &storage) {
+    Err(Error::BadActionLogEntry { id }) => assert_eq!(id, entry.id),
+    _ => panic!("Expected a BadActionLogEntry error"),
+  }
+}
@@ -0,0 +1,123 @@
+extern crate chrono;
+extern crate task_manager;
+
+mod common;
+
+use std::collections::HashMap;
+use std::rc::Rc;
+use task_manager::commands::ActiveTasksViewCommand;
+use task_manager::config::Config;
+use task_manager::util::terminal::{HeadlessTerminal, Terminal};
+use task_manager::util::ui::ColorPair;
+use task_manager::util::UserInterface;
+use task_manager::views::{ActiveTasksView, UndoHistoryView, View};
+
+struct Harness {
+  terminal: Rc<HeadlessTerminal>,
+  // The actions in the history need their view to still be around.
+  _tasks_view: Rc<ActiveTasksView>,
+  view: Rc<UndoHistoryView>,
+  task_id: i32,
+}
+
+// Does some work in the active tasks view, then opens the undo history
+// over it.
+fn open_history_after(keys: &str) -> Harness {
+  let storage = common::storage();
+  let task = storage.create_task("Water plants").unwrap();
+
+  let terminal = Rc::new(HeadlessTerminal::new(30, 160));
+  let ui = Rc::new(UserInterface::new(
+    Rc::clone(&terminal) as Rc<dyn Terminal>
+  ));
+  let keymap = ActiveTasksViewCommand::keymap(&HashMap::new()).unwrap();
+  let tasks_view =
+    ActiveTasksView::new(&ui, &storage, &Config::default(), keymap);
+  tasks_view.redraw();
+  terminal.type_keys(keys);
+  while let Some(ch) = terminal.getch() {
+    Rc::clone(&tasks_view).handle_key(ch);
+  }
+
+  let view = UndoHistoryView::new(&ui, &tasks_view.undo_buffer);
+  view.redraw();
+  Harness {
+    terminal,
+    _tasks_view: tasks_view,
+    view,
+    task_id: task.id,
+  }
+}
+
+fn run(terminal: &HeadlessTerminal, view: &Rc<UndoHistoryView>, keys: &str) {
+  terminal.type_keys(keys);
+  while let Some(ch) = terminal.getch() {
+    Rc::clone(view).handle_key(ch);
+  }
+  // What the Application does after `ViewResult::RedrawScreen`.
+  view.redraw();
+}
+
+fn line(terminal: &HeadlessTerminal, needle: &str) -> String {
+  let row = terminal
+    .find_row(needle)
+    .unwrap_or_else(|| panic!("{} is not on screen", needle));
+  terminal.lines()[row].trim().to_string()
+}
+
+#[test]
+fn lists_actions_and_marks_the_current_point() {
+  let harness = open_history_after("rPu");
+  let (terminal, task_id) = (&harness.terminal, harness.task_id);
+
+  assert_eq!(line(terminal, "(start)"), "0 |         | (start)");
+  assert_eq!(
+    line(terminal, "recorded effort"),
+    format!(">   1 | applied | recorded effort on #{}", task_id)
+  );
+  assert_eq!(
+    line(terminal, "priority"),
+    format!("2 | undone  | priority Low\u{2192}Medium on #{}", task_id)
+  );
+  let row = terminal.find_row("recorded effort").unwrap();
+  assert_eq!(terminal.row_color(row), ColorPair::Highlight);
+}
+
+#[test]
+fn enter_undoes_or_redoes_to_the_selected_point() {
+  let harness = open_history_after("rP");
+  let (terminal, view) = (&harness.terminal, &harness.view);
+
+  run(terminal, view, "g\n");
+  assert!(line(terminal, "(start)").starts_with(">   0"));
+  assert!(line(terminal, "recorded effort").contains("| undone "));
+
+  run(terminal, view, "$\n");
+  assert!(line(terminal, "priority").starts_with(">   2"));
+  assert!(line(terminal, "recorded effort").contains("| applied "));
+}
+
+#[test]
+fn later_branches_are_kept_and_can_be_gone_to() {
+  // Effort, priority undone, then a delay instead.
+  let harness = open_history_after("rPul");
+  let (terminal, view) = (&harness.terminal, &harness.view);
+  let task_id = harness.task_id;
+
+  assert_eq!(
+    line(terminal, "priority"),
+    format!("2 | undone  | priority Low\u{2192}Medium on #{}", task_id)
+  );
+  assert_eq!(
+    line(terminal, "delay"),
+    format!(
+      ">   3 | applied | \u{2514} requested delay on #{}",
+      task_id
+    )
+  );
+
+  run(terminal, view, "k\n");
+  assert!(line(terminal, "priority").starts_with(">   2 | applied"));
+  assert!(line(terminal, "delay").contains("| undone "));
+  assert!(line(terminal, "recorded effort").contains("| applied "));
+}